@@ -0,0 +1,126 @@
+//! This module contains the display width computation used for aligning formatted values.
+
+use core::fmt::{self, Alignment, Display, Write};
+
+#[cfg(feature = "unicode-width")]
+use unicode_width::UnicodeWidthChar;
+
+/// Returns the number of terminal columns a character occupies.
+///
+/// East Asian wide and full-width characters take two columns, while combining marks,
+/// control characters and invisible formatting characters take none.
+/// This uses the complete Unicode width tables of the `unicode-width` crate.
+#[cfg(feature = "unicode-width")]
+pub fn char_width(c: char) -> usize {
+    c.width().unwrap_or(0)
+}
+
+/// Returns the number of terminal columns a character occupies.
+///
+/// East Asian wide and full-width characters take two columns, while combining marks,
+/// control characters and invisible formatting characters take none.
+/// Since complete Unicode width tables are large, only these common ranges are covered
+/// and every other character is assumed to take one column.
+/// Enable the `unicode-width` feature to use the complete tables instead.
+#[cfg(not(feature = "unicode-width"))]
+pub fn char_width(c: char) -> usize {
+    match c as u32 {
+        0x00..=0x1F
+        | 0x7F..=0x9F
+        | 0x0300..=0x036F
+        | 0x061C
+        | 0x200B..=0x200F
+        | 0x202A..=0x202E
+        | 0x2060..=0x2064
+        | 0x2066..=0x2069
+        | 0xFE00..=0xFE0F
+        | 0xFEFF => 0,
+        0x1100..=0x115F
+        | 0x231A..=0x231B
+        | 0x2329..=0x232A
+        | 0x2E80..=0x303E
+        | 0x3041..=0x33FF
+        | 0x3400..=0x4DBF
+        | 0x4E00..=0x9FFF
+        | 0xA000..=0xA4CF
+        | 0xA960..=0xA97F
+        | 0xAC00..=0xD7A3
+        | 0xF900..=0xFAFF
+        | 0xFE10..=0xFE19
+        | 0xFE30..=0xFE6F
+        | 0xFF00..=0xFF60
+        | 0xFFE0..=0xFFE6
+        | 0x1F300..=0x1F64F
+        | 0x1F900..=0x1F9FF
+        | 0x20000..=0x2FFFD
+        | 0x30000..=0x3FFFD => 2,
+        _ => 1,
+    }
+}
+
+/// Returns the number of terminal columns a string occupies.
+///
+/// ```
+/// use size_format::display_width;
+///
+/// assert_eq!(display_width("42.0MB"), 6);
+/// assert_eq!(display_width("４２.０ＭＢ"), 11);
+/// assert_eq!(display_width("42,0\u{202F}Mo"), 7);
+/// ```
+pub fn display_width(s: &str) -> usize {
+    s.chars().map(char_width).sum()
+}
+
+/// Sums up the display width of everything written to it.
+struct WidthCounter(usize);
+
+impl Write for WidthCounter {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0 += display_width(s);
+        Ok(())
+    }
+}
+
+/// Returns the display width of the formatted value, or zero if formatting fails.
+pub(crate) fn width_of<T: Display + ?Sized>(value: &T) -> usize {
+    let mut counter = WidthCounter(0);
+
+    match write!(counter, "{}", value) {
+        Ok(()) => counter.0,
+        Err(_) => 0,
+    }
+}
+
+/// Writes the output of `render` padded to the width requested by the formatter.
+///
+/// The output is rendered twice: once to measure its display width and once to write it.
+pub(crate) fn pad<F>(f: &mut fmt::Formatter, default_align: Alignment, render: F) -> fmt::Result
+where
+    F: Fn(&mut dyn Write) -> fmt::Result,
+{
+    let width = match f.width() {
+        Some(width) => width,
+        None => return render(f),
+    };
+
+    let mut counter = WidthCounter(0);
+    render(&mut counter)?;
+
+    let padding = width.saturating_sub(counter.0);
+    let (before, after) = match f.align().unwrap_or(default_align) {
+        Alignment::Left => (0, padding),
+        Alignment::Right => (padding, 0),
+        Alignment::Center => (padding / 2, padding - padding / 2),
+    };
+    let fill = f.fill();
+
+    for _ in 0..before {
+        f.write_char(fill)?;
+    }
+    render(f)?;
+    for _ in 0..after {
+        f.write_char(fill)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,84 @@
+//! This module contains sizes annotated with their exact byte count.
+
+use align;
+use core::fmt::{self, Display};
+use options::{format_with, FormatOptions, PrefixSystem};
+use write_grouped;
+use ByteSize;
+
+/// Displays a size followed by its exact number of bytes ("42.0MB (42,012,345 bytes)").
+///
+/// This is useful where both readability and precision are needed, such as in audit logs.
+/// The precision of the format string applies to the humanized value.
+/// ```
+/// use size_format::{ByteSize, PrefixSystem};
+///
+/// let annotated = ByteSize(42_012_345).annotated().with_prefix_system(PrefixSystem::SI);
+/// assert_eq!(format!("{}", annotated), "42.0MB (42,012,345 bytes)".to_string());
+///
+/// let annotated = ByteSize(1_536).annotated().with_group_separator(".").with_unit_name("Byte");
+/// assert_eq!(format!("{:.2}", annotated), "1.50KiB (1.536 Byte)".to_string());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct AnnotatedSize {
+    /// The size to display.
+    size: ByteSize,
+    /// The prefix system of the humanized value.
+    prefix_system: PrefixSystem,
+    /// The separator between groups of three digits in the exact count.
+    group_separator: &'static str,
+    /// The name of the unit after the exact count.
+    unit_name: &'static str,
+}
+
+impl AnnotatedSize {
+    /// Creates a new annotated size using binary prefixes, commas for grouping and "bytes".
+    pub(crate) fn new(size: ByteSize) -> AnnotatedSize {
+        AnnotatedSize {
+            size,
+            prefix_system: PrefixSystem::Binary,
+            group_separator: ",",
+            unit_name: "bytes",
+        }
+    }
+
+    /// Sets the prefix system of the humanized value.
+    pub fn with_prefix_system(mut self, prefix_system: PrefixSystem) -> AnnotatedSize {
+        self.prefix_system = prefix_system;
+        self
+    }
+
+    /// Sets the separator between groups of three digits in the exact count.
+    ///
+    /// No grouping is done if this is empty.
+    pub fn with_group_separator(mut self, group_separator: &'static str) -> AnnotatedSize {
+        self.group_separator = group_separator;
+        self
+    }
+
+    /// Sets the name of the unit after the exact count.
+    pub fn with_unit_name(mut self, unit_name: &'static str) -> AnnotatedSize {
+        self.unit_name = unit_name;
+        self
+    }
+
+    /// Writes the annotated size without any padding.
+    fn write_unpadded(&self, f: &mut dyn fmt::Write, precision: Option<usize>) -> fmt::Result {
+        let options = FormatOptions::new().with_prefix_system(self.prefix_system);
+
+        format_with(self.size.bytes(), &options).write_unpadded(f, precision)?;
+        f.write_str(" (")?;
+        write_grouped(f, self.size.bytes(), self.group_separator)?;
+        write!(f, " {})", self.unit_name)
+    }
+}
+
+impl Display for AnnotatedSize {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let precision = f.precision();
+
+        align::pad(f, fmt::Alignment::Right, |w| {
+            self.write_unpadded(w, precision)
+        })
+    }
+}
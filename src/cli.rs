@@ -0,0 +1,97 @@
+//! This module contains helpers for parsing sizes in command line interfaces.
+
+use alloc::{format, string::String};
+#[cfg(feature = "clap")]
+use clap::{
+    builder::{TypedValueParser, ValueParserFactory},
+    error::ErrorKind,
+    Arg, Command,
+};
+use parse::suggest_unit;
+#[cfg(feature = "clap")]
+use std::{ffi::OsStr, string::ToString};
+use ByteSize;
+
+/// Parses a size argument such as "2GiB", with an error message meant for the user.
+///
+/// This has the signature expected by argument parsers such as `clap`, so it can be used
+/// directly as a value parser (`#[arg(value_parser = parse_size_arg)]`).
+/// With the `clap` feature, `ByteSize` fields need no explicit value parser at all.
+/// If the unit is written in the wrong case, the message suggests the correct spelling.
+/// ```
+/// use size_format::{parse_size_arg, ByteSize};
+///
+/// assert_eq!(parse_size_arg("2GiB"), Ok(ByteSize(2 << 30)));
+/// assert_eq!(
+///     parse_size_arg("2gib"),
+///     Err("invalid size \"2gib\": unknown prefix at position 1 (did you mean \"2GiB\"?)".to_string())
+/// );
+/// ```
+pub fn parse_size_arg(s: &str) -> Result<ByteSize, String> {
+    s.parse().map_err(|error| match suggest_unit(s) {
+        Some(unit) => {
+            let number = s.trim().trim_end_matches(|c: char| !c.is_ascii_digit());
+            format!(
+                "invalid size {:?}: {} (did you mean {:?}?)",
+                s,
+                error,
+                format!("{}{}", number, unit)
+            )
+        }
+        None => format!("invalid size {:?}: {}", s, error),
+    })
+}
+
+/// The `clap` value parser for `ByteSize` arguments.
+///
+/// It is used automatically for `ByteSize` fields, because `ByteSize` implements `ValueParserFactory`.
+/// Invalid sizes are reported with the messages of `parse_size_arg`.
+/// ```
+/// extern crate clap;
+/// extern crate size_format;
+///
+/// use clap::{value_parser, Arg, Command};
+/// use size_format::ByteSize;
+///
+/// let command = Command::new("cache").arg(Arg::new("size").value_parser(value_parser!(ByteSize)));
+///
+/// let matches = command.clone().try_get_matches_from(["cache", "512MiB"]).unwrap();
+/// assert_eq!(matches.get_one::<ByteSize>("size"), Some(&ByteSize(512 << 20)));
+/// assert!(command.try_get_matches_from(["cache", "512mib"]).is_err());
+/// ```
+#[cfg(feature = "clap")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ByteSizeValueParser;
+
+#[cfg(feature = "clap")]
+impl TypedValueParser for ByteSizeValueParser {
+    type Value = ByteSize;
+
+    fn parse_ref(
+        &self,
+        cmd: &Command,
+        arg: Option<&Arg>,
+        value: &OsStr,
+    ) -> Result<ByteSize, clap::Error> {
+        let value = value
+            .to_str()
+            .ok_or_else(|| clap::Error::new(ErrorKind::InvalidUtf8).with_cmd(cmd))?;
+
+        parse_size_arg(value).map_err(|message| {
+            let arg = arg.map_or_else(|| "...".to_string(), ToString::to_string);
+            cmd.clone().error(
+                ErrorKind::ValueValidation,
+                format!("invalid value for '{}': {}", arg, message),
+            )
+        })
+    }
+}
+
+#[cfg(feature = "clap")]
+impl ValueParserFactory for ByteSize {
+    type Parser = ByteSizeValueParser;
+
+    fn value_parser() -> ByteSizeValueParser {
+        ByteSizeValueParser
+    }
+}
@@ -1,63 +1,507 @@
 //! This module contains all customization options for the formatting.
 
-use generic_array::{typenum::U9, ArrayLength, GenericArray};
+use core::{fmt, marker::PhantomData};
+use DEFAULT_PRECISION;
 
 /// A trait for marker types that represent decimal separators.
+///
+/// Besides the decimal separator itself, this also covers the other locale dependent
+/// parts of a number, so a single type can describe a complete locale.
 pub trait DecimalSeparator {
     /// The separator to use.
-    const SEPARATOR: char;
+    ///
+    /// This may consist of several characters, for example to add directional marks.
+    const SEPARATOR: &'static str;
+
+    /// The separator between groups of three digits in the integer part.
+    ///
+    /// No grouping is done if this is empty.
+    const GROUP_SEPARATOR: &'static str = "";
+
+    /// The spacing between the number and the prefix.
+    const UNIT_SPACING: &'static str = "";
+
+    /// The symbol used for bytes in this locale.
+    ///
+    /// Since the unit is not part of the formatted number, this is meant to be appended by the caller.
+    const BYTE_UNIT: &'static str = "B";
+
+    /// The word used for plain byte counts in this locale.
+    const BYTE_NAME: &'static str = "bytes";
 }
 
 /// Represents a comma separation scheme for numbers (',').
 pub struct CommaSeparated;
 
 impl DecimalSeparator for CommaSeparated {
-    const SEPARATOR: char = ',';
+    const SEPARATOR: &'static str = ",";
 }
 
 /// Represents a point or dot separation scheme for numbers ('.').
 pub struct PointSeparated;
 
 impl DecimalSeparator for PointSeparated {
-    const SEPARATOR: char = '.';
+    const SEPARATOR: &'static str = ".";
+}
+
+/// Represents the German number format ("1.234,5 MB").
+pub struct LocaleDE;
+
+impl DecimalSeparator for LocaleDE {
+    const SEPARATOR: &'static str = ",";
+    const GROUP_SEPARATOR: &'static str = ".";
+    const UNIT_SPACING: &'static str = " ";
+    const BYTE_NAME: &'static str = "Byte";
+}
+
+/// Represents the French number format ("1 234,5 Mo").
+///
+/// Digits are grouped with narrow no-break spaces and bytes are called octets.
+pub struct LocaleFR;
+
+impl DecimalSeparator for LocaleFR {
+    const SEPARATOR: &'static str = ",";
+    const GROUP_SEPARATOR: &'static str = "\u{202F}";
+    const UNIT_SPACING: &'static str = "\u{A0}";
+    const BYTE_UNIT: &'static str = "o";
+    const BYTE_NAME: &'static str = "octets";
+}
+
+/// Represents the Swiss number format ("1'234.5 MB").
+pub struct LocaleCH;
+
+impl DecimalSeparator for LocaleCH {
+    const SEPARATOR: &'static str = ".";
+    const GROUP_SEPARATOR: &'static str = "'";
+    const UNIT_SPACING: &'static str = " ";
+    const BYTE_NAME: &'static str = "Byte";
+}
+
+/// Represents a separation scheme with a space between the number and the prefix ("42.0 M").
+///
+/// All other parts of the number are taken from the wrapped separation scheme.
+/// ```
+/// use size_format::{CommaSeparated, SIPrefixes, SizeFormatter, Spaced};
+///
+/// assert_eq!(
+///     format!("{}B", SizeFormatter::<u64, SIPrefixes, Spaced<CommaSeparated>>::new(42_000_000)),
+///     "42,0 MB".to_string()
+/// );
+/// ```
+pub struct Spaced<Separator: DecimalSeparator>(PhantomData<Separator>);
+
+impl<Separator: DecimalSeparator> DecimalSeparator for Spaced<Separator> {
+    const SEPARATOR: &'static str = Separator::SEPARATOR;
+    const GROUP_SEPARATOR: &'static str = Separator::GROUP_SEPARATOR;
+    const UNIT_SPACING: &'static str = " ";
+    const BYTE_UNIT: &'static str = Separator::BYTE_UNIT;
+    const BYTE_NAME: &'static str = Separator::BYTE_NAME;
+}
+
+/// Describes the spacing between the number and the prefix.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Spacing {
+    /// No spacing at all ("42.0MB").
+    None,
+    /// A regular space ("42.0 MB").
+    Space,
+    /// A no-break space (U+00A0), which keeps the number and unit on one line in HTML.
+    NoBreakSpace,
+    /// A narrow no-break space (U+202F), as mandated by the SI brochure for typeset output.
+    NarrowNoBreakSpace,
+}
+
+impl Spacing {
+    /// Returns the string written between the number and the prefix.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Spacing::None => "",
+            Spacing::Space => " ",
+            Spacing::NoBreakSpace => "\u{A0}",
+            Spacing::NarrowNoBreakSpace => "\u{202F}",
+        }
+    }
+}
+
+/// Describes the letter case of the prefix and unit symbols, since house styles differ.
+///
+/// The case is applied to the prefix and the unit together, as in "kB", "KB", "kb" or "Kb".
+/// Only ASCII letters are changed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum SymbolCase {
+    /// The symbols as defined by the prefix type and the unit ("kB", "KiB").
+    #[default]
+    Standard,
+    /// All letters in upper case ("KB", "KIB").
+    Upper,
+    /// All letters in lower case ("kb", "kib").
+    Lower,
+    /// The first letter in upper case and the others in lower case ("Kb", "Kib").
+    Capitalized,
+}
+
+impl SymbolCase {
+    /// Returns the character in this case, given whether it starts the symbol.
+    fn convert(self, c: char, first: bool) -> char {
+        match self {
+            SymbolCase::Standard => c,
+            SymbolCase::Upper => c.to_ascii_uppercase(),
+            SymbolCase::Lower => c.to_ascii_lowercase(),
+            SymbolCase::Capitalized if first => c.to_ascii_uppercase(),
+            SymbolCase::Capitalized => c.to_ascii_lowercase(),
+        }
+    }
+
+    /// Iterates over the characters of the symbol made up of the parts, in this case.
+    fn chars<'a>(self, parts: &'a [&'a str]) -> impl Iterator<Item = char> + 'a {
+        parts
+            .iter()
+            .flat_map(|part| part.chars())
+            .enumerate()
+            .map(move |(index, c)| self.convert(c, index == 0))
+    }
+
+    /// Writes the symbol made up of the parts in this case.
+    pub(crate) fn write(self, f: &mut dyn fmt::Write, parts: &[&str]) -> fmt::Result {
+        self.chars(parts).try_for_each(|c| f.write_char(c))
+    }
+
+    /// Checks whether `written` is the symbol made up of the parts in this case.
+    pub(crate) fn matches(self, parts: &[&str], written: &str) -> bool {
+        self.chars(parts).eq(written.chars())
+    }
+}
+
+/// Decides whether the unit name after a formatted number is plural.
+///
+/// The number is given as it is written, with the separators of the locale and ASCII digits.
+pub type PluralRule = fn(number: &str) -> bool;
+
+/// The plural rule of English, which uses the singular only for exactly "1".
+///
+/// Numbers with decimal places, such as "1.0", use the plural.
+/// ```
+/// use size_format::english_plural;
+///
+/// assert!(!english_plural("1"));
+/// assert!(english_plural("1.0"));
+/// assert!(english_plural("0"));
+/// ```
+pub fn english_plural(number: &str) -> bool {
+    number != "1"
+}
+
+/// Describes how a number that exceeds the maximum of a clamped formatter is marked.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ClampStyle {
+    /// Puts a greater-than sign before the maximum (">1TB").
+    GreaterThan,
+    /// Puts a plus sign after the number of the maximum ("999+MB").
+    Plus,
 }
 
 /// Abstracts over the types of prefixes possible.
 pub trait PrefixType {
-    /// The number of prefixes in the prefix array.
-    type N: ArrayLength<&'static str>;
-
     /// Returns the size of the prefix used.
     ///
     /// For the metric system for example that would be 1000.
+    ///
+    /// The prefix size must be at least 2, otherwise formatting numbers fails to compile.
+    /// ```compile_fail,E0080
+    /// extern crate size_format;
+    ///
+    /// use size_format::{PointSeparated, PrefixType, SizeFormatter};
+    ///
+    /// struct Unscalable;
+    ///
+    /// impl PrefixType for Unscalable {
+    ///     const PREFIX_SIZE: u32 = 1;
+    ///
+    ///     const PREFIXES: &'static [&'static str] = &["", "x"];
+    /// }
+    ///
+    /// format!("{}", SizeFormatter::<u32, Unscalable, PointSeparated>::new(1));
+    /// ```
     const PREFIX_SIZE: u32;
 
+    /// The precision used if neither the format string nor the formatter specify one.
+    /// ```
+    /// extern crate size_format;
+    ///
+    /// use size_format::{PointSeparated, PrefixType, SizeFormatter};
+    ///
+    /// struct Items;
+    ///
+    /// impl PrefixType for Items {
+    ///     const PREFIX_SIZE: u32 = 1000;
+    ///     const DEFAULT_PRECISION: usize = 0;
+    ///
+    ///     const PREFIXES: &'static [&'static str] = &["", "k", "M"];
+    /// }
+    ///
+    /// # fn main() {
+    /// assert_eq!(format!("{}", SizeFormatter::<u32, Items, PointSeparated>::new(42_500)), "42k".to_string());
+    /// assert_eq!(format!("{:.1}", SizeFormatter::<u32, Items, PointSeparated>::new(42_500)), "42.5k".to_string());
+    /// # }
+    /// ```
+    const DEFAULT_PRECISION: usize = DEFAULT_PRECISION;
+
+    /// The factors between consecutive prefixes, for units that do not scale uniformly.
+    ///
+    /// The entry at index `i` is the number of units of the prefix at `i` in the prefix at `i + 1`.
+    /// Steps without an entry scale by the prefix size, which is all of them by default.
+    /// Every factor must be at least 2.
+    /// ```
+    /// extern crate size_format;
+    ///
+    /// use size_format::{PointSeparated, PrefixType, SizeFormatter};
+    ///
+    /// struct Time;
+    ///
+    /// impl PrefixType for Time {
+    ///     const PREFIX_SIZE: u32 = 1000;
+    ///     const STEP_SIZES: &'static [u32] = &[1000, 60, 60, 24];
+    ///
+    ///     const PREFIXES: &'static [&'static str] = &["ms", "s", "min", "h", "d"];
+    /// }
+    ///
+    /// # fn main() {
+    /// let time = |ms| SizeFormatter::<u64, Time, PointSeparated>::new(ms);
+    ///
+    /// assert_eq!(format!("{}", time(1_500)), "1.5s".to_string());
+    /// assert_eq!(format!("{}", time(90_000)), "1.5min".to_string());
+    /// assert_eq!(format!("{}", time(5_400_000)), "1.5h".to_string());
+    /// assert_eq!(format!("{}", time(129_600_000)), "1.5d".to_string());
+    /// # }
+    /// ```
+    const STEP_SIZES: &'static [u32] = &[];
+
+    /// The index of the prefix that stands for the unit itself.
+    ///
+    /// Prefixes before it are fractions of the unit, such as milli or nano. Numbers are always
+    /// counted in the first prefix, but `SizeFormatter::from_base_units` and
+    /// `SizeFormatter::from_base_fraction` convert from the unit.
+    const BASE_INDEX: usize = 0;
+
     /// Represents the prefixes used by the prefix type.
-    fn prefixes() -> GenericArray<&'static str, Self::N>;
+    ///
+    /// The first prefix is used for unscaled numbers.
+    /// If there are no prefixes at all, numbers are formatted as plain integers.
+    const PREFIXES: &'static [&'static str];
+
+    /// Represents the prefixes written out as words, such as "mega".
+    ///
+    /// These are used when formatting with long names. If there are none, the prefixes are used.
+    /// There may be fewer long prefixes than prefixes, in which case long names only use the
+    /// prefixes that have a long form. More long prefixes than prefixes fail to compile.
+    /// ```compile_fail,E0080
+    /// extern crate size_format;
+    ///
+    /// use size_format::{PointSeparated, PrefixType, SizeFormatter};
+    ///
+    /// struct Mismatched;
+    ///
+    /// impl PrefixType for Mismatched {
+    ///     const PREFIX_SIZE: u32 = 1000;
+    ///
+    ///     const PREFIXES: &'static [&'static str] = &["", "k"];
+    ///     const LONG_PREFIXES: Option<&'static [&'static str]> = Some(&["", "kilo", "mega"]);
+    /// }
+    ///
+    /// format!("{}", SizeFormatter::<u32, Mismatched, PointSeparated>::new(1));
+    /// ```
+    const LONG_PREFIXES: Option<&'static [&'static str]> = None;
 }
 
 /// Represents the prefixes used in the SI system of measurements.
 pub struct SIPrefixes;
 
 impl PrefixType for SIPrefixes {
-    type N = U9;
-
     const PREFIX_SIZE: u32 = 1000;
 
-    fn prefixes() -> GenericArray<&'static str, Self::N> {
-        ["", "k", "M", "G", "T", "P", "E", "Z", "Y"].into()
-    }
+    const PREFIXES: &'static [&'static str] = &["", "k", "M", "G", "T", "P", "E", "Z", "Y"];
+
+    const LONG_PREFIXES: Option<&'static [&'static str]> = Some(&[
+        "", "kilo", "mega", "giga", "tera", "peta", "exa", "zetta", "yotta",
+    ]);
 }
 
 /// Represents the prefixes used for display file sizes using powers of 1024.
 pub struct BinaryPrefixes;
 
 impl PrefixType for BinaryPrefixes {
-    type N = U9;
+    const PREFIX_SIZE: u32 = 1024;
+
+    const PREFIXES: &'static [&'static str] = &["", "Ki", "Mi", "Gi", "Ti", "Pi", "Ei", "Zi", "Yi"];
+
+    const LONG_PREFIXES: Option<&'static [&'static str]> = Some(&[
+        "", "kibi", "mebi", "gibi", "tebi", "pebi", "exbi", "zebi", "yobi",
+    ]);
+}
+
+/// Represents the SI prefixes for bits, with the unit included ("kbit", "Mbit").
+pub struct SIBitPrefixes;
+
+impl PrefixType for SIBitPrefixes {
+    const PREFIX_SIZE: u32 = 1000;
+
+    const PREFIXES: &'static [&'static str] = &[
+        "bit", "kbit", "Mbit", "Gbit", "Tbit", "Pbit", "Ebit", "Zbit", "Ybit",
+    ];
+
+    const LONG_PREFIXES: Option<&'static [&'static str]> = SIPrefixes::LONG_PREFIXES;
+}
 
+/// Represents the binary prefixes for bits, with the unit included ("Kibit", "Mibit").
+pub struct BinaryBitPrefixes;
+
+impl PrefixType for BinaryBitPrefixes {
     const PREFIX_SIZE: u32 = 1024;
 
-    fn prefixes() -> GenericArray<&'static str, Self::N> {
-        ["", "Ki", "Mi", "Gi", "Ti", "Pi", "Ei", "Zi", "Yi"].into()
-    }
+    const PREFIXES: &'static [&'static str] = &[
+        "bit", "Kibit", "Mibit", "Gibit", "Tibit", "Pibit", "Eibit", "Zibit", "Yibit",
+    ];
+
+    const LONG_PREFIXES: Option<&'static [&'static str]> = BinaryPrefixes::LONG_PREFIXES;
+}
+
+/// Represents the JEDEC prefixes, which use SI style symbols for powers of 1024.
+///
+/// This matches the sizes shown by Windows and many legacy tools, where "1KB" means 1024 bytes.
+pub struct JEDECPrefixes;
+
+impl PrefixType for JEDECPrefixes {
+    const PREFIX_SIZE: u32 = 1024;
+
+    const PREFIXES: &'static [&'static str] = &["", "K", "M", "G", "T", "P", "E", "Z", "Y"];
+
+    const LONG_PREFIXES: Option<&'static [&'static str]> = SIPrefixes::LONG_PREFIXES;
+}
+
+/// Represents the SI prefixes including ronna and quetta, which were added in 2022.
+///
+/// This allows `u128` sizes beyond yotta to be formatted with a fitting prefix.
+/// ```
+/// use size_format::{PointSeparated, SIPrefixesExtended, SizeFormatter};
+///
+/// assert_eq!(
+///     format!("{}B", SizeFormatter::<u128, SIPrefixesExtended, PointSeparated>::new(10u128.pow(30))),
+///     "1.0QB".to_string()
+/// );
+/// ```
+pub struct SIPrefixesExtended;
+
+impl PrefixType for SIPrefixesExtended {
+    const PREFIX_SIZE: u32 = 1000;
+
+    const PREFIXES: &'static [&'static str] =
+        &["", "k", "M", "G", "T", "P", "E", "Z", "Y", "R", "Q"];
+
+    const LONG_PREFIXES: Option<&'static [&'static str]> = Some(&[
+        "", "kilo", "mega", "giga", "tera", "peta", "exa", "zetta", "yotta", "ronna", "quetta",
+    ]);
+}
+
+/// Represents the binary prefixes including the binary forms of ronna and quetta.
+///
+/// This allows `u128` sizes beyond yobi to be formatted with a fitting prefix.
+/// ```
+/// use size_format::{BinaryPrefixesExtended, PointSeparated, SizeFormatter};
+///
+/// assert_eq!(
+///     format!("{}B", SizeFormatter::<u128, BinaryPrefixesExtended, PointSeparated>::new(1 << 100)),
+///     "1.0QiB".to_string()
+/// );
+/// ```
+pub struct BinaryPrefixesExtended;
+
+impl PrefixType for BinaryPrefixesExtended {
+    const PREFIX_SIZE: u32 = 1024;
+
+    const PREFIXES: &'static [&'static str] = &[
+        "", "Ki", "Mi", "Gi", "Ti", "Pi", "Ei", "Zi", "Yi", "Ri", "Qi",
+    ];
+
+    const LONG_PREFIXES: Option<&'static [&'static str]> = Some(&[
+        "", "kibi", "mebi", "gibi", "tebi", "pebi", "exbi", "zebi", "yobi", "robi", "quebi",
+    ]);
+}
+
+/// Represents the units of time from nanoseconds up to days.
+///
+/// Numbers are counted in nanoseconds, while the base unit is the second.
+/// The steps above seconds are 60, 60 and 24, so there is no prefix beyond days.
+/// Minutes, hours and days are not prefixes of seconds, so long names go up to seconds.
+/// ```
+/// use size_format::DurationFormatter;
+///
+/// assert_eq!(
+///     format!("{}", DurationFormatter::new(1_500_000).with_long_names("seconds")),
+///     "1.5 milliseconds".to_string()
+/// );
+/// ```
+pub struct DurationUnits;
+
+impl PrefixType for DurationUnits {
+    const PREFIX_SIZE: u32 = 1000;
+    const STEP_SIZES: &'static [u32] = &[1000, 1000, 1000, 60, 60, 24];
+    const BASE_INDEX: usize = 3;
+
+    const PREFIXES: &'static [&'static str] = &["ns", "µs", "ms", "s", "min", "h", "d"];
+
+    const LONG_PREFIXES: Option<&'static [&'static str]> = Some(&["nano", "micro", "milli", ""]);
+}
+
+/// Represents the units of frequency, from hertz up to terahertz.
+///
+/// These are the SI prefixes with the unit included, so formatted frequencies can be parsed back.
+pub struct FrequencyUnits;
+
+impl PrefixType for FrequencyUnits {
+    const PREFIX_SIZE: u32 = 1000;
+
+    const PREFIXES: &'static [&'static str] = &["Hz", "kHz", "MHz", "GHz", "THz"];
+
+    const LONG_PREFIXES: Option<&'static [&'static str]> =
+        Some(&["", "kilo", "mega", "giga", "tera"]);
+}
+
+/// Represents the short scale abbreviations for plain counts, where B stands for billion.
+///
+/// These are common for view counts and similar numbers ("1.2k", "3.4M", "5.6B").
+pub struct ShortScalePrefixes;
+
+impl PrefixType for ShortScalePrefixes {
+    const PREFIX_SIZE: u32 = 1000;
+
+    const PREFIXES: &'static [&'static str] = &["", "k", "M", "B", "T"];
+}
+
+/// Represents the Japanese myriad prefixes, which group by powers of 10000 (万, 億, 兆, 京).
+pub struct JapaneseMyriadPrefixes;
+
+impl PrefixType for JapaneseMyriadPrefixes {
+    const PREFIX_SIZE: u32 = 10_000;
+
+    const PREFIXES: &'static [&'static str] = &["", "万", "億", "兆", "京"];
+}
+
+/// Represents the traditional Chinese myriad prefixes, which group by powers of 10000 (萬, 億, 兆, 京).
+pub struct TraditionalChineseMyriadPrefixes;
+
+impl PrefixType for TraditionalChineseMyriadPrefixes {
+    const PREFIX_SIZE: u32 = 10_000;
+
+    const PREFIXES: &'static [&'static str] = &["", "萬", "億", "兆", "京"];
+}
+
+/// Represents the simplified Chinese myriad prefixes, which group by powers of 10000 (万, 亿, 万亿).
+///
+/// Since 兆 is commonly read as a million in mainland China, 万亿 is used for 10^12 instead.
+pub struct SimplifiedChineseMyriadPrefixes;
+
+impl PrefixType for SimplifiedChineseMyriadPrefixes {
+    const PREFIX_SIZE: u32 = 10_000;
+
+    const PREFIXES: &'static [&'static str] = &["", "万", "亿", "万亿"];
 }
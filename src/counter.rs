@@ -0,0 +1,139 @@
+//! This module contains types for byte counts that change over time.
+
+#[cfg(target_has_atomic = "64")]
+use core::{
+    borrow::Borrow,
+    marker::PhantomData,
+    sync::atomic::{AtomicU64, Ordering},
+};
+use core::{
+    fmt::{self, Display},
+    ops::AddAssign,
+};
+use ByteSize;
+#[cfg(target_has_atomic = "64")]
+use {DecimalSeparator, PrefixType, SizeFormatter};
+
+/// Accumulates a number of bytes, for example while copying data.
+///
+/// The total saturates instead of overflowing.
+/// ```
+/// use size_format::{ByteCounter, ByteSize};
+///
+/// let mut copied = ByteCounter::new();
+/// for chunk in &[vec![0u8; 1_024], vec![0u8; 512]] {
+///     copied += chunk.len();
+/// }
+///
+/// assert_eq!(copied.snapshot(), ByteSize(1_536));
+/// assert_eq!(format!("copied {}", copied), "copied 1.5KiB".to_string());
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ByteCounter {
+    /// The number of bytes counted so far.
+    total: u64,
+}
+
+impl ByteCounter {
+    /// Creates a new counter starting at zero.
+    pub fn new() -> ByteCounter {
+        ByteCounter { total: 0 }
+    }
+
+    /// Returns the number of bytes counted so far.
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    /// Returns the number of bytes counted so far as a size.
+    pub fn snapshot(&self) -> ByteSize {
+        ByteSize(self.total)
+    }
+}
+
+impl AddAssign<u64> for ByteCounter {
+    fn add_assign(&mut self, bytes: u64) {
+        self.total = self.total.saturating_add(bytes);
+    }
+}
+
+impl AddAssign<usize> for ByteCounter {
+    fn add_assign(&mut self, bytes: usize) {
+        *self += bytes as u64;
+    }
+}
+
+impl AddAssign<ByteSize> for ByteCounter {
+    fn add_assign(&mut self, size: ByteSize) {
+        *self += size.0;
+    }
+}
+
+impl Display for ByteCounter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Display::fmt(&self.snapshot(), f)
+    }
+}
+
+/// Formats the current value of an atomic counter every time it is displayed.
+///
+/// The counter can either be borrowed or owned, so it can be embedded directly in statistics.
+/// ```
+/// use size_format::{AtomicSizeFormatter, PointSeparated, SIPrefixes};
+/// use std::sync::atomic::{AtomicU64, Ordering};
+///
+/// let transferred = AtomicU64::new(0);
+/// let live = AtomicSizeFormatter::<_, SIPrefixes, PointSeparated>::new(&transferred);
+///
+/// transferred.fetch_add(1_500_000, Ordering::Relaxed);
+/// assert_eq!(format!("{}B", live), "1.5MB".to_string());
+///
+/// transferred.fetch_add(1_000_000, Ordering::Relaxed);
+/// assert_eq!(format!("{}B", live), "2.5MB".to_string());
+/// ```
+#[cfg(target_has_atomic = "64")]
+pub struct AtomicSizeFormatter<Counter, Prefix, Separator>
+where
+    Counter: Borrow<AtomicU64>,
+    Prefix: PrefixType,
+    Separator: DecimalSeparator,
+{
+    /// The counter to be formatted.
+    counter: Counter,
+    _marker: PhantomData<(Prefix, Separator)>,
+}
+
+#[cfg(target_has_atomic = "64")]
+impl<Counter, Prefix, Separator> AtomicSizeFormatter<Counter, Prefix, Separator>
+where
+    Counter: Borrow<AtomicU64>,
+    Prefix: PrefixType,
+    Separator: DecimalSeparator,
+{
+    /// Creates a new formatter for the given counter.
+    pub fn new(counter: Counter) -> AtomicSizeFormatter<Counter, Prefix, Separator> {
+        AtomicSizeFormatter {
+            counter,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the counter, so it can be updated.
+    pub fn counter(&self) -> &AtomicU64 {
+        self.counter.borrow()
+    }
+}
+
+#[cfg(target_has_atomic = "64")]
+impl<Counter, Prefix, Separator> Display for AtomicSizeFormatter<Counter, Prefix, Separator>
+where
+    Counter: Borrow<AtomicU64>,
+    Prefix: PrefixType,
+    Separator: DecimalSeparator,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let value = self.counter().load(Ordering::Relaxed);
+
+        Display::fmt(&SizeFormatter::<u64, Prefix, Separator>::new(value), f)
+    }
+}
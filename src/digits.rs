@@ -0,0 +1,152 @@
+//! This module contains digit sets for rendering numbers in scripts other than ASCII.
+
+use align;
+use core::{
+    fmt::{self, Alignment, Display, Write},
+    marker::PhantomData,
+};
+
+/// A trait for marker types that represent a set of digits.
+pub trait DigitSet {
+    /// Maps a character of the formatted output to this digit set.
+    ///
+    /// Characters that have no counterpart in the digit set are returned unchanged.
+    fn map(c: char) -> char;
+}
+
+/// Returns the character `offset` places after `base`.
+fn shifted(base: u32, offset: u8) -> char {
+    core::char::from_u32(base + u32::from(offset)).unwrap()
+}
+
+/// Represents the ASCII digits ('0' to '9'), which are used by default.
+pub struct AsciiDigits;
+
+impl DigitSet for AsciiDigits {
+    fn map(c: char) -> char {
+        c
+    }
+}
+
+/// Represents the full-width forms used in CJK texts ("４２.０ＭＢ").
+///
+/// Besides the digits, this also converts ASCII letters, so prefixes and units match the digits.
+pub struct FullWidthDigits;
+
+impl DigitSet for FullWidthDigits {
+    fn map(c: char) -> char {
+        match c {
+            '0'..='9' => shifted(0xFF10, c as u8 - b'0'),
+            'A'..='Z' => shifted(0xFF21, c as u8 - b'A'),
+            'a'..='z' => shifted(0xFF41, c as u8 - b'a'),
+            _ => c,
+        }
+    }
+}
+
+/// Represents the Arabic-Indic digits ("٤٢").
+pub struct ArabicIndicDigits;
+
+impl DigitSet for ArabicIndicDigits {
+    fn map(c: char) -> char {
+        match c {
+            '0'..='9' => shifted(0x0660, c as u8 - b'0'),
+            _ => c,
+        }
+    }
+}
+
+/// Represents the Devanagari digits ("४२").
+pub struct DevanagariDigits;
+
+impl DigitSet for DevanagariDigits {
+    fn map(c: char) -> char {
+        match c {
+            '0'..='9' => shifted(0x0966, c as u8 - b'0'),
+            _ => c,
+        }
+    }
+}
+
+/// Displays a value with its digits replaced by those of another digit set.
+///
+/// This works with any displayable value, so it composes with all formatters.
+/// ```
+/// use size_format::{FullWidthDigits, SizeFormatterSI, WithDigits};
+///
+/// assert_eq!(
+///     format!(
+///         "{}",
+///         WithDigits::<_, FullWidthDigits>::new(format_args!("{}B", SizeFormatterSI::new(42_000_000)))
+///     ),
+///     "４２.０ＭＢ".to_string()
+/// );
+/// ```
+pub struct WithDigits<T, Digits>
+where
+    T: Display,
+    Digits: DigitSet,
+{
+    /// The value to be displayed.
+    inner: T,
+    _marker: PhantomData<Digits>,
+}
+
+impl<T, Digits> WithDigits<T, Digits>
+where
+    T: Display,
+    Digits: DigitSet,
+{
+    /// Wraps the value, so it is displayed using the digit set.
+    pub fn new(inner: T) -> WithDigits<T, Digits> {
+        WithDigits {
+            inner,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T, Digits> Display for WithDigits<T, Digits>
+where
+    T: Display,
+    Digits: DigitSet,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let precision = f.precision();
+
+        // The padding is done here, so the width of the mapped digits is taken into account.
+        align::pad(f, Alignment::Right, |w| {
+            let mut writer = MappedWriter::new(w, Digits::map);
+
+            match precision {
+                Some(precision) => write!(writer, "{:.*}", precision, self.inner),
+                None => write!(writer, "{}", self.inner),
+            }
+        })
+    }
+}
+
+/// Passes all written characters through a mapping before writing them to the inner writer.
+pub(crate) struct MappedWriter<'a, W: 'a + ?Sized> {
+    /// The writer that receives the mapped characters.
+    inner: &'a mut W,
+    /// The mapping to apply to every character.
+    map: fn(char) -> char,
+}
+
+impl<'a, W: Write + ?Sized> MappedWriter<'a, W> {
+    /// Creates a writer that maps all characters before writing them to `inner`.
+    pub(crate) fn new(inner: &'a mut W, map: fn(char) -> char) -> MappedWriter<'a, W> {
+        MappedWriter { inner, map }
+    }
+}
+
+impl<'a, W: Write + ?Sized> Write for MappedWriter<'a, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            self.inner.write_char((self.map)(c))?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,80 @@
+//! This module contains the lossless string representation of sizes.
+
+use align;
+use core::{
+    fmt::{self, Display},
+    str::FromStr,
+};
+use parse::{parse_bytes, ParseSizeError};
+use {BinaryPrefixes, ByteSize, PrefixType};
+
+/// Formats a size with as many decimal places as needed to represent it exactly.
+///
+/// The largest binary prefix not exceeding the size is used, like for `ByteSize`. Since every
+/// fraction of a power of 1024 has a finite decimal expansion, no precision is lost, so parsing
+/// the formatted size always returns the original size. This makes it suitable for sizes in
+/// human editable files.
+/// ```
+/// use size_format::{ByteSize, ExactSize};
+///
+/// let size = ByteSize(1_000_001);
+/// assert_eq!(size.exact().to_string(), "976.5634765625KiB".to_string());
+/// assert_eq!(format!("{:.1}", size), "976.5KiB".to_string());
+///
+/// let parsed: ExactSize = "976.5634765625KiB".parse().unwrap();
+/// assert_eq!(parsed.size(), size);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ExactSize(ByteSize);
+
+impl ExactSize {
+    /// Creates the exact representation of the size.
+    pub(crate) fn new(size: ByteSize) -> ExactSize {
+        ExactSize(size)
+    }
+
+    /// Returns the represented size.
+    pub fn size(self) -> ByteSize {
+        self.0
+    }
+
+    /// Writes the size without any padding.
+    fn write_unpadded(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        let bytes = u128::from(self.0.bytes());
+        let mut index = 0;
+        while index + 1 < BinaryPrefixes::PREFIXES.len() && bytes >> (10 * (index + 1)) != 0 {
+            index += 1;
+        }
+
+        let shift = 10 * index as u32;
+        write!(f, "{}", bytes >> shift)?;
+
+        // Each step produces one digit, until the remainder is used up.
+        let mut remainder = bytes & ((1 << shift) - 1);
+        if remainder != 0 {
+            f.write_char('.')?;
+        }
+        while remainder != 0 {
+            remainder *= 10;
+            write!(f, "{}", remainder >> shift)?;
+            remainder &= (1 << shift) - 1;
+        }
+
+        write!(f, "{}B", BinaryPrefixes::PREFIXES[index])
+    }
+}
+
+impl Display for ExactSize {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        align::pad(f, fmt::Alignment::Right, |w| self.write_unpadded(w))
+    }
+}
+
+impl FromStr for ExactSize {
+    type Err = ParseSizeError;
+
+    /// Parses a size like `ByteSize::from_str`.
+    fn from_str(s: &str) -> Result<ExactSize, ParseSizeError> {
+        parse_bytes(s).map(|bytes| ExactSize(ByteSize(bytes)))
+    }
+}
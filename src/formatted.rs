@@ -0,0 +1,127 @@
+//! This module contains an owned, stack allocated formatted size.
+
+use align;
+use core::{
+    fmt::{self, Debug, Display, Write},
+    hash::{Hash, Hasher},
+    ops::Deref,
+    str,
+};
+
+/// An owned string holding a formatted size, without requiring an allocator.
+///
+/// The capacity is large enough for any number formatted with the built-in prefix systems and
+/// locales at their maximum precision, including `u128` values, a sign, a clamp marker and an
+/// approximation marker of up to three bytes. Units are not included.
+/// Creating it fails if the formatted value does not fit.
+/// ```
+/// use size_format::{FormattedSize, SizeFormatterSI};
+///
+/// let formatted = FormattedSize::new(format_args!("{:.2}B", SizeFormatterSI::new(42_420_000))).unwrap();
+///
+/// assert_eq!(&*formatted, "42.42MB");
+/// assert_eq!(formatted.len(), 7);
+/// ```
+#[derive(Clone, Copy)]
+pub struct FormattedSize {
+    /// The UTF-8 encoded formatted value, followed by unused bytes.
+    bytes: [u8; FormattedSize::CAPACITY],
+    /// The number of bytes used.
+    len: usize,
+}
+
+/// The number of digits of `u128::MAX`, plus one for rounding up to the next power of ten.
+const MAX_DIGITS: usize = 40;
+/// The number of group separators in the integer part of `u128::MAX`.
+const MAX_GROUPS: usize = 12;
+/// The longest group separator of the built-in locales, a narrow no-break space.
+const GROUP_SEPARATOR_LEN: usize = 3;
+/// The longest decimal separator of the built-in locales.
+const DECIMAL_SEPARATOR_LEN: usize = 1;
+/// The longest spacing between number and prefix of the built-in locales, a no-break space.
+const SPACING_LEN: usize = 2;
+/// The longest prefix of the built-in prefix systems, such as "万亿" or "quetta".
+const PREFIX_LEN: usize = 6;
+/// The plus sign, the clamp marker and an approximation marker such as "≈".
+const MARKERS_LEN: usize = 1 + 1 + 3;
+
+impl FormattedSize {
+    /// The maximum number of bytes a formatted size can have.
+    pub const CAPACITY: usize = MAX_DIGITS
+        + MAX_GROUPS * GROUP_SEPARATOR_LEN
+        + DECIMAL_SEPARATOR_LEN
+        + SPACING_LEN
+        + PREFIX_LEN
+        + MARKERS_LEN;
+
+    /// Formats the given value into a new formatted size.
+    pub fn new<T: Display>(value: T) -> Result<FormattedSize, fmt::Error> {
+        let mut formatted = FormattedSize {
+            bytes: [0; FormattedSize::CAPACITY],
+            len: 0,
+        };
+
+        write!(formatted, "{}", value)?;
+
+        Ok(formatted)
+    }
+
+    /// Returns the formatted size as a string slice.
+    pub fn as_str(&self) -> &str {
+        str::from_utf8(&self.bytes[..self.len]).expect("only complete strings are written")
+    }
+}
+
+impl Write for FormattedSize {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self.len + s.len();
+        if end > FormattedSize::CAPACITY {
+            return Err(fmt::Error);
+        }
+
+        self.bytes[self.len..end].copy_from_slice(s.as_bytes());
+        self.len = end;
+
+        Ok(())
+    }
+}
+
+impl Deref for FormattedSize {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<str> for FormattedSize {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl Display for FormattedSize {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        align::pad(f, fmt::Alignment::Left, |w| w.write_str(self.as_str()))
+    }
+}
+
+impl Debug for FormattedSize {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl PartialEq for FormattedSize {
+    fn eq(&self, other: &FormattedSize) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Eq for FormattedSize {}
+
+impl Hash for FormattedSize {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state);
+    }
+}
@@ -0,0 +1,77 @@
+//! This module contains the trait for getting the size of common types.
+
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec::Vec};
+#[cfg(feature = "std")]
+use std::{fs, io};
+use ByteSize;
+
+/// Returns the size of a value in bytes, ready to be displayed.
+/// ```
+/// use size_format::HumanSize;
+///
+/// let buffer = [0u8; 1_536];
+///
+/// assert_eq!(format!("{}", buffer[..].human_size()), "1.5KiB".to_string());
+/// assert_eq!(format!("{}", "größe".human_size()), "7B".to_string());
+/// ```
+pub trait HumanSize {
+    /// The type of the size, which may indicate that the size could not be determined.
+    type Output;
+
+    /// Returns the size of the value in bytes.
+    fn human_size(&self) -> Self::Output;
+}
+
+impl HumanSize for [u8] {
+    type Output = ByteSize;
+
+    fn human_size(&self) -> ByteSize {
+        ByteSize(self.len() as u64)
+    }
+}
+
+impl HumanSize for str {
+    type Output = ByteSize;
+
+    fn human_size(&self) -> ByteSize {
+        ByteSize(self.len() as u64)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl HumanSize for Vec<u8> {
+    type Output = ByteSize;
+
+    fn human_size(&self) -> ByteSize {
+        self[..].human_size()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl HumanSize for String {
+    type Output = ByteSize;
+
+    fn human_size(&self) -> ByteSize {
+        self[..].human_size()
+    }
+}
+
+#[cfg(feature = "std")]
+impl HumanSize for fs::Metadata {
+    type Output = ByteSize;
+
+    fn human_size(&self) -> ByteSize {
+        ByteSize(self.len())
+    }
+}
+
+#[cfg(feature = "std")]
+impl HumanSize for fs::File {
+    type Output = io::Result<ByteSize>;
+
+    /// Returns the size of the file, which fails if its metadata cannot be read.
+    fn human_size(&self) -> io::Result<ByteSize> {
+        self.metadata().map(|metadata| metadata.human_size())
+    }
+}
@@ -0,0 +1,57 @@
+//! This module contains the integration with `std::io`.
+
+use core::fmt;
+use options::format_with;
+use std::io;
+use FormatOptions;
+
+/// Writes a byte size directly into an `io::Write`, without allocating an intermediate string.
+///
+/// The size is written like `format_with`, so binary prefixes are used unless the options
+/// choose another prefix system.
+/// ```
+/// use size_format::{write_size, FormatOptions, Locale};
+///
+/// let mut output = Vec::new();
+/// write_size(&mut output, 1_536, &FormatOptions::new()).unwrap();
+///
+/// let options = FormatOptions::new().with_locale(Locale::from_tag("fr").unwrap());
+/// write_size(&mut output, 1_536, &options).unwrap();
+///
+/// assert_eq!(output, "1.5KiB1,5\u{A0}Kio".as_bytes());
+/// ```
+pub fn write_size<W: io::Write + ?Sized>(
+    w: &mut W,
+    n: u64,
+    options: &FormatOptions,
+) -> io::Result<()> {
+    let mut writer = IoWriter {
+        inner: w,
+        error: None,
+    };
+
+    let result = format_with(n, options).write_unpadded(&mut writer, None);
+
+    match (result, writer.error) {
+        (Ok(()), _) => Ok(()),
+        (Err(_), Some(error)) => Err(error),
+        (Err(_), None) => Err(io::Error::other("formatting the size failed")),
+    }
+}
+
+/// Forwards formatted output to an `io::Write`, keeping the first error that occurred.
+struct IoWriter<'a, W: 'a + io::Write + ?Sized> {
+    /// The writer that receives the output.
+    inner: &'a mut W,
+    /// The error returned by the inner writer, since `fmt::Error` cannot carry it.
+    error: Option<io::Error>,
+}
+
+impl<'a, W: io::Write + ?Sized> fmt::Write for IoWriter<'a, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.inner.write_all(s.as_bytes()).map_err(|error| {
+            self.error = Some(error);
+            fmt::Error
+        })
+    }
+}
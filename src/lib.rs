@@ -18,7 +18,41 @@
 //! );
 //! ```
 //!
-//! The precision can also be specified. Please note that values are always rounded down.
+//! Sizes beyond the range of `u64` can be formatted using the `u128` aliases.
+//! ```
+//! use size_format::{SizeFormatterBinary128, SizeFormatterSI128};
+//!
+//! assert_eq!(
+//!     format!("{}B", SizeFormatterBinary128::new(1 << 70)),
+//!     "1.0ZiB".to_string()
+//! );
+//! assert_eq!(
+//!     format!("{}B", SizeFormatterSI128::new(20_000_000_000_000_000_000_000)),
+//!     "20.0ZB".to_string()
+//! );
+//! ```
+//!
+//! The `Extended` variants additionally use the prefixes ronna and quetta, which were added in 2022.
+//! ```
+//! use size_format::SizeFormatterSIExtended128;
+//!
+//! assert_eq!(
+//!     format!("{}B", SizeFormatterSIExtended128::new(2_000_000_000_000_000_000_000_000_000)),
+//!     "2.0RB".to_string()
+//! );
+//! ```
+//!
+//! The unit can also be carried by the formatter, instead of being appended to the output.
+//! ```
+//! use size_format::SizeFormatterSI;
+//!
+//! assert_eq!(
+//!     format!("{}", SizeFormatterSI::new(42_000_000).with_unit("B")),
+//!     "42.0MB".to_string()
+//! );
+//! ```
+//!
+//! The precision can also be specified. Please note that values are rounded down by default.
 //! ```
 //! use size_format::SizeFormatterSI;
 //!
@@ -57,26 +91,30 @@
 //! );
 //! ```
 //!
+//! Ready-made locales additionally group the digits and put a space between the number and the prefix.
+//! ```
+//! use size_format::{LocaleDE, SIPrefixes, SizeFormatter};
+//!
+//! assert_eq!(
+//!     format!("{}B", SizeFormatter::<u64, SIPrefixes, LocaleDE>::new(42_500_000)),
+//!     "42,5 MB".to_string()
+//! );
+//! ```
+//!
 //! Although this crate was mainly intended for data sizes, it can also be used for other units.
 //!
 //! It is also possible to implement the `PrefixType` trait to make your own prefix system.
 //! ```
-//! extern crate generic_array;
 //! extern crate size_format;
 //!
 //! use size_format::{PointSeparated, PrefixType, SizeFormatter};
-//! use generic_array::{typenum::U3, GenericArray};
 //!
 //! struct Millimeter;
 //!
 //! impl PrefixType for Millimeter {
-//!     type N = U3;
-//!
 //!     const PREFIX_SIZE: u32 = 1000;
 //!
-//!     fn prefixes() -> GenericArray<&'static str, Self::N> {
-//!         ["m", "", "k"].into()
-//!     }
+//!     const PREFIXES: &'static [&'static str] = &["m", "", "k"];
 //! }
 //!
 //! assert_eq!(
@@ -100,23 +138,153 @@
 #![no_std]
 #![warn(missing_docs)]
 
-extern crate generic_array;
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "bytemuck")]
+extern crate bytemuck;
+#[cfg(feature = "clap")]
+extern crate clap;
+#[cfg(feature = "nom")]
+extern crate nom;
 extern crate num;
+#[cfg(feature = "rand")]
+extern crate rand;
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(any(test, feature = "std"))]
+#[cfg_attr(test, macro_use)]
+extern crate std;
+#[cfg(feature = "unicode-width")]
+extern crate unicode_width;
+#[cfg(feature = "winnow")]
+extern crate winnow;
+#[cfg(feature = "zerocopy")]
+extern crate zerocopy;
 
 use core::{
     cmp,
     fmt::{self, Display},
     marker::PhantomData,
+    str::FromStr,
+    time::Duration,
 };
-use num::{integer::Integer, rational::Ratio, traits::cast::FromPrimitive, traits::Pow};
+use digits::MappedWriter;
+use math::{bytes_in, int_log, step_size, Rounding};
+#[cfg(feature = "float")]
+use num::traits::cast::ToPrimitive;
+use num::{
+    integer::Integer,
+    rational::Ratio,
+    traits::cast::FromPrimitive,
+    traits::{CheckedAdd, CheckedMul, Pow},
+};
+use parse::parse_number;
 
+mod align;
+mod annotated;
+#[cfg(feature = "alloc")]
+mod cli;
 mod config;
+mod counter;
+mod digits;
+mod exact;
+mod formatted;
+mod human;
+#[cfg(feature = "std")]
+mod io;
+mod list;
+mod live;
+mod locale;
+#[macro_use]
+mod macros;
+pub mod math;
+mod options;
+mod parse;
+pub mod parser;
+#[cfg(feature = "alloc")]
+mod preserved;
+pub mod presets;
+#[cfg(feature = "rand")]
+mod random;
+mod rate;
+mod renormalize;
+#[cfg(feature = "float")]
+mod representations;
+#[cfg(feature = "float")]
+mod scaled;
+#[cfg(feature = "serde")]
+mod serialization;
+mod size;
+mod sortable;
+mod stats;
+#[cfg(feature = "alloc")]
+mod string;
+mod summary;
+mod time;
+#[cfg(feature = "std")]
+mod tracking;
+#[cfg(kani)]
+mod verification;
 
+pub use self::align::{char_width, display_width};
+pub use self::annotated::AnnotatedSize;
+#[cfg(feature = "alloc")]
+pub use self::cli::parse_size_arg;
+#[cfg(feature = "clap")]
+pub use self::cli::ByteSizeValueParser;
 pub use self::config::{
-    BinaryPrefixes, CommaSeparated, DecimalSeparator, PointSeparated, PrefixType, SIPrefixes,
+    english_plural, BinaryBitPrefixes, BinaryPrefixes, BinaryPrefixesExtended, ClampStyle,
+    CommaSeparated, DecimalSeparator, DurationUnits, FrequencyUnits, JEDECPrefixes,
+    JapaneseMyriadPrefixes, LocaleCH, LocaleDE, LocaleFR, PluralRule, PointSeparated, PrefixType,
+    SIBitPrefixes, SIPrefixes, SIPrefixesExtended, ShortScalePrefixes,
+    SimplifiedChineseMyriadPrefixes, Spaced, Spacing, SymbolCase, TraditionalChineseMyriadPrefixes,
 };
+#[cfg(target_has_atomic = "64")]
+pub use self::counter::AtomicSizeFormatter;
+pub use self::counter::ByteCounter;
+pub use self::digits::{
+    ArabicIndicDigits, AsciiDigits, DevanagariDigits, DigitSet, FullWidthDigits, WithDigits,
+};
+pub use self::exact::ExactSize;
+pub use self::formatted::FormattedSize;
+pub use self::human::HumanSize;
+#[cfg(feature = "std")]
+pub use self::io::write_size;
+pub use self::list::{SizeList, SizeListBinary, SizeListSI};
+pub use self::live::{
+    PrefixHysteresis, PrefixHysteresisBinary, PrefixHysteresisSI, StableWidth, WidthTracker,
+};
+pub use self::locale::Locale;
+pub use self::options::{
+    format_with, FormatOptions, NoPrefixSystem, PrefixSystem, RuntimePrefixes, SizeWithOptions,
+};
+pub use self::parse::{
+    parse_partial, parse_prefixed, parse_range, parse_size, parse_size_const, suggest_unit,
+    ParseErrorKind, ParseMode, ParseSizeError, PrefixPolicy, SizeParser,
+};
+#[cfg(feature = "alloc")]
+pub use self::preserved::PreservedSize;
+#[cfg(feature = "rand")]
+pub use self::random::{LogUniform, UniformByteSize};
+pub use self::rate::{
+    CounterReset, RateFormatter, RateFormatterBinary, RateFormatterSI, RateTracker,
+};
+pub use self::renormalize::{renormalize, Renormalized};
+#[cfg(feature = "float")]
+pub use self::representations::Representations;
+#[cfg(feature = "float")]
+pub use self::scaled::ScaledSize;
+pub use self::size::{ByteSize, Granularity};
+pub use self::sortable::SortableSize;
+pub use self::stats::{SizeStats, SizeStatsBinary, SizeStatsSI};
+#[cfg(feature = "alloc")]
+pub use self::string::ToSizeString;
+pub use self::summary::{TransferSummary, TransferSummaryBinary, TransferSummarySI};
+pub use self::time::ElapsedFormatter;
+#[cfg(feature = "std")]
+pub use self::tracking::TrackingAllocator;
 
-/// The precision to use by default for formatting the numbers.
+/// The precision to use by default for formatting the numbers, unless the prefix type overrides it.
 const DEFAULT_PRECISION: usize = 1;
 
 /// Implements `Display` to format the contained byte size using SI prefixes.
@@ -125,8 +293,157 @@ pub type SizeFormatterSI = SizeFormatter<u64, SIPrefixes, PointSeparated>;
 /// Implements `Display` to format the contained byte size using binary prefixes.
 pub type SizeFormatterBinary = SizeFormatter<u64, BinaryPrefixes, PointSeparated>;
 
+/// Implements `Display` to format the contained byte size using JEDEC prefixes.
+///
+/// These are powers of 1024 written with SI style symbols, as in Windows.
+/// ```
+/// use size_format::SizeFormatterJEDEC;
+///
+/// assert_eq!(format!("{}B", SizeFormatterJEDEC::new(1_536)), "1.5KB".to_string());
+/// assert_eq!(format!("{}B", SizeFormatterJEDEC::new(5 << 30)), "5.0GB".to_string());
+/// ```
+pub type SizeFormatterJEDEC = SizeFormatter<u64, JEDECPrefixes, PointSeparated>;
+
+/// Implements `Display` to format the contained byte size using SI prefixes and a decimal comma.
+/// ```
+/// use size_format::SizeFormatterSIComma;
+///
+/// assert_eq!(format!("{}B", SizeFormatterSIComma::new(1_500)), "1,5kB".to_string());
+/// ```
+pub type SizeFormatterSIComma = SizeFormatter<u64, SIPrefixes, CommaSeparated>;
+
+/// Implements `Display` to format the contained byte size using binary prefixes and a decimal comma.
+pub type SizeFormatterBinaryComma = SizeFormatter<u64, BinaryPrefixes, CommaSeparated>;
+
+/// Implements `Display` to format the contained byte size using JEDEC prefixes and a decimal comma.
+pub type SizeFormatterJEDECComma = SizeFormatter<u64, JEDECPrefixes, CommaSeparated>;
+
+/// Implements `Display` to format the contained number of bits using SI prefixes.
+///
+/// Byte counts can be converted using `from_bytes_as_bits`.
+/// ```
+/// use size_format::SizeFormatterSIBits;
+///
+/// assert_eq!(format!("{}", SizeFormatterSIBits::new(1_500_000)), "1.5Mbit".to_string());
+/// assert_eq!(
+///     format!("{}/s", SizeFormatterSIBits::from_bytes_as_bits(125_000).unwrap()),
+///     "1.0Mbit/s".to_string()
+/// );
+/// ```
+pub type SizeFormatterSIBits = SizeFormatter<u64, SIBitPrefixes, PointSeparated>;
+
+/// Implements `Display` to format the contained number of bits using binary prefixes.
+pub type SizeFormatterBinaryBits = SizeFormatter<u64, BinaryBitPrefixes, PointSeparated>;
+
+/// Implements `Display` to format the contained `u128` number of bits using SI prefixes.
+///
+/// Every `u64` byte count fits into this type when converted to bits.
+pub type SizeFormatterSIBits128 = SizeFormatter<u128, SIBitPrefixes, PointSeparated>;
+
+/// Implements `Display` to format the contained `u128` byte size using SI prefixes.
+///
+/// This can hold sizes beyond the range of `u64`, such as large aggregate totals.
+/// Every `u64` size can be converted into it.
+/// ```
+/// use size_format::SizeFormatterSI128;
+///
+/// let formatter: SizeFormatterSI128 = u64::MAX.into();
+/// assert_eq!(format!("{}B", formatter), "18.4EB".to_string());
+/// ```
+pub type SizeFormatterSI128 = SizeFormatter<u128, SIPrefixes, PointSeparated>;
+
+/// Implements `Display` to format the contained `u128` byte size using binary prefixes.
+///
+/// This can hold sizes beyond the range of `u64`, such as large aggregate totals.
+pub type SizeFormatterBinary128 = SizeFormatter<u128, BinaryPrefixes, PointSeparated>;
+
+/// Implements `Display` to format the contained `u128` byte size using JEDEC prefixes.
+pub type SizeFormatterJEDEC128 = SizeFormatter<u128, JEDECPrefixes, PointSeparated>;
+
+/// Implements `Display` to format the contained `u128` byte size using all SI prefixes up to quetta.
+pub type SizeFormatterSIExtended128 = SizeFormatter<u128, SIPrefixesExtended, PointSeparated>;
+
+/// Implements `Display` to format the contained `u128` byte size using all binary prefixes up to quebi.
+pub type SizeFormatterBinaryExtended128 =
+    SizeFormatter<u128, BinaryPrefixesExtended, PointSeparated>;
+
+/// Implements `Display` to format the contained number of nanoseconds as a duration.
+///
+/// Durations can also be created from seconds using `from_base_units` or from a `Duration`.
+/// ```
+/// use size_format::DurationFormatter;
+/// use std::time::Duration;
+///
+/// assert_eq!(format!("{}", DurationFormatter::new(1_500_000)), "1.5ms".to_string());
+/// assert_eq!(format!("{}", DurationFormatter::new(350)), "350ns".to_string());
+/// assert_eq!(format!("{}", DurationFormatter::from_base_units(138).unwrap()), "2.3min".to_string());
+/// assert_eq!(
+///     format!("{}", DurationFormatter::from_duration(Duration::from_secs(3_960))),
+///     "1.1h".to_string()
+/// );
+/// ```
+pub type DurationFormatter = SizeFormatter<u128, DurationUnits, PointSeparated>;
+
+/// Implements `Display` to abbreviate plain counts using the short scale.
+/// ```
+/// use size_format::CountFormatter;
+///
+/// assert_eq!(format!("{}", CountFormatter::new(1_234)), "1.2k".to_string());
+/// assert_eq!(format!("{}", CountFormatter::new(3_456_789)), "3.4M".to_string());
+/// assert_eq!(format!("{}", CountFormatter::new(5_600_000_000)), "5.6B".to_string());
+/// assert_eq!(format!("{}", CountFormatter::new(42)), "42".to_string());
+/// ```
+pub type CountFormatter = SizeFormatter<u64, ShortScalePrefixes, PointSeparated>;
+
+/// Implements `Display` and `FromStr` for frequencies in hertz.
+/// ```
+/// use size_format::FrequencyFormatter;
+///
+/// assert_eq!(format!("{}", FrequencyFormatter::new(44_100)), "44.1kHz".to_string());
+/// assert_eq!(format!("{}", FrequencyFormatter::new(3_600_000_000)), "3.6GHz".to_string());
+/// assert_eq!(format!("{}", FrequencyFormatter::new(50)), "50Hz".to_string());
+///
+/// let parsed: FrequencyFormatter = "2.4GHz".parse().unwrap();
+/// assert_eq!(format!("{}", parsed), "2.4GHz".to_string());
+/// ```
+pub type FrequencyFormatter = SizeFormatter<u64, FrequencyUnits, PointSeparated>;
+
+/// Implements `Display` to format the contained `usize` byte size using SI prefixes.
+pub type SizeFormatterSIUsize = SizeFormatter<usize, SIPrefixes, PointSeparated>;
+
+/// Implements `Display` to format the contained `usize` byte size using binary prefixes.
+pub type SizeFormatterBinaryUsize = SizeFormatter<usize, BinaryPrefixes, PointSeparated>;
+
+/// Describes the prefix that a formatter chose for its number.
+///
+/// Prefixes compare by their position in the prefix system, so larger prefixes compare greater.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ChosenPrefix {
+    /// The position of the prefix in the prefix system.
+    index: usize,
+    /// The symbol of the prefix.
+    symbol: &'static str,
+}
+
+impl ChosenPrefix {
+    /// Returns the position of the prefix in the prefix system.
+    ///
+    /// This is also the number of times the number was divided by the prefix size.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the symbol of the prefix.
+    pub fn symbol(&self) -> &'static str {
+        self.symbol
+    }
+}
+
 /// Represents a size that can be formatted.
 ///
+/// The width, fill and alignment of the format string are supported, where the width
+/// is measured in terminal columns (see `display_width`). Sizes are right aligned by default.
+///
 /// # Panics
 /// - May panic if the `BaseType` is too small for the prefix specified in `Prefix`
 ///   and the number is being formatted.
@@ -139,6 +456,42 @@ where
 {
     /// The number to be formatted.
     num: BaseType,
+    /// Whether formatting should fail if the number cannot be represented exactly.
+    exact: bool,
+    /// The marker to put before numbers that cannot be represented exactly.
+    approximation_marker: Option<&'static str>,
+    /// The index of the prefix to use instead of choosing one by magnitude.
+    forced_prefix: Option<usize>,
+    /// The indices of the smallest and largest prefix that are chosen by magnitude.
+    prefix_limits: (usize, usize),
+    /// The fraction of the size of a prefix at which it is chosen.
+    threshold: Option<Ratio<u32>>,
+    /// The locale to use instead of the one described by the separator type.
+    locale: Option<Locale>,
+    /// The maximum number to display and how exceeding it is marked.
+    clamp: Option<(BaseType, ClampStyle)>,
+    /// The precision to use if the format string does not specify one.
+    precision: Option<usize>,
+    /// The precision to use for each prefix, if no other precision is specified.
+    prefix_precisions: &'static [usize],
+    /// Whether zeros at the end of the fractional part are left out.
+    trim_zeros: bool,
+    /// Whether positive numbers are preceded by a plus sign.
+    plus_sign: bool,
+    /// The singular and plural name of the unit, if prefixes are written as words.
+    long_unit: Option<(&'static str, &'static str)>,
+    /// The symbol of the unit written after the prefix.
+    unit: &'static str,
+    /// The letter case of the prefix and unit symbols.
+    symbol_case: SymbolCase,
+    /// Decides whether the plural name of the unit is used.
+    plural_rule: PluralRule,
+    /// How the number is rounded to the precision, if it is not rounded down.
+    rounding: Option<Rounding>,
+    /// The step in the chosen prefix that the number is rounded to.
+    step: Option<Ratio<u32>>,
+    /// The prefixes to use instead of those of the prefix type, if they are chosen at runtime.
+    runtime_prefixes: Option<RuntimePrefixes>,
     _marker: PhantomData<(Prefix, Separator)>,
 }
 
@@ -153,132 +506,1497 @@ where
     pub fn new(num: BaseType) -> SizeFormatter<BaseType, Prefix, Separator> {
         SizeFormatter {
             num,
+            exact: false,
+            approximation_marker: None,
+            forced_prefix: None,
+            prefix_limits: (0, usize::MAX),
+            threshold: None,
+            locale: None,
+            clamp: None,
+            precision: None,
+            prefix_precisions: &[],
+            trim_zeros: false,
+            plus_sign: false,
+            long_unit: None,
+            unit: "",
+            symbol_case: SymbolCase::Standard,
+            plural_rule: english_plural,
+            rounding: None,
+            step: None,
+            runtime_prefixes: None,
             _marker: PhantomData,
         }
     }
 
     /// Creates a new size formatter from a compatible number.
     pub fn from<T: Into<BaseType>>(num: T) -> SizeFormatter<BaseType, Prefix, Separator> {
-        SizeFormatter {
-            num: num.into(),
-            _marker: PhantomData,
+        SizeFormatter::new(num.into())
+    }
+
+    /// Returns the number to be formatted.
+    pub fn num(&self) -> BaseType {
+        self.num.clone()
+    }
+
+    /// Makes formatting fail if the number cannot be represented exactly with the used precision.
+    ///
+    /// This is useful where a silently truncated value would be wrong, such as in billing.
+    /// Note that `format!` panics if formatting fails, so `write!` should be used instead.
+    /// ```
+    /// use size_format::SizeFormatterSI;
+    /// use std::fmt::Write;
+    ///
+    /// let mut output = String::new();
+    /// assert!(write!(output, "{:.1}B", SizeFormatterSI::new(1_500).require_exact()).is_ok());
+    /// assert_eq!(output, "1.5kB".to_string());
+    ///
+    /// let mut output = String::new();
+    /// assert!(write!(output, "{:.1}B", SizeFormatterSI::new(1_999).require_exact()).is_err());
+    /// ```
+    pub fn require_exact(mut self) -> SizeFormatter<BaseType, Prefix, Separator> {
+        self.exact = true;
+        self
+    }
+
+    /// Puts the marker before the number if it cannot be represented exactly with the used precision.
+    ///
+    /// This tells readers which values lost information through rounding.
+    /// ```
+    /// use size_format::SizeFormatterSI;
+    ///
+    /// let marked = |num| format!("{}B", SizeFormatterSI::new(num).with_approximation_marker("~"));
+    ///
+    /// assert_eq!(marked(1_999_999_999), "~1.9GB".to_string());
+    /// assert_eq!(marked(2_000_000), "2.0MB".to_string());
+    /// ```
+    pub fn with_approximation_marker(
+        mut self,
+        marker: &'static str,
+    ) -> SizeFormatter<BaseType, Prefix, Separator> {
+        self.approximation_marker = Some(marker);
+        self
+    }
+
+    /// Displays numbers above `max` as `max` together with a marker.
+    ///
+    /// This is useful where the true value should not or need not be shown.
+    /// A clamped number is never exact, so it fails with `require_exact` and gets the
+    /// approximation marker.
+    /// ```
+    /// use size_format::{ClampStyle, SizeFormatterSI};
+    ///
+    /// let clamped = |num| SizeFormatterSI::new(num).clamp(1_000_000_000_000, ClampStyle::GreaterThan);
+    /// assert_eq!(format!("{:.0}B", clamped(1_500_000_000_000)), ">1TB".to_string());
+    /// assert_eq!(format!("{:.0}B", clamped(900_000_000_000)), "900GB".to_string());
+    ///
+    /// let clamped = SizeFormatterSI::new(1_500_000_000).clamp(999_000_000, ClampStyle::Plus);
+    /// assert_eq!(format!("{:.0}B", clamped), "999+MB".to_string());
+    /// ```
+    pub fn clamp(
+        mut self,
+        max: BaseType,
+        style: ClampStyle,
+    ) -> SizeFormatter<BaseType, Prefix, Separator> {
+        self.clamp = Some((max, style));
+        self
+    }
+
+    /// Sets the precision that is used if the format string does not specify one.
+    ///
+    /// This is useful where the format string cannot be controlled, such as in logging macros.
+    /// ```
+    /// use size_format::SizeFormatterSI;
+    ///
+    /// let formatter = SizeFormatterSI::new(1_234_567).with_precision(3);
+    ///
+    /// assert_eq!(format!("{}B", formatter), "1.234MB".to_string());
+    /// assert_eq!(format!("{:.1}B", formatter), "1.2MB".to_string());
+    /// ```
+    pub fn with_precision(
+        mut self,
+        precision: usize,
+    ) -> SizeFormatter<BaseType, Prefix, Separator> {
+        self.precision = Some(precision);
+        self
+    }
+
+    /// Never chooses a prefix smaller than the one at `index`.
+    ///
+    /// Smaller numbers are written as fractions of that prefix instead.
+    ///
+    /// # Panics
+    /// - Formatting panics if the size of the prefix does not fit into the base type.
+    /// ```
+    /// use size_format::SizeFormatterSI;
+    ///
+    /// // Index 2 is the prefix for mega.
+    /// assert_eq!(format!("{:.3}B", SizeFormatterSI::new(400_000).with_min_prefix(2)), "0.400MB".to_string());
+    /// assert_eq!(format!("{}B", SizeFormatterSI::new(4_000_000_000).with_min_prefix(2)), "4.0GB".to_string());
+    /// ```
+    pub fn with_min_prefix(mut self, index: usize) -> SizeFormatter<BaseType, Prefix, Separator> {
+        self.prefix_limits.0 = index;
+        self
+    }
+
+    /// Never chooses a prefix larger than the one at `index`.
+    ///
+    /// Larger numbers are written as multiples of that prefix instead.
+    /// ```
+    /// use size_format::SizeFormatterSI;
+    ///
+    /// // Index 4 is the prefix for tera.
+    /// assert_eq!(
+    ///     format!("{}B", SizeFormatterSI::new(2_000_000_000_000_000).with_max_prefix(4)),
+    ///     "2000.0TB".to_string()
+    /// );
+    /// ```
+    pub fn with_max_prefix(mut self, index: usize) -> SizeFormatter<BaseType, Prefix, Separator> {
+        self.prefix_limits.1 = index;
+        self
+    }
+
+    /// Chooses a prefix once the number reaches `threshold` times its size, instead of its size.
+    ///
+    /// A threshold below one switches to the next prefix early, one above keeps the smaller prefix
+    /// for longer. It should be between the reciprocal of the prefix size and the prefix size,
+    /// since the prefix moves by at most one step. It is ignored if it does not fit into the base type.
+    /// ```
+    /// extern crate num;
+    /// extern crate size_format;
+    ///
+    /// use num::rational::Ratio;
+    /// use size_format::SizeFormatterSI;
+    ///
+    /// # fn main() {
+    /// let early = SizeFormatterSI::new(999_000).with_prefix_threshold(Ratio::new(9, 10));
+    /// assert_eq!(format!("{}B", early), "0.9MB".to_string());
+    ///
+    /// let late = SizeFormatterSI::new(1_200_000).with_prefix_threshold(Ratio::new(3, 2));
+    /// assert_eq!(format!("{}B", late), "1200.0kB".to_string());
+    /// # }
+    /// ```
+    pub fn with_prefix_threshold(
+        mut self,
+        threshold: Ratio<u32>,
+    ) -> SizeFormatter<BaseType, Prefix, Separator> {
+        self.threshold = Some(threshold);
+        self
+    }
+
+    /// Sets how the number is rounded to the precision, instead of rounding it down.
+    ///
+    /// If rounding up reaches the size of the next prefix, that prefix is used instead,
+    /// unless the prefix is forced or limited.
+    /// ```
+    /// use size_format::{math::Rounding, SizeFormatterSI};
+    ///
+    /// let rounded = |num, rounding| format!("{}B", SizeFormatterSI::new(num).with_rounding(rounding));
+    ///
+    /// assert_eq!(rounded(1_250_000, Rounding::HalfUp), "1.3MB".to_string());
+    /// assert_eq!(rounded(1_250_000, Rounding::HalfEven), "1.2MB".to_string());
+    /// assert_eq!(rounded(1_350_000, Rounding::HalfEven), "1.4MB".to_string());
+    /// assert_eq!(rounded(1_250_001, Rounding::HalfEven), "1.3MB".to_string());
+    /// ```
+    pub fn with_rounding(
+        mut self,
+        rounding: Rounding,
+    ) -> SizeFormatter<BaseType, Prefix, Separator> {
+        self.rounding = Some(rounding);
+        self
+    }
+
+    /// Rounds the number to a multiple of `step` in the chosen prefix.
+    ///
+    /// The number is rounded to the nearest multiple, unless another rounding is set with
+    /// `with_rounding`. The step must be at most one and its denominator must divide the prefix
+    /// size, for example a half, a quarter or a fifth for SI prefixes. Other steps are ignored.
+    /// Like rounding, reaching the size of the next prefix moves to that prefix.
+    /// ```
+    /// extern crate num;
+    /// extern crate size_format;
+    ///
+    /// use num::rational::Ratio;
+    /// use size_format::SizeFormatterSI;
+    ///
+    /// # fn main() {
+    /// let stepped = |num| {
+    ///     let formatter = SizeFormatterSI::new(num).with_step(Ratio::new(1, 4)).trim_trailing_zeros();
+    ///     format!("{:.2}B", formatter)
+    /// };
+    ///
+    /// assert_eq!(stepped(1_430_000_000), "1.5GB".to_string());
+    /// assert_eq!(stepped(1_800_000_000), "1.75GB".to_string());
+    /// assert_eq!(stepped(1_900_000_000), "2GB".to_string());
+    /// # }
+    /// ```
+    pub fn with_step(mut self, step: Ratio<u32>) -> SizeFormatter<BaseType, Prefix, Separator> {
+        self.step = Some(step);
+        self
+    }
+
+    /// Returns the number divided by the divisor, rounded to a multiple of the step.
+    fn round_to_step(&self, num: BaseType, divisor: BaseType) -> Ratio<BaseType> {
+        let step = match self.step {
+            Some(step) if step <= Ratio::from_integer(1) && *step.numer() != 0 => step,
+            _ => return Ratio::new(num, divisor),
+        };
+        let (numer, denom) = match (
+            BaseType::from_u32(*step.numer()),
+            BaseType::from_u32(*step.denom()),
+        ) {
+            (Some(numer), Some(denom)) if divisor.is_multiple_of(&denom) => (numer, denom),
+            _ => return Ratio::new(num, divisor),
+        };
+
+        // The number of bytes in one step, which is at most the divisor.
+        let unit = divisor.clone() / denom.clone() * numer.clone();
+        let (integer, rem) = num.div_rem(&divisor);
+        let (steps, rem) = rem.div_rem(&unit);
+        let half = rem.cmp(&(unit.clone() - rem.clone()));
+        let round_up = !rem.is_zero()
+            && match self.rounding.unwrap_or(Rounding::HalfUp) {
+                Rounding::Down => false,
+                Rounding::Up => true,
+                Rounding::HalfUp => half != cmp::Ordering::Less,
+                Rounding::HalfEven => {
+                    half == cmp::Ordering::Greater
+                        || (half == cmp::Ordering::Equal && steps.is_odd())
+                }
+            };
+        let steps = if round_up {
+            steps + BaseType::one()
+        } else {
+            steps
+        };
+
+        Ratio::new(integer * denom.clone() + steps * numer, denom)
+    }
+
+    /// Leaves out zeros at the end of the fractional part, so the precision is a maximum.
+    /// ```
+    /// use size_format::SizeFormatterSI;
+    ///
+    /// assert_eq!(format!("{}B", SizeFormatterSI::new(2_000_000).trim_trailing_zeros()), "2MB".to_string());
+    /// assert_eq!(
+    ///     format!("{:.3}B", SizeFormatterSI::new(1_500_000_000).trim_trailing_zeros()),
+    ///     "1.5GB".to_string()
+    /// );
+    /// ```
+    pub fn trim_trailing_zeros(mut self) -> SizeFormatter<BaseType, Prefix, Separator> {
+        self.trim_zeros = true;
+        self
+    }
+
+    /// Sets the separator between the integer and the fractional part.
+    ///
+    /// This allows choosing the separator at runtime, while the other parts of the number
+    /// are still written as described by the separator type.
+    /// ```
+    /// use size_format::SizeFormatterSI;
+    ///
+    /// assert_eq!(format!("{}B", SizeFormatterSI::new(1_500_000).with_separator(",")), "1,5MB".to_string());
+    /// ```
+    pub fn with_separator(
+        mut self,
+        separator: &'static str,
+    ) -> SizeFormatter<BaseType, Prefix, Separator> {
+        let locale = self.locale.unwrap_or_else(Locale::of::<Separator>);
+        self.locale = Some(locale.with_decimal_separator(separator));
+        self
+    }
+
+    /// Writes the symbol of the unit after the prefix, so it does not have to be appended.
+    ///
+    /// Long names replace the unit symbol with the name of the unit.
+    /// ```
+    /// use size_format::{FrequencyFormatter, SizeFormatterSI};
+    ///
+    /// assert_eq!(format!("{}", SizeFormatterSI::new(42_000_000).with_unit("B")), "42.0MB".to_string());
+    /// assert_eq!(format!("{}", SizeFormatterSI::new(512).with_unit("B/s")), "512B/s".to_string());
+    /// assert_eq!(format!("{}", FrequencyFormatter::new(440).with_unit("/s")), "440Hz/s".to_string());
+    /// ```
+    pub fn with_unit(mut self, unit: &'static str) -> SizeFormatter<BaseType, Prefix, Separator> {
+        self.unit = unit;
+        self
+    }
+
+    /// Sets the letter case of the prefix and unit symbols, such as "KB" instead of "kB".
+    ///
+    /// Sizes written this way can be parsed back using `parse_with_case`.
+    /// ```
+    /// use size_format::{SizeFormatterBinary, SizeFormatterSI, SymbolCase};
+    ///
+    /// let cased = |case| SizeFormatterSI::new(1_500).with_unit("B").with_symbol_case(case);
+    ///
+    /// assert_eq!(format!("{}", cased(SymbolCase::Standard)), "1.5kB".to_string());
+    /// assert_eq!(format!("{}", cased(SymbolCase::Upper)), "1.5KB".to_string());
+    /// assert_eq!(format!("{}", cased(SymbolCase::Lower)), "1.5kb".to_string());
+    /// assert_eq!(format!("{}", cased(SymbolCase::Capitalized)), "1.5Kb".to_string());
+    /// assert_eq!(
+    ///     format!("{}B", SizeFormatterBinary::new(1_536).with_symbol_case(SymbolCase::Lower)),
+    ///     "1.5kiB".to_string()
+    /// );
+    /// ```
+    pub fn with_symbol_case(
+        mut self,
+        symbol_case: SymbolCase,
+    ) -> SizeFormatter<BaseType, Prefix, Separator> {
+        self.symbol_case = symbol_case;
+        self
+    }
+
+    /// Writes the prefix as a word followed by the name of the unit ("42.0 megabytes").
+    ///
+    /// This reads more naturally in prose and is easier for screen readers.
+    /// The number and the prefix are separated by the unit spacing, or a space if there is none.
+    /// Prefix types without long prefixes use their prefix symbols.
+    /// Prefixes without a long form are not used, such as minutes for durations.
+    /// ```
+    /// use size_format::{SizeFormatterBinary, SizeFormatterSI};
+    ///
+    /// assert_eq!(
+    ///     format!("{}", SizeFormatterSI::new(42_000_000).with_long_names("bytes")),
+    ///     "42.0 megabytes".to_string()
+    /// );
+    /// assert_eq!(
+    ///     format!("{}", SizeFormatterBinary::new(1_536).with_long_names("bytes")),
+    ///     "1.5 kibibytes".to_string()
+    /// );
+    /// ```
+    pub fn with_long_names(
+        mut self,
+        unit_name: &'static str,
+    ) -> SizeFormatter<BaseType, Prefix, Separator> {
+        self.long_unit = Some((unit_name, unit_name));
+        self
+    }
+
+    /// Writes the prefix as a word followed by the singular or plural name of the unit.
+    ///
+    /// The plural rule decides which name is used and defaults to the English rule.
+    /// ```
+    /// use size_format::SizeFormatterSI;
+    ///
+    /// let named = |num| SizeFormatterSI::new(num).with_unit_names("byte", "bytes");
+    ///
+    /// assert_eq!(format!("{:.0}", named(1_000)), "1 kilobyte".to_string());
+    /// assert_eq!(format!("{:.0}", named(2_000)), "2 kilobytes".to_string());
+    /// assert_eq!(format!("{}", named(1_000)), "1.0 kilobytes".to_string());
+    /// ```
+    pub fn with_unit_names(
+        mut self,
+        singular: &'static str,
+        plural: &'static str,
+    ) -> SizeFormatter<BaseType, Prefix, Separator> {
+        self.long_unit = Some((singular, plural));
+        self
+    }
+
+    /// Sets the rule that decides whether the plural name of the unit is used.
+    /// ```
+    /// use size_format::SizeFormatterSI;
+    ///
+    /// // In French, numbers below two use the singular.
+    /// fn french_plural(number: &str) -> bool {
+    ///     !(number.starts_with('0') || number.starts_with('1'))
+    ///         || number.chars().nth(1).map_or(false, |c| c.is_ascii_digit())
+    /// }
+    ///
+    /// let named = |num| {
+    ///     SizeFormatterSI::new(num)
+    ///         .with_unit_names("octet", "octets")
+    ///         .with_plural_rule(french_plural)
+    /// };
+    ///
+    /// assert_eq!(format!("{}", named(1_500)), "1.5 kilooctet".to_string());
+    /// assert_eq!(format!("{}", named(2_500)), "2.5 kilooctets".to_string());
+    /// assert_eq!(format!("{}", named(15_000)), "15.0 kilooctets".to_string());
+    /// ```
+    pub fn with_plural_rule(
+        mut self,
+        rule: PluralRule,
+    ) -> SizeFormatter<BaseType, Prefix, Separator> {
+        self.plural_rule = rule;
+        self
+    }
+
+    /// Precedes positive numbers with a plus sign, which is useful for showing growth.
+    ///
+    /// Zero is written without a sign. This can also be requested with the `+` flag.
+    /// ```
+    /// use size_format::SizeFormatterSI;
+    ///
+    /// assert_eq!(format!("{}B", SizeFormatterSI::new(1_200_000).with_plus_sign()), "+1.2MB".to_string());
+    /// assert_eq!(format!("{:+}B", SizeFormatterSI::new(1_200_000)), "+1.2MB".to_string());
+    /// assert_eq!(format!("{:+}B", SizeFormatterSI::new(0)), "0B".to_string());
+    /// ```
+    pub fn with_plus_sign(mut self) -> SizeFormatter<BaseType, Prefix, Separator> {
+        self.plus_sign = true;
+        self
+    }
+
+    /// Sets the spacing between the number and the prefix.
+    ///
+    /// The other parts of the number are still written as described by the separator type.
+    /// ```
+    /// use size_format::{SizeFormatterSI, Spacing};
+    ///
+    /// assert_eq!(
+    ///     format!("{}B", SizeFormatterSI::new(42_000_000).with_spacing(Spacing::Space)),
+    ///     "42.0 MB".to_string()
+    /// );
+    /// ```
+    pub fn with_spacing(mut self, spacing: Spacing) -> SizeFormatter<BaseType, Prefix, Separator> {
+        let locale = self.locale.unwrap_or_else(Locale::of::<Separator>);
+        self.locale = Some(locale.with_unit_spacing(spacing.as_str()));
+        self
+    }
+
+    /// Sets the precision to use for each prefix, if no other precision is specified.
+    ///
+    /// The entry at an index is used for the prefix at that index and the last entry
+    /// is used for all larger prefixes. The precision of the format string and
+    /// `with_precision` take precedence over this table.
+    /// ```
+    /// use size_format::SizeFormatterSI;
+    ///
+    /// let formatted = |num| format!("{}B", SizeFormatterSI::new(num).with_prefix_precisions(&[0, 1, 1, 2]));
+    ///
+    /// assert_eq!(formatted(1_500), "1.5kB".to_string());
+    /// assert_eq!(formatted(1_234_567_890), "1.23GB".to_string());
+    /// assert_eq!(formatted(1_234_567_890_000), "1.23TB".to_string());
+    /// ```
+    pub fn with_prefix_precisions(
+        mut self,
+        precisions: &'static [usize],
+    ) -> SizeFormatter<BaseType, Prefix, Separator> {
+        self.prefix_precisions = precisions;
+        self
+    }
+
+    /// Applies a function to the number, keeping the rest of the configuration.
+    ///
+    /// This allows adjusting values in a pipeline, for example to subtract an overhead.
+    /// ```
+    /// use size_format::SizeFormatterSI;
+    ///
+    /// let formatter = SizeFormatterSI::new(1_500_000).require_exact();
+    /// let compressed = formatter.map(|num| num / 3);
+    ///
+    /// assert_eq!(format!("{}B", compressed), "500.0kB".to_string());
+    /// ```
+    pub fn map<F>(mut self, f: F) -> SizeFormatter<BaseType, Prefix, Separator>
+    where
+        F: FnOnce(BaseType) -> BaseType,
+    {
+        self.num = f(self.num);
+        self
+    }
+
+    /// Applies the runtime formatting options to the formatter.
+    ///
+    /// Options that are set override the compile time configuration of the formatter.
+    /// The prefix type cannot be overridden, so options with a prefix system are not accepted.
+    pub fn with_options(
+        mut self,
+        options: &FormatOptions,
+    ) -> SizeFormatter<BaseType, Prefix, Separator> {
+        if let Some(locale) = options.locale() {
+            self.locale = Some(locale);
         }
+        if let Some(precision) = options.precision() {
+            self.precision = Some(precision);
+        }
+        self
     }
-}
 
-impl<BaseType, Prefix, Separator> Display for SizeFormatter<BaseType, Prefix, Separator>
-where
-    BaseType: Clone + Integer + Display + FromPrimitive + Pow<u32, Output = BaseType>,
-    Ratio<BaseType>: FromPrimitive,
-    Prefix: PrefixType,
-    Separator: DecimalSeparator,
-{
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let max_prefix = Prefix::prefixes().len() - 1;
-        let precision = f.precision().unwrap_or(DEFAULT_PRECISION);
-        let prefix_size = BaseType::from_u32(Prefix::PREFIX_SIZE)
-            .expect("prefix size is too large for number type");
+    /// Returns an iterator over the number expressed in every prefix.
+    ///
+    /// This is useful for offering alternative units, for example in a unit selection.
+    /// ```
+    /// use size_format::SizeFormatterBinary;
+    ///
+    /// let mut representations = SizeFormatterBinary::new(44_040_192).representations();
+    ///
+    /// assert_eq!(representations.next(), Some((44_040_192.0, "")));
+    /// assert_eq!(representations.next(), Some((43_008.0, "Ki")));
+    /// assert_eq!(representations.next(), Some((42.0, "Mi")));
+    /// assert_eq!(representations.count(), 6);
+    /// ```
+    #[cfg(feature = "float")]
+    pub fn representations(&self) -> Representations<Prefix>
+    where
+        BaseType: ToPrimitive,
+    {
+        Representations::new(self.num.to_f64().unwrap_or(f64::INFINITY))
+    }
+
+    /// Returns the prefix that is used when formatting the number.
+    ///
+    /// This allows decisions that are consistent with the formatted output.
+    /// ```
+    /// use size_format::SizeFormatterSI;
+    ///
+    /// let prefix = SizeFormatterSI::new(42_000_000_000).chosen_prefix();
+    ///
+    /// assert_eq!(prefix.index(), 3);
+    /// assert_eq!(prefix.symbol(), "G");
+    /// ```
+    pub fn chosen_prefix(&self) -> ChosenPrefix {
+        match self.divisions() {
+            Some(index) => ChosenPrefix {
+                index,
+                symbol: self.prefixes()[index],
+            },
+            None => ChosenPrefix {
+                index: 0,
+                symbol: "",
+            },
+        }
+    }
+
+    /// Returns the number scaled to the chosen prefix, for rendering it elsewhere.
+    ///
+    /// Numbers that do not fit into a `u128` saturate in `exact_bytes`.
+    #[cfg(feature = "float")]
+    pub fn scaled(&self) -> ScaledSize
+    where
+        BaseType: ToPrimitive,
+    {
+        let prefix = self.chosen_prefix();
+        let value = self
+            .representations()
+            .nth(prefix.index())
+            .map(|(value, _)| value)
+            .unwrap_or_else(|| self.num.to_f64().unwrap_or(f64::INFINITY));
 
-        // Find the right prefix.
-        let divisions = cmp::min(int_log(self.num.clone(), prefix_size.clone()), max_prefix);
+        ScaledSize {
+            value,
+            unit: prefix.symbol(),
+            exact_bytes: self.num.to_u128().unwrap_or(u128::MAX),
+        }
+    }
 
-        // Cap the precision to what makes sense.
-        let precision = cmp::min(precision, divisions * 3);
+    /// Returns the index of the prefix to use, or `None` if there are no prefixes.
+    fn divisions(&self) -> Option<usize> {
+        let () = ValidPrefix::<Prefix>::CHECK;
 
-        let ratio = Ratio::<BaseType>::new(self.num.clone(), prefix_size.pow(divisions as u32));
+        let max_prefix = self.max_prefix()?;
 
-        let format_number = FormatRatio::<BaseType, Separator>::new(ratio);
+        let divisions = match self.forced_prefix {
+            Some(index) => index,
+            None => {
+                let (min, max) = self.prefix_limits;
+                let num = self.displayed().0;
+                let divisions = self.apply_threshold(num.clone(), self.magnitude(num));
 
-        write!(
+                cmp::max(cmp::min(divisions, max), min)
+            }
+        };
+
+        Some(cmp::min(divisions, max_prefix))
+    }
+
+    /// Returns the index of the largest prefix that can be written, or `None` if there are no prefixes.
+    ///
+    /// With long names, only the prefixes that have a long form can be written.
+    fn max_prefix(&self) -> Option<usize> {
+        let count = match (self.long_unit, self.long_prefixes()) {
+            (Some(_), Some(long_prefixes)) => long_prefixes.len(),
+            _ => self.prefixes().len(),
+        };
+
+        count.checked_sub(1)
+    }
+
+    /// Uses prefixes chosen at runtime instead of those of the prefix type.
+    pub(crate) fn with_runtime_prefixes(
+        mut self,
+        prefixes: RuntimePrefixes,
+    ) -> SizeFormatter<BaseType, Prefix, Separator> {
+        self.runtime_prefixes = Some(prefixes);
+        self
+    }
+
+    /// Returns the prefix symbols, starting with the one for unscaled numbers.
+    fn prefixes(&self) -> &'static [&'static str] {
+        self.runtime_prefixes
+            .map_or(Prefix::PREFIXES, |prefixes| prefixes.prefixes())
+    }
+
+    /// Returns the prefixes written out as words, if there are any.
+    fn long_prefixes(&self) -> Option<&'static [&'static str]> {
+        match self.runtime_prefixes {
+            Some(_) => None,
+            None => Prefix::LONG_PREFIXES,
+        }
+    }
+
+    /// Returns the factor between the prefix at `index` and the next one.
+    fn raw_step_size(&self, index: usize) -> u32 {
+        self.runtime_prefixes
+            .map_or_else(|| step_size::<Prefix>(index), |prefixes| prefixes.base())
+    }
+
+    /// Moves the prefix chosen by magnitude by one step, if the threshold requires it.
+    fn apply_threshold(&self, num: BaseType, divisions: usize) -> usize {
+        let threshold = match self.threshold {
+            Some(threshold) => threshold,
+            None => return divisions,
+        };
+        // The number in the prefix chosen by magnitude, which is at least one unless it is zero.
+        let scaled = Ratio::new(num, self.divisor(divisions));
+        let to_base = |numer: u64, denom: u64| {
+            Some(Ratio::new(
+                BaseType::from_u64(numer)?,
+                BaseType::from_u64(denom)?,
+            ))
+        };
+
+        if threshold > Ratio::from_integer(1) {
+            match to_base(u64::from(*threshold.numer()), u64::from(*threshold.denom())) {
+                Some(threshold) if divisions > 0 && scaled < threshold => divisions - 1,
+                _ => divisions,
+            }
+        } else {
+            // Compare with the threshold of the next prefix, expressed in the current prefix.
+            let numer = u64::from(*threshold.numer()) * u64::from(self.raw_step_size(divisions));
+            match to_base(numer, u64::from(*threshold.denom())) {
+                Some(threshold) if scaled >= threshold => divisions + 1,
+                _ => divisions,
+            }
+        }
+    }
+
+    /// Returns the number that is displayed, together with the marker if it was clamped.
+    fn displayed(&self) -> (BaseType, Option<ClampStyle>) {
+        match self.clamp {
+            Some((ref max, style)) if self.num > *max => (max.clone(), Some(style)),
+            _ => (self.num.clone(), None),
+        }
+    }
+
+    /// Makes the formatter use the prefix at `index`, regardless of the magnitude of the number.
+    ///
+    /// This makes rows easier to compare, since they all share a unit.
+    /// Indices beyond the available prefixes use the largest prefix.
+    ///
+    /// # Panics
+    /// - Formatting panics if the size of the prefix does not fit into the base type.
+    /// ```
+    /// use size_format::SizeFormatterSI;
+    ///
+    /// // Index 2 is the prefix for mega.
+    /// assert_eq!(format!("{}B", SizeFormatterSI::new(400_000).with_forced_prefix(2)), "0.4MB".to_string());
+    /// assert_eq!(
+    ///     format!("{}B", SizeFormatterSI::new(12_345_600_000).with_forced_prefix(2)),
+    ///     "12345.6MB".to_string()
+    /// );
+    /// ```
+    pub fn with_forced_prefix(
+        mut self,
+        index: usize,
+    ) -> SizeFormatter<BaseType, Prefix, Separator> {
+        self.forced_prefix = Some(index);
+        self
+    }
+
+    /// Returns the factor between the prefix at `index` and the next one as the base type.
+    fn step_size(&self, index: usize) -> BaseType {
+        BaseType::from_u32(self.raw_step_size(index))
+            .expect("prefix size is too large for number type")
+    }
+
+    /// Returns the number of units of the first prefix in the prefix at `index`.
+    fn divisor(&self, index: usize) -> BaseType {
+        (0..index).fold(BaseType::one(), |divisor, step| {
+            divisor * self.step_size(step)
+        })
+    }
+
+    /// Returns the number of units of the first prefix in the prefix at `index`,
+    /// or `None` if it does not fit into the base type.
+    fn checked_divisor(&self, index: usize) -> Option<BaseType> {
+        let divisor = (0..index).try_fold(1u128, |divisor, step| {
+            divisor.checked_mul(u128::from(self.raw_step_size(step)))
+        })?;
+
+        BaseType::from_u128(divisor)
+    }
+
+    /// Returns the index of the largest prefix that `num` fills at least once.
+    ///
+    /// Like the logarithm, this ignores the number of available prefixes.
+    fn magnitude(&self, mut num: BaseType) -> usize {
+        let mut index = 0;
+
+        loop {
+            let step = self.step_size(index);
+            if num < step {
+                return index;
+            }
+            num = num / step;
+            index += 1;
+        }
+    }
+
+    /// Writes the formatted number without any padding.
+    pub(crate) fn write_unpadded(
+        &self,
+        f: &mut dyn fmt::Write,
+        precision: Option<usize>,
+    ) -> fmt::Result {
+        self.write_signed(f, precision, self.plus_sign)
+    }
+
+    /// Writes the formatted number without any padding, preceded by a plus sign if requested.
+    fn write_signed(
+        &self,
+        f: &mut dyn fmt::Write,
+        precision: Option<usize>,
+        plus_sign: bool,
+    ) -> fmt::Result {
+        if plus_sign && !self.num.is_zero() {
+            f.write_char('+')?;
+        }
+
+        let locale = self.locale.unwrap_or_else(Locale::of::<Separator>);
+        let f: &mut dyn fmt::Write = &mut MappedWriter::new(f, locale.digits());
+        // A clamped number is never exact, since the actual number is lost.
+        let (num, clamped) = self.displayed();
+        let plus = if clamped == Some(ClampStyle::Plus) {
+            "+"
+        } else {
+            ""
+        };
+
+        let mut divisions = match self.divisions() {
+            Some(divisions) => divisions,
+            // Without any prefixes the number cannot be scaled, so it is printed as an integer.
+            None => {
+                if clamped.is_some() {
+                    self.write_inexact(f)?;
+                }
+                if clamped == Some(ClampStyle::GreaterThan) {
+                    f.write_char('>')?;
+                }
+                let format_number = FormatRatio::new(Ratio::from_integer(num)).with_locale(&locale);
+                return self.write_number(
+                    f,
+                    format_args!("{:.0}", format_number),
+                    plus,
+                    &locale,
+                    None,
+                );
+            }
+        };
+        let (mut scaled_precision, mut format_number, mut stepped_exactly) =
+            self.scale(num.clone(), divisions, precision, &locale);
+
+        // Rounding may carry the number up to the size of the next prefix, which is used instead.
+        let unrounded = Ratio::new(num.clone(), self.divisor(divisions));
+        let step = Ratio::from_integer(self.step_size(divisions));
+        if self.forced_prefix.is_none()
+            && divisions < cmp::min(self.prefix_limits.1, self.max_prefix().unwrap_or(0))
+            && unrounded < step
+            && Ratio::from_integer(format_number.rounded_integer(scaled_precision)) >= step
+            && self.checked_divisor(divisions + 1).is_some()
+        {
+            divisions += 1;
+            let (promoted_precision, promoted_number, promoted_exactly) =
+                self.scale(num, divisions, precision, &locale);
+            scaled_precision = promoted_precision;
+            format_number = promoted_number;
+            stepped_exactly = promoted_exactly;
+        }
+        let precision = scaled_precision;
+
+        let is_exact = clamped.is_none() && stepped_exactly && format_number.is_exact(precision);
+        if !is_exact {
+            self.write_inexact(f)?;
+        }
+        if clamped == Some(ClampStyle::GreaterThan) {
+            f.write_char('>')?;
+        }
+
+        self.write_number(
             f,
-            "{:.*}{}",
-            precision,
-            format_number,
-            Prefix::prefixes()[divisions]
+            format_args!("{:.*}", precision, format_number),
+            plus,
+            &locale,
+            Some(divisions),
         )
     }
-}
 
-/// Returns the number of times `num` can be divided by `base`.
-fn int_log<BaseType>(mut num: BaseType, base: BaseType) -> usize
-where
-    BaseType: Clone + Integer + Display + FromPrimitive + Pow<u32, Output = BaseType>,
-    Ratio<BaseType>: FromPrimitive,
-{
-    let mut divisions = 0;
+    /// Returns the precision, the number scaled to the prefix at `divisions` and whether it was
+    /// stepped exactly.
+    fn scale(
+        &self,
+        num: BaseType,
+        divisions: usize,
+        precision: Option<usize>,
+        locale: &Locale,
+    ) -> (usize, FormatRatio<BaseType>, bool) {
+        let table_precision = self
+            .prefix_precisions
+            .get(divisions)
+            .or_else(|| self.prefix_precisions.last())
+            .cloned();
+        let precision = precision
+            .or(self.precision)
+            .or(table_precision)
+            .unwrap_or(Prefix::DEFAULT_PRECISION);
+
+        // Cap the precision to what makes sense, which is the number of digits
+        // that the prefix steps fully cover (3 for 1000 and 1024, 4 for 10000).
+        // Steps below ten still need a digit for their fractions, such as a half for two.
+        let covered_digits = (0..divisions)
+            .map(|step| cmp::max(int_log::<u32>(self.raw_step_size(step), 10), 1))
+            .sum();
+        let precision = cmp::min(precision, covered_digits);
+
+        let divisor = self.divisor(divisions);
+        let ratio = self.round_to_step(num.clone(), divisor.clone());
+        let stepped_exactly = ratio == Ratio::new(num, divisor);
+
+        let mut format_number = FormatRatio::new(ratio)
+            .with_locale(locale)
+            .with_rounding(self.rounding.unwrap_or(Rounding::Down));
+        if self.trim_zeros {
+            format_number = format_number.trim_trailing_zeros();
+        }
 
-    while num >= base {
-        num = num / base.clone();
-        divisions += 1;
+        (precision, format_number, stepped_exactly)
     }
 
-    divisions
-}
+    /// Fails if the number has to be exact, otherwise writes the approximation marker if there is one.
+    fn write_inexact(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        if self.exact {
+            return Err(fmt::Error);
+        }
+        if let Some(marker) = self.approximation_marker {
+            f.write_str(marker)?;
+        }
 
-/// This allows formatting a ratio as a decimal number.
-///
-/// This is a temporary solution until support for that is added to the `num` crate.
-struct FormatRatio<BaseType, Separator>
-where
-    BaseType: Clone + Integer + Display + FromPrimitive + Pow<u32, Output = BaseType>,
-    Ratio<BaseType>: FromPrimitive,
-    Separator: DecimalSeparator,
-{
-    num: Ratio<BaseType>,
-    _marker: PhantomData<Separator>,
+        Ok(())
+    }
+
+    /// Writes the number followed by the clamp marker and the prefix at the index.
+    fn write_number<T: Display>(
+        &self,
+        f: &mut dyn fmt::Write,
+        number: T,
+        plus: &str,
+        locale: &Locale,
+        divisions: Option<usize>,
+    ) -> fmt::Result {
+        // The number is kept to decide on the plural, numbers too long for that are plural anyway.
+        let plural = match FormattedSize::new(&number) {
+            Ok(formatted) => {
+                f.write_str(&formatted)?;
+                (self.plural_rule)(&formatted)
+            }
+            Err(_) => {
+                write!(f, "{}", number)?;
+                true
+            }
+        };
+        f.write_str(plus)?;
+
+        self.write_prefix(f, locale, divisions, plural)
+    }
+
+    /// Writes the spacing and the prefix at the index, or the long names if they are used.
+    fn write_prefix(
+        &self,
+        f: &mut dyn fmt::Write,
+        locale: &Locale,
+        divisions: Option<usize>,
+        plural: bool,
+    ) -> fmt::Result {
+        let unit = match self.long_unit {
+            Some((_, plural_name)) if plural => plural_name,
+            Some((singular_name, _)) => singular_name,
+            None => {
+                let prefix = divisions.map_or("", |index| self.prefixes()[index]);
+                f.write_str(locale.unit_spacing())?;
+                return self.symbol_case.write(f, &[prefix, self.unit]);
+            }
+        };
+
+        let spacing = if locale.unit_spacing().is_empty() {
+            " "
+        } else {
+            locale.unit_spacing()
+        };
+        let prefix = divisions.map_or("", |index| {
+            self.long_prefixes().unwrap_or_else(|| self.prefixes())[index]
+        });
+
+        write!(f, "{}{}{}", spacing, prefix, unit)
+    }
 }
 
-impl<BaseType, Separator> FormatRatio<BaseType, Separator>
+impl<BaseType, Prefix, Separator> Display for SizeFormatter<BaseType, Prefix, Separator>
 where
     BaseType: Clone + Integer + Display + FromPrimitive + Pow<u32, Output = BaseType>,
     Ratio<BaseType>: FromPrimitive,
+    Prefix: PrefixType,
     Separator: DecimalSeparator,
 {
-    /// Creates a new format ratio from the number.
-    fn new(num: Ratio<BaseType>) -> FormatRatio<BaseType, Separator> {
-        FormatRatio {
-            num,
-            _marker: PhantomData,
-        }
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let precision = f.precision();
+        let plus_sign = self.plus_sign || f.sign_plus();
+
+        // Sizes are right aligned by default, like other numbers.
+        align::pad(f, fmt::Alignment::Right, |w| {
+            self.write_signed(w, precision, plus_sign)
+        })
     }
 }
 
-impl<BaseType, Separator> Display for FormatRatio<BaseType, Separator>
+impl<BaseType, Prefix, Separator> SizeFormatter<BaseType, Prefix, Separator>
 where
-    BaseType: Clone + Integer + Display + FromPrimitive + Pow<u32, Output = BaseType>,
+    BaseType: Clone
+        + Integer
+        + Display
+        + FromPrimitive
+        + Pow<u32, Output = BaseType>
+        + CheckedMul
+        + CheckedAdd,
     Ratio<BaseType>: FromPrimitive,
+    Prefix: PrefixType,
     Separator: DecimalSeparator,
 {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.num.trunc())?;
-        let precision = f.precision().unwrap_or(DEFAULT_PRECISION);
+    /// Creates a new formatter counting the bits in the given number of bytes.
+    ///
+    /// Returns `None` if the number of bits does not fit into the type.
+    /// ```
+    /// use size_format::{SizeFormatterBinaryBits, SizeFormatterSIBits128};
+    ///
+    /// assert_eq!(
+    ///     format!("{}", SizeFormatterBinaryBits::from_bytes_as_bits(128).unwrap()),
+    ///     "1.0Kibit".to_string()
+    /// );
+    /// assert!(SizeFormatterBinaryBits::from_bytes_as_bits(u64::MAX).is_none());
+    /// assert_eq!(
+    ///     format!("{}", SizeFormatterSIBits128::from_bytes_as_bits(u64::MAX.into()).unwrap()),
+    ///     "147.5Ebit".to_string()
+    /// );
+    /// ```
+    pub fn from_bytes_as_bits(
+        bytes: BaseType,
+    ) -> Option<SizeFormatter<BaseType, Prefix, Separator>> {
+        bytes
+            .checked_mul(&BaseType::from_u8(8)?)
+            .map(SizeFormatter::new)
+    }
 
-        if precision > 0 {
-            write!(f, "{}", Separator::SEPARATOR)?;
-            let mut frac = self.num.fract();
+    /// Creates a new size formatter from a number of units, as given by `Prefix::BASE_INDEX`.
+    ///
+    /// Returns `None` if the number does not fit into the type when counted in the first prefix.
+    pub fn from_base_units(units: BaseType) -> Option<SizeFormatter<BaseType, Prefix, Separator>> {
+        units
+            .checked_mul(&bytes_in::<Prefix, BaseType>(Prefix::BASE_INDEX)?)
+            .map(SizeFormatter::new)
+    }
 
-            for _ in 0..precision {
-                if frac.is_integer() {
-                    // If the fractional part is an integer, we're done and just need more zeroes.
-                    write!(f, "0")?;
-                } else {
-                    // Otherwise print every digit separately.
-                    frac = frac * Ratio::from_u64(10).unwrap();
-                    write!(f, "{}", frac.trunc())?;
-                    frac = frac.fract();
-                }
-            }
-        }
+    /// Creates a new size formatter from a fractional number of units, as given by `Prefix::BASE_INDEX`.
+    ///
+    /// Parts smaller than the first prefix are rounded down.
+    /// Returns `None` if the number does not fit into the type when counted in the first prefix.
+    /// ```
+    /// extern crate num;
+    /// extern crate size_format;
+    ///
+    /// use num::rational::Ratio;
+    /// use size_format::{PointSeparated, PrefixType, SizeFormatter};
+    ///
+    /// struct Scientific;
+    ///
+    /// impl PrefixType for Scientific {
+    ///     const PREFIX_SIZE: u32 = 1000;
+    ///     const BASE_INDEX: usize = 3;
+    ///
+    ///     const PREFIXES: &'static [&'static str] = &["n", "µ", "m", "", "k", "M", "G"];
+    /// }
+    ///
+    /// # fn main() {
+    /// type Volts = SizeFormatter<u64, Scientific, PointSeparated>;
+    ///
+    /// let half = Volts::from_base_fraction(Ratio::new(1, 2)).unwrap();
+    /// assert_eq!(format!("{:.0}V", half), "500mV".to_string());
+    ///
+    /// let kilo = Volts::from_base_units(1_500).unwrap();
+    /// assert_eq!(format!("{}V", kilo), "1.5kV".to_string());
+    /// # }
+    /// ```
+    pub fn from_base_fraction(
+        units: Ratio<BaseType>,
+    ) -> Option<SizeFormatter<BaseType, Prefix, Separator>> {
+        let scale = bytes_in::<Prefix, BaseType>(Prefix::BASE_INDEX)?;
+        let (integer, rem) = units.numer().div_rem(units.denom());
+        let integer = integer.checked_mul(&scale)?;
+        // The remainder is less than the denominator, so this only overflows if the result does.
+        let fraction = rem.checked_mul(&scale)? / units.denom().clone();
 
-        Ok(())
+        integer.checked_add(&fraction).map(SizeFormatter::new)
     }
-}
 
-#[cfg(test)]
-#[macro_use]
-extern crate std;
+    /// Parses a number formatted with this formatter, followed by the unit.
+    ///
+    /// The number uses the decimal separator of the `Separator` and may be followed by its
+    /// unit spacing, but must not contain group separators. Fractions of the number are
+    /// rounded down. The unit may be omitted.
+    /// ```
+    /// extern crate size_format;
+    ///
+    /// use size_format::{PointSeparated, PrefixType, SizeFormatter};
+    ///
+    /// struct Millimeter;
+    ///
+    /// impl PrefixType for Millimeter {
+    ///     const PREFIX_SIZE: u32 = 1000;
+    ///
+    ///     const PREFIXES: &'static [&'static str] = &["m", "", "k"];
+    /// }
+    ///
+    /// # fn main() {
+    /// type Length = SizeFormatter<u32, Millimeter, PointSeparated>;
+    ///
+    /// assert_eq!(Length::parse_with_unit("1.5km", "m").unwrap().num(), 1_500_000);
+    /// assert!(Length::parse_with_unit("5000km", "m").is_err());
+    /// # }
+    /// ```
+    pub fn parse_with_unit(
+        s: &str,
+        unit: &str,
+    ) -> Result<SizeFormatter<BaseType, Prefix, Separator>, ParseSizeError> {
+        parse_number::<BaseType, Prefix>(
+            s,
+            unit,
+            ParseMode::Strict,
+            Separator::SEPARATOR,
+            Separator::UNIT_SPACING,
+        )
+        .map(SizeFormatter::new)
+    }
+
+    /// Parses a number followed by a prefix and the unit, written in the given case.
+    ///
+    /// This accepts the output of formatters using `with_symbol_case` with the same case.
+    /// ```
+    /// use size_format::{SizeFormatterBinary, SymbolCase};
+    ///
+    /// let parse = |s| SizeFormatterBinary::parse_with_case(s, "B", SymbolCase::Upper);
+    ///
+    /// assert_eq!(parse("1.5KIB").unwrap().num(), 1_536);
+    /// assert_eq!(parse("2MIB").unwrap().num(), 2 << 20);
+    /// assert!(parse("1.5KiB").is_err());
+    /// ```
+    pub fn parse_with_case(
+        s: &str,
+        unit: &str,
+        case: SymbolCase,
+    ) -> Result<SizeFormatter<BaseType, Prefix, Separator>, ParseSizeError> {
+        parse_number::<BaseType, Prefix>(
+            s,
+            unit,
+            ParseMode::Cased(case),
+            Separator::SEPARATOR,
+            Separator::UNIT_SPACING,
+        )
+        .map(SizeFormatter::new)
+    }
+}
+
+impl<Separator: DecimalSeparator> SizeFormatter<u128, DurationUnits, Separator> {
+    /// Creates a new formatter for the given duration, counted in nanoseconds.
+    pub fn from_duration(duration: Duration) -> SizeFormatter<u128, DurationUnits, Separator> {
+        SizeFormatter::new(duration.as_nanos())
+    }
+}
+
+impl<BaseType, Prefix, Separator> FromStr for SizeFormatter<BaseType, Prefix, Separator>
+where
+    BaseType: Clone
+        + Integer
+        + Display
+        + FromPrimitive
+        + Pow<u32, Output = BaseType>
+        + CheckedMul
+        + CheckedAdd,
+    Ratio<BaseType>: FromPrimitive,
+    Prefix: PrefixType,
+    Separator: DecimalSeparator,
+{
+    type Err = ParseSizeError;
+
+    /// Parses a number as written by this formatter, such as "1.5k" or "1,5 M".
+    ///
+    /// See `parse_with_unit` for details.
+    fn from_str(s: &str) -> Result<SizeFormatter<BaseType, Prefix, Separator>, ParseSizeError> {
+        SizeFormatter::parse_with_unit(s, "")
+    }
+}
+
+impl<Prefix, Separator> From<u64> for SizeFormatter<u128, Prefix, Separator>
+where
+    Prefix: PrefixType,
+    Separator: DecimalSeparator,
+{
+    /// Widens a `u64` byte size, so that totals of several sizes cannot overflow.
+    fn from(num: u64) -> SizeFormatter<u128, Prefix, Separator> {
+        SizeFormatter::new(u128::from(num))
+    }
+}
+
+/// Performs compile time checks on a prefix type.
+struct ValidPrefix<Prefix>(PhantomData<Prefix>);
+
+impl<Prefix: PrefixType> ValidPrefix<Prefix> {
+    /// Fails to evaluate, if the prefix sizes would make scaling impossible.
+    ///
+    /// With a prefix size of zero or one, a number could be divided infinitely often.
+    /// Long prefixes of the wrong length would be indexed out of bounds.
+    const CHECK: () = {
+        assert!(
+            Prefix::PREFIX_SIZE > 1,
+            "the prefix size of a prefix type must be at least 2"
+        );
+
+        let mut index = 0;
+        while index < Prefix::STEP_SIZES.len() {
+            assert!(
+                Prefix::STEP_SIZES[index] > 1,
+                "the step sizes of a prefix type must be at least 2"
+            );
+            index += 1;
+        }
+
+        if let Some(long_prefixes) = Prefix::LONG_PREFIXES {
+            assert!(
+                long_prefixes.len() <= Prefix::PREFIXES.len(),
+                "a prefix type must not have more long prefixes than prefixes"
+            );
+        }
+    };
+}
+
+/// Formats a non-negative ratio as a decimal number.
+///
+/// The precision of the format string gives the number of decimal places, which defaults to 1.
+/// Further digits are cut off, so the value is rounded down unless another rounding is chosen.
+/// The separators are taken from a locale, which defaults to a point and no grouping.
+/// ```
+/// extern crate num;
+/// extern crate size_format;
+///
+/// use num::rational::Ratio;
+/// use size_format::{FormatRatio, Locale};
+///
+/// # fn main() {
+/// assert_eq!(format!("{:.3}", FormatRatio::new(Ratio::new(2u32, 3))), "0.666".to_string());
+/// assert_eq!(format!("{:>6}", FormatRatio::new(Ratio::new(7u8, 2))), "   3.5".to_string());
+///
+/// let locale = Locale::from_tag("de").unwrap();
+/// assert_eq!(
+///     format!("{:.2}", FormatRatio::new(Ratio::new(1_234_567u64, 8)).with_locale(&locale)),
+///     "154.320,87".to_string()
+/// );
+/// # }
+/// ```
+pub struct FormatRatio<BaseType>
+where
+    BaseType: Clone + Integer + Display + FromPrimitive + Pow<u32, Output = BaseType>,
+    Ratio<BaseType>: FromPrimitive,
+{
+    num: Ratio<BaseType>,
+    /// The separator between the integer and the fractional part.
+    separator: &'static str,
+    /// The separator between groups of three digits in the integer part.
+    group_separator: &'static str,
+    /// Whether zeros at the end of the fractional part are left out.
+    trim_zeros: bool,
+    /// How the number is rounded to the precision.
+    rounding: Rounding,
+}
+
+impl<BaseType> FormatRatio<BaseType>
+where
+    BaseType: Clone + Integer + Display + FromPrimitive + Pow<u32, Output = BaseType>,
+    Ratio<BaseType>: FromPrimitive,
+{
+    /// Creates a new format ratio from the number.
+    pub fn new(num: Ratio<BaseType>) -> FormatRatio<BaseType> {
+        FormatRatio {
+            num,
+            separator: ".",
+            group_separator: "",
+            trim_zeros: false,
+            rounding: Rounding::Down,
+        }
+    }
+
+    /// Uses the decimal and group separators of the locale.
+    pub fn with_locale(mut self, locale: &Locale) -> FormatRatio<BaseType> {
+        self.separator = locale.decimal_separator();
+        self.group_separator = locale.group_separator();
+        self
+    }
+
+    /// Leaves out zeros at the end of the fractional part, so the precision is a maximum.
+    ///
+    /// If the whole fractional part is zero, the separator is left out as well.
+    /// ```
+    /// extern crate num;
+    /// extern crate size_format;
+    ///
+    /// use num::rational::Ratio;
+    /// use size_format::FormatRatio;
+    ///
+    /// # fn main() {
+    /// let trimmed = |numer, denom| FormatRatio::new(Ratio::new(numer, denom)).trim_trailing_zeros();
+    ///
+    /// assert_eq!(format!("{:.3}", trimmed(3u32, 2)), "1.5".to_string());
+    /// assert_eq!(format!("{:.3}", trimmed(4u32, 2)), "2".to_string());
+    /// assert_eq!(format!("{:.2}", trimmed(2_001u32, 1_000)), "2".to_string());
+    /// # }
+    /// ```
+    pub fn trim_trailing_zeros(mut self) -> FormatRatio<BaseType> {
+        self.trim_zeros = true;
+        self
+    }
+
+    /// Rounds the number to the given number of decimal places with the rounding strategy.
+    /// ```
+    /// extern crate num;
+    /// extern crate size_format;
+    ///
+    /// use num::rational::Ratio;
+    /// use size_format::{math::Rounding, FormatRatio};
+    ///
+    /// # fn main() {
+    /// let rounded = |numer, rounding| FormatRatio::new(Ratio::new(numer, 1000u32)).with_rounding(rounding);
+    ///
+    /// assert_eq!(format!("{:.2}", rounded(1_005, Rounding::Down)), "1.00".to_string());
+    /// assert_eq!(format!("{:.2}", rounded(1_005, Rounding::HalfUp)), "1.01".to_string());
+    /// assert_eq!(format!("{:.2}", rounded(1_005, Rounding::HalfEven)), "1.00".to_string());
+    /// assert_eq!(format!("{:.2}", rounded(1_995, Rounding::HalfEven)), "2.00".to_string());
+    /// # }
+    /// ```
+    pub fn with_rounding(mut self, rounding: Rounding) -> FormatRatio<BaseType> {
+        self.rounding = rounding;
+        self
+    }
+
+    /// Returns the integer part of the number after rounding it to the given precision.
+    fn rounded_integer(&self, precision: usize) -> BaseType {
+        let integer = self.num.to_integer();
+
+        match self.rounded_digits(precision).changed {
+            Some(0) => integer + BaseType::one(),
+            _ => integer,
+        }
+    }
+
+    /// Determines how the digits up to the given precision are written.
+    fn rounded_digits(&self, precision: usize) -> RoundedDigits {
+        let denom = self.num.denom();
+        let mut rem = self.num.numer().clone() % denom.clone();
+        let mut last_odd = self.num.to_integer().is_odd();
+        let mut last_non_nine = 0;
+        let mut last_non_zero = 0;
+
+        for place in 1..=precision {
+            let (digit, next_rem) = if rem.is_zero() {
+                (0, rem)
+            } else {
+                next_digit(rem, denom)
+            };
+            if digit != 9 {
+                last_non_nine = place;
+            }
+            if digit != 0 {
+                last_non_zero = place;
+            }
+            last_odd = digit % 2 == 1;
+            rem = next_rem;
+        }
+
+        // Compare twice the remainder with the denominator without computing it.
+        let half = rem.cmp(&(denom.clone() - rem.clone()));
+        let round_up = !rem.is_zero()
+            && match self.rounding {
+                Rounding::Down => false,
+                Rounding::Up => true,
+                Rounding::HalfUp => half != cmp::Ordering::Less,
+                Rounding::HalfEven => {
+                    half == cmp::Ordering::Greater || (half == cmp::Ordering::Equal && last_odd)
+                }
+            };
+
+        // When rounding up, the last digit that is not a nine is incremented and followed by zeros.
+        let (changed, written) = match (round_up, self.trim_zeros) {
+            (true, true) => (Some(last_non_nine), last_non_nine),
+            (true, false) => (Some(last_non_nine), precision),
+            (false, true) => (None, last_non_zero),
+            (false, false) => (None, precision),
+        };
+
+        RoundedDigits { changed, written }
+    }
+
+    /// Checks whether the number can be represented exactly with the given precision.
+    ///
+    /// This is the case if the reduced denominator only consists of at most `precision`
+    /// factors of two and five each, since it then divides `10^precision`.
+    /// ```
+    /// extern crate num;
+    /// extern crate size_format;
+    ///
+    /// use num::rational::Ratio;
+    /// use size_format::FormatRatio;
+    ///
+    /// # fn main() {
+    /// assert!(FormatRatio::new(Ratio::new(3u32, 8)).is_exact(3));
+    /// assert!(!FormatRatio::new(Ratio::new(3u32, 8)).is_exact(2));
+    /// assert!(!FormatRatio::new(Ratio::new(1u32, 3)).is_exact(9));
+    /// # }
+    /// ```
+    pub fn is_exact(&self, precision: usize) -> bool {
+        let mut denom = self.num.reduced().denom().clone();
+
+        for factor in &[2, 5] {
+            let factor = BaseType::from_u8(*factor).unwrap();
+            let mut count = 0;
+
+            while denom.is_multiple_of(&factor) {
+                denom = denom / factor.clone();
+                count += 1;
+            }
+
+            if count > precision {
+                return false;
+            }
+        }
+
+        denom.is_one()
+    }
+
+    /// Writes the number with the given number of decimal places without any padding.
+    fn write_unpadded(&self, f: &mut dyn fmt::Write, precision: usize) -> fmt::Result {
+        let RoundedDigits { changed, written } = self.rounded_digits(precision);
+        let denom = self.num.denom();
+        let mut integer = self.num.to_integer();
+        if changed == Some(0) {
+            integer = integer + BaseType::one();
+        }
+        write_grouped(f, integer, self.group_separator)?;
+
+        if written > 0 {
+            write!(f, "{}", self.separator)?;
+            let mut rem = self.num.numer().clone() % denom.clone();
+
+            for place in 1..=written {
+                let digit = if rem.is_zero() {
+                    // If the fractional part is an integer, we're done and just need more zeroes.
+                    0
+                } else {
+                    // Otherwise compute every digit separately.
+                    let (digit, next_rem) = next_digit(rem, denom);
+                    rem = next_rem;
+                    digit
+                };
+
+                match changed {
+                    Some(changed) if place == changed => write!(f, "{}", digit + 1)?,
+                    Some(changed) if place > changed => write!(f, "0")?,
+                    _ => write!(f, "{}", digit)?,
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Describes how the decimal places of a rounded number are written.
+struct RoundedDigits {
+    /// The decimal place that is incremented when rounding up, after which only zeros follow.
+    ///
+    /// The place zero refers to the integer part.
+    changed: Option<usize>,
+    /// The number of decimal places that are written.
+    written: usize,
+}
+
+impl<BaseType> Display for FormatRatio<BaseType>
+where
+    BaseType: Clone + Integer + Display + FromPrimitive + Pow<u32, Output = BaseType>,
+    Ratio<BaseType>: FromPrimitive,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let precision = f.precision().unwrap_or(DEFAULT_PRECISION);
+
+        align::pad(f, fmt::Alignment::Right, |w| {
+            self.write_unpadded(w, precision)
+        })
+    }
+}
+
+/// Writes an integer with its digits grouped in thousands using the group separator.
+pub(crate) fn write_grouped<BaseType>(
+    f: &mut dyn fmt::Write,
+    num: BaseType,
+    group_separator: &'static str,
+) -> fmt::Result
+where
+    BaseType: Clone + Integer + Display + FromPrimitive,
+{
+    // Types that cannot hold a thousand never need grouping.
+    let thousand = match BaseType::from_u16(1000) {
+        Some(thousand) if !group_separator.is_empty() => thousand,
+        _ => return write!(f, "{}", num),
+    };
+
+    if num < thousand {
+        write!(f, "{}", num)
+    } else {
+        let (high, low) = num.div_rem(&thousand);
+        write_grouped(f, high, group_separator)?;
+        write!(f, "{}{:03}", group_separator, low)
+    }
+}
+
+/// Returns the next decimal digit of the fraction `rem / denom` and the remainder after it.
+///
+/// This computes `rem * 10 / denom` and `rem * 10 % denom` without ever exceeding `denom`,
+/// so it cannot overflow even if `denom` is close to the maximum of the type.
+/// `rem` must be less than `denom`.
+fn next_digit<BaseType>(rem: BaseType, denom: &BaseType) -> (u8, BaseType)
+where
+    BaseType: Clone + Integer,
+{
+    let gap = denom.clone() - rem.clone();
+    let mut digit = 0;
+    let mut acc = BaseType::zero();
+
+    // Add `rem` ten times, counting every time the sum wraps around at `denom`.
+    for _ in 0..10 {
+        if acc >= gap {
+            acc = acc - gap.clone();
+            digit += 1;
+        } else {
+            acc = acc + rem.clone();
+        }
+    }
+
+    (digit, acc)
+}
 
 #[cfg(test)]
+#[allow(clippy::identity_op)]
 mod tests {
     use super::*;
     use std::string::ToString;
@@ -361,18 +2079,14 @@ mod tests {
         assert_eq!(
             format!(
                 "{}B",
-                SizeFormatter::<u128, SIPrefixes, PointSeparated>::new(
-                    1_000_000_000_000_000_000_000
-                )
+                SizeFormatterSI128::new(1_000_000_000_000_000_000_000)
             ),
             "1.0ZB".to_string()
         );
         assert_eq!(
             format!(
                 "{}B",
-                SizeFormatter::<u128, SIPrefixes, PointSeparated>::new(
-                    1_000_000_000_000_000_000_000_000
-                )
+                SizeFormatterSI128::new(1_000_000_000_000_000_000_000_000)
             ),
             "1.0YB".to_string()
         );
@@ -383,23 +2097,64 @@ mod tests {
         assert_eq!(
             format!(
                 "{}B",
-                SizeFormatter::<u128, SIPrefixes, PointSeparated>::new(
-                    1_000_000_000_000_000_000_000_000_000
-                )
+                SizeFormatterSI128::new(1_000_000_000_000_000_000_000_000_000)
             ),
             "1000.0YB".to_string()
         );
         assert_eq!(
             format!(
                 "{}B",
-                SizeFormatter::<u128, SIPrefixes, PointSeparated>::new(
-                    1_000_000_000_000_000_000_000_000_000_000
-                )
+                SizeFormatterSI128::new(1_000_000_000_000_000_000_000_000_000_000)
             ),
             "1000000.0YB".to_string()
         );
     }
 
+    #[test]
+    fn u128_extremes() {
+        assert_eq!(
+            format!("{:.30}B", SizeFormatterSI128::new(u128::MAX)),
+            "340282366920938.463463374607431768211455YB".to_string()
+        );
+        assert_eq!(
+            format!("{:.30}B", SizeFormatterBinary128::new(u128::MAX)),
+            "281474976710655.999999999999999999999999YiB".to_string()
+        );
+        assert_eq!(
+            format!(
+                "{:.30}B",
+                SizeFormatter::<u64, SIPrefixes, PointSeparated>::new(u64::MAX)
+            ),
+            "18.446744073709551615EB".to_string()
+        );
+        assert_eq!(
+            format!(
+                "{:.30}B",
+                SizeFormatter::<u64, BinaryPrefixes, PointSeparated>::new(u64::MAX)
+            ),
+            "15.999999999999999999EiB".to_string()
+        );
+    }
+
+    #[test]
+    fn denominator_close_to_overflow() {
+        struct HugeSteps;
+
+        impl PrefixType for HugeSteps {
+            const PREFIX_SIZE: u32 = u32::MAX;
+
+            const PREFIXES: &'static [&'static str] = &["", "a", "b", "c", "d"];
+        }
+
+        assert_eq!(
+            format!(
+                "{:.12}",
+                SizeFormatter::<u128, HugeSteps, PointSeparated>::new(u128::MAX)
+            ),
+            "1.000000000931d".to_string()
+        );
+    }
+
     #[test]
     fn precision() {
         assert_eq!(format!("{:.9}B", SizeFormatterSI::new(1)), "1B".to_string());
@@ -457,11 +2212,3329 @@ mod tests {
     }
 
     #[test]
-    fn from() {
+    fn locales() {
         assert_eq!(
-            format!("{}B", SizeFormatterSI::from(546_987u32)),
-            "546.9kB".to_string()
+            format!("{}B", SizeFormatter::<u64, SIPrefixes, LocaleDE>::new(999)),
+            "999 B".to_string()
+        );
+        assert_eq!(
+            format!(
+                "{:.2}B",
+                SizeFormatter::<u64, SIPrefixes, LocaleDE>::new(42_420_000)
+            ),
+            "42,42 MB".to_string()
+        );
+        assert_eq!(
+            format!(
+                "{}B",
+                SizeFormatter::<u128, SIPrefixes, LocaleDE>::new(
+                    1_234_567_800_000_000_000_000_000_000_000
+                )
+            ),
+            "1.234.567,8 YB".to_string()
+        );
+        assert_eq!(
+            format!(
+                "{}B",
+                SizeFormatter::<u128, SIPrefixes, LocaleDE>::new(
+                    1_000_000_000_000_000_000_000_000_000
+                )
+            ),
+            "1.000,0 YB".to_string()
+        );
+
+        assert_eq!(
+            format!(
+                "{}{}",
+                SizeFormatter::<u64, SIPrefixes, LocaleFR>::new(42_500_000),
+                LocaleFR::BYTE_UNIT
+            ),
+            "42,5\u{A0}Mo".to_string()
+        );
+        assert_eq!(
+            format!(
+                "{}{}",
+                SizeFormatter::<u128, SIPrefixes, LocaleFR>::new(
+                    1_234_567_800_000_000_000_000_000_000_000
+                ),
+                LocaleFR::BYTE_UNIT
+            ),
+            "1\u{202F}234\u{202F}567,8\u{A0}Yo".to_string()
+        );
+
+        assert_eq!(
+            format!(
+                "{}B",
+                SizeFormatter::<u128, SIPrefixes, LocaleCH>::new(
+                    1_234_500_000_000_000_000_000_000_000
+                )
+            ),
+            "1'234.5 YB".to_string()
+        );
+    }
+
+    #[test]
+    fn custom_group_separator() {
+        struct Typographic;
+
+        impl DecimalSeparator for Typographic {
+            const SEPARATOR: &'static str = ".";
+            const GROUP_SEPARATOR: &'static str = "\u{2019}";
+        }
+
+        assert_eq!(
+            format!(
+                "{:.2}",
+                SizeFormatter::<u64, SIPrefixes, Typographic>::new(u64::MAX)
+            ),
+            "18.44E".to_string()
+        );
+        assert_eq!(
+            format!(
+                "{:.2}",
+                SizeFormatter::<u128, SIPrefixes, Typographic>::new(
+                    1_234_567_890_120_000_000_000_000_000_000_000
+                )
+            ),
+            "1\u{2019}234\u{2019}567\u{2019}890.12Y".to_string()
+        );
+    }
+
+    #[test]
+    fn myriad_prefixes() {
+        assert_eq!(
+            format!(
+                "{}",
+                SizeFormatter::<u64, JapaneseMyriadPrefixes, PointSeparated>::new(9_999)
+            ),
+            "9999".to_string()
+        );
+        assert_eq!(
+            format!(
+                "{}",
+                SizeFormatter::<u64, JapaneseMyriadPrefixes, PointSeparated>::new(12_345)
+            ),
+            "1.2万".to_string()
+        );
+        assert_eq!(
+            format!(
+                "{:.9}",
+                SizeFormatter::<u64, JapaneseMyriadPrefixes, PointSeparated>::new(123_456_789)
+            ),
+            "1.23456789億".to_string()
+        );
+        assert_eq!(
+            format!(
+                "{:.2}",
+                SizeFormatter::<u64, TraditionalChineseMyriadPrefixes, PointSeparated>::new(
+                    50_000_000_000_000_000
+                )
+            ),
+            "5.00京".to_string()
+        );
+        assert_eq!(
+            format!(
+                "{}",
+                SizeFormatter::<u64, SimplifiedChineseMyriadPrefixes, PointSeparated>::new(
+                    3_000_000_000_000
+                )
+            ),
+            "3.0万亿".to_string()
+        );
+        assert_eq!(
+            format!(
+                "{}",
+                SizeFormatter::<u64, SimplifiedChineseMyriadPrefixes, PointSeparated>::new(
+                    30_000_000_000_000_000
+                )
+            ),
+            "30000.0万亿".to_string()
+        );
+    }
+
+    #[test]
+    fn digit_sets() {
+        assert_eq!(
+            format!(
+                "{:.2}",
+                WithDigits::<_, FullWidthDigits>::new(SizeFormatterBinary::new(1_536))
+            ),
+            "１.５０Ｋｉ".to_string()
+        );
+        assert_eq!(
+            format!(
+                "{}",
+                WithDigits::<_, ArabicIndicDigits>::new(SizeFormatter::<
+                    u64,
+                    SIPrefixes,
+                    CommaSeparated,
+                >::new(42_000))
+            ),
+            "٤٢,٠k".to_string()
+        );
+        assert_eq!(
+            format!(
+                "{}",
+                WithDigits::<_, DevanagariDigits>::new(SizeFormatterSI::new(1_234_567))
+            ),
+            "१.२M".to_string()
         );
+        assert_eq!(
+            format!(
+                "{}",
+                WithDigits::<_, AsciiDigits>::new(SizeFormatterSI::new(1_234_567))
+            ),
+            "1.2M".to_string()
+        );
+    }
+
+    #[test]
+    fn alignment() {
+        assert_eq!(
+            format!("{:8}|", SizeFormatterSI::new(1_500)),
+            "    1.5k|".to_string()
+        );
+        assert_eq!(
+            format!("{:<8}|", SizeFormatterSI::new(1_500)),
+            "1.5k    |".to_string()
+        );
+        assert_eq!(
+            format!("{:*^9.2}|", SizeFormatterSI::new(1_500)),
+            "**1.50k**|".to_string()
+        );
+        assert_eq!(
+            format!("{:2}|", SizeFormatterSI::new(1_500)),
+            "1.5k|".to_string()
+        );
+        assert_eq!(
+            format!(
+                "{:8}|",
+                SizeFormatter::<u64, SIPrefixes, LocaleFR>::new(42_500_000)
+            ),
+            "  42,5\u{A0}M|".to_string()
+        );
+        assert_eq!(
+            format!(
+                "{:10}|",
+                WithDigits::<_, FullWidthDigits>::new(SizeFormatterBinary::new(1_536))
+            ),
+            " １.５Ｋｉ|".to_string()
+        );
+    }
+
+    #[test]
+    #[cfg(target_has_atomic = "64")]
+    fn atomic_counter() {
+        use core::sync::atomic::{AtomicU64, Ordering};
+
+        let owned =
+            AtomicSizeFormatter::<_, BinaryPrefixes, PointSeparated>::new(AtomicU64::new(0));
+        assert_eq!(format!("{}B", owned), "0B".to_string());
+
+        owned.counter().fetch_add(1_536, Ordering::Relaxed);
+        assert_eq!(format!("{:>8.2}B", owned), "  1.50KiB".to_string());
+
+        let counter = AtomicU64::new(999);
+        let borrowed = AtomicSizeFormatter::<_, SIPrefixes, PointSeparated>::new(&counter);
+        assert_eq!(format!("{}B", borrowed), "999B".to_string());
+
+        counter.store(1_000, Ordering::Relaxed);
+        assert_eq!(format!("{}B", borrowed), "1.0kB".to_string());
+    }
+
+    #[test]
+    fn byte_counter() {
+        use core::ops::AddAssign;
+
+        let mut counter = ByteCounter::new();
+        assert_eq!(format!("{}", counter), "0B".to_string());
+
+        counter += 1_000u64;
+        counter += 24usize;
+        counter.add_assign(ByteSize(1_024));
+        assert_eq!(counter.total(), 2_048);
+        assert_eq!(counter.snapshot(), ByteSize(2_048));
+        assert_eq!(format!("{:.2}", counter), "2.00KiB".to_string());
+        assert_eq!(format!("{:>8}|", counter), "  2.0KiB|".to_string());
+
+        counter += u64::MAX;
+        assert_eq!(counter.total(), u64::MAX);
+    }
+
+    #[test]
+    fn rate_tracker() {
+        use core::time::Duration;
+
+        let mut tracker = RateTracker::new(Duration::from_secs(1));
+        assert_eq!(format!("{}", tracker), "0B/s".to_string());
+
+        tracker.sample(1_000, Duration::from_millis(500));
+        assert_eq!(format!("{}", tracker), "0B/s".to_string());
+
+        tracker.sample(2_000, Duration::from_millis(1_000));
+        assert_eq!(tracker.bytes_per_second(), 2_000);
+
+        // Out of order samples are ignored.
+        tracker.sample(1_000_000, Duration::from_millis(1_000));
+        assert_eq!(tracker.bytes_per_second(), 2_000);
+
+        // A sample covering one time constant has half the weight.
+        tracker.sample(6_000, Duration::from_millis(2_000));
+        assert_eq!(tracker.bytes_per_second(), 3_000);
+
+        // A restart counts the new total as transferred.
+        tracker.sample(3_000, Duration::from_millis(3_000));
+        assert_eq!(tracker.bytes_per_second(), 3_000);
+        assert_eq!(
+            format!("{:>9.2}", tracker.rate::<BinaryPrefixes, CommaSeparated>()),
+            "2,92KiB/s".to_string()
+        );
+    }
+
+    #[test]
+    fn counter_rate() {
+        use core::time::Duration;
+
+        let second = Duration::from_secs(1);
+        assert_eq!(
+            format!(
+                "{}",
+                RateFormatterBinary::from_counter(0, 1_536, second, CounterReset::Restart)
+            ),
+            "1.5KiB/s".to_string()
+        );
+        assert_eq!(
+            RateFormatterSI::from_counter(10, 4, second, CounterReset::Restart).bytes_per_second(),
+            4
+        );
+        assert_eq!(
+            RateFormatterSI::from_counter(250, 4, second, CounterReset::Wrap(255))
+                .bytes_per_second(),
+            10
+        );
+        assert_eq!(
+            RateFormatterSI::from_counter(0, 1, Duration::from_millis(1), CounterReset::Restart)
+                .bytes_per_second(),
+            1_000
+        );
+        assert_eq!(
+            RateFormatterSI::from_counter(
+                0,
+                u64::MAX,
+                Duration::from_nanos(1),
+                CounterReset::Restart
+            )
+            .bytes_per_second(),
+            u64::MAX
+        );
+        assert_eq!(
+            RateFormatterSI::from_counter(0, 5, Duration::from_secs(0), CounterReset::Restart)
+                .bytes_per_second(),
+            0
+        );
+    }
+
+    #[test]
+    fn transfer_summary() {
+        use core::time::Duration;
+
+        assert_eq!(
+            format!(
+                "{:.2}",
+                TransferSummarySI::new(1_500_000, Duration::from_millis(500)).with_files(1)
+            ),
+            "1 file, 1.50MB in 500ms (3.00MB/s)".to_string()
+        );
+        assert_eq!(
+            format!(
+                "{}|",
+                TransferSummary::<SIPrefixes, LocaleDE>::new(0, Duration::from_secs(0))
+            ),
+            "0 B in 0ms (0 B/s)|".to_string()
+        );
+        assert_eq!(
+            format!(
+                "{:<40}|",
+                TransferSummaryBinary::new(1_024, Duration::from_secs(7_260)).with_files(3)
+            ),
+            "3 files, 1.0KiB in 2h 1min (0B/s)       |".to_string()
+        );
+    }
+
+    #[test]
+    fn math() {
+        use math::{bytes_in, scale, Rounding};
+
+        assert_eq!(bytes_in::<SIPrefixes, u8>(0), Some(1));
+        assert_eq!(bytes_in::<SIPrefixes, u8>(1), None);
+        assert_eq!(bytes_in::<BinaryPrefixes, u128>(8), Some(1 << 80));
+
+        assert_eq!(
+            scale::<SIPrefixes, u64>(1_499, 0, 1, Rounding::HalfUp),
+            Some(1)
+        );
+        assert_eq!(
+            scale::<SIPrefixes, u64>(1_500, 0, 1, Rounding::HalfUp),
+            Some(2)
+        );
+        assert_eq!(scale::<SIPrefixes, u64>(1_001, 0, 1, Rounding::Up), Some(2));
+        assert_eq!(scale::<SIPrefixes, u64>(1_000, 0, 1, Rounding::Up), Some(1));
+        assert_eq!(scale::<SIPrefixes, u64>(0, 0, 1, Rounding::Up), Some(0));
+        assert_eq!(scale::<SIPrefixes, u64>(7, 2, 2, Rounding::Up), Some(7));
+        assert_eq!(scale::<SIPrefixes, u64>(19, 0, 6, Rounding::Up), Some(1));
+        assert_eq!(scale::<SIPrefixes, u64>(19, 6, 0, Rounding::Up), None);
+        assert_eq!(scale::<SIPrefixes, u16>(10, 0, 2, Rounding::Up), Some(1));
+        assert_eq!(scale::<SIPrefixes, u8>(10, 0, 1, Rounding::Up), None);
+
+        // The divisor does not fit into the type here.
+        assert_eq!(
+            scale::<BinaryPrefixes, u64>(u64::MAX, 0, 7, Rounding::HalfUp),
+            Some(0)
+        );
+        assert_eq!(
+            scale::<BinaryPrefixes, u64>(u64::MAX, 0, 7, Rounding::Up),
+            Some(1)
+        );
+        assert_eq!(
+            scale::<BinaryPrefixes, u128>(1 << 79, 0, 8, Rounding::HalfUp),
+            Some(1)
+        );
+        assert_eq!(
+            scale::<BinaryPrefixes, u128>((1 << 79) - 1, 0, 8, Rounding::HalfUp),
+            Some(0)
+        );
+    }
+
+    #[test]
+    fn rounding_with_odd_base() {
+        use math::{scale, Rounding};
+
+        struct Ternary;
+
+        impl PrefixType for Ternary {
+            const PREFIX_SIZE: u32 = 3;
+
+            const PREFIXES: &'static [&'static str] = &["", "t", "tt"];
+        }
+
+        // 4 / 9 is less than a half, 5 / 9 more.
+        assert_eq!(scale::<Ternary, u8>(4, 0, 2, Rounding::HalfUp), Some(0));
+        assert_eq!(scale::<Ternary, u8>(5, 0, 2, Rounding::HalfUp), Some(1));
+        assert_eq!(scale::<Ternary, u8>(13, 0, 2, Rounding::HalfUp), Some(1));
+        assert_eq!(scale::<Ternary, u8>(14, 0, 2, Rounding::HalfUp), Some(2));
+        // 121 / 243 is less than a half, 122 / 243 more, even though 243 does not fit.
+        assert_eq!(scale::<Ternary, u8>(121, 0, 5, Rounding::HalfUp), Some(0));
+        assert_eq!(scale::<Ternary, u8>(122, 0, 5, Rounding::HalfUp), Some(1));
+    }
+
+    #[test]
+    #[cfg(feature = "float")]
+    fn representations() {
+        let mut representations = SizeFormatterSI::new(1_500_000).representations();
+        assert_eq!(representations.len(), 9);
+        assert_eq!(representations.next(), Some((1_500_000.0, "")));
+        assert_eq!(representations.next(), Some((1_500.0, "k")));
+        assert_eq!(representations.next(), Some((1.5, "M")));
+        assert_eq!(representations.len(), 6);
+        assert_eq!(representations.last().map(|(_, prefix)| prefix), Some("Y"));
+
+        let all = SizeFormatterBinary128::new(u128::MAX)
+            .representations()
+            .last();
+        assert_eq!(all, Some((281_474_976_710_656.0, "Yi")));
+    }
+
+    #[test]
+    fn chosen_prefix() {
+        let prefix = SizeFormatterSI::new(999).chosen_prefix();
+        assert_eq!((prefix.index(), prefix.symbol()), (0, ""));
+
+        let prefix = SizeFormatterBinary::new(1_024 * 1_024).chosen_prefix();
+        assert_eq!((prefix.index(), prefix.symbol()), (2, "Mi"));
+
+        let prefix = SizeFormatterSI128::new(u128::MAX).chosen_prefix();
+        assert_eq!((prefix.index(), prefix.symbol()), (8, "Y"));
+
+        assert!(
+            SizeFormatterSI::new(2_000_000).chosen_prefix()
+                > SizeFormatterSI::new(999_999).chosen_prefix()
+        );
+    }
+
+    #[test]
+    fn size_list() {
+        let sizes = [1_200_000u64, 440_000, 2_000_000_000];
+
+        assert_eq!(
+            format!("{}", SizeListSI::new(&sizes).with_unit("B")),
+            "1.2MB, 440.0kB, 2.0GB".to_string()
+        );
+        assert_eq!(
+            format!(
+                "{:.4}",
+                SizeListSI::new(&sizes)
+                    .with_unit("B")
+                    .with_shared_prefix()
+                    .with_separator(" | ")
+            ),
+            "0.0012GB | 0.0004GB | 2.0000GB".to_string()
+        );
+        assert_eq!(
+            format!(
+                "{:>6}",
+                SizeListBinary::new(sizes.iter().map(|size| size / 1_000))
+            ),
+            " 1.1Ki,    440,  1.9Mi".to_string()
+        );
+        assert_eq!(
+            format!("{}", SizeListSI::new(&[] as &[u64]).with_shared_prefix()),
+            "".to_string()
+        );
+    }
+
+    #[test]
+    fn formatted_size() {
+        let widest = FormattedSize::new(format_args!(
+            "{:.100}",
+            SizeFormatter::<u128, SIPrefixes, LocaleFR>::new(u128::MAX)
+        ))
+        .unwrap();
+        assert_eq!(
+            widest.as_str(),
+            "340\u{202F}282\u{202F}366\u{202F}920\u{202F}938,463463374607431768211455\u{A0}Y"
+        );
+
+        let small = FormattedSize::new(SizeFormatterBinary::new(1_536)).unwrap();
+        assert_eq!(small, FormattedSize::new("1.5Ki").unwrap());
+        assert_eq!(
+            format!("{:>7}|{:?}", small, small),
+            "  1.5Ki|\"1.5Ki\"".to_string()
+        );
+        assert!(small.starts_with("1.5"));
+
+        assert!(FormattedSize::new(format_args!("{:1$}", "", FormattedSize::CAPACITY)).is_ok());
+        assert!(
+            FormattedSize::new(format_args!("{:1$}", "", FormattedSize::CAPACITY + 1)).is_err()
+        );
+
+        // The widest sizes of the built-in prefix systems and locales.
+        let fits = |formatted: fmt::Arguments| {
+            let mut output = std::string::String::new();
+            fmt::Write::write_fmt(&mut output, formatted).unwrap();
+            assert!(output.len() <= FormattedSize::CAPACITY, "{}", output);
+            assert_eq!(FormattedSize::new(&output[..]).unwrap().as_str(), output);
+            output.len()
+        };
+        let myriad =
+            SizeFormatter::<u128, SimplifiedChineseMyriadPrefixes, LocaleFR>::new(u128::MAX);
+        assert_eq!(fits(format_args!("{:.100}", myriad)), 72);
+        let durations = SizeFormatter::<u128, DurationUnits, LocaleFR>::new(u128::MAX);
+        assert_eq!(fits(format_args!("{:.100}", durations)), 65);
+        let unscaled = SizeFormatter::<u128, SIPrefixesExtended, LocaleFR>::new(u128::MAX)
+            .with_forced_prefix(0)
+            .clamp(u128::MAX - 1, ClampStyle::Plus)
+            .with_approximation_marker("\u{2248}")
+            .with_plus_sign();
+        assert_eq!(fits(format_args!("{:.100}", unscaled)), 82);
+        let fractional = SizeFormatter::<u128, SIPrefixesExtended, LocaleFR>::new(u128::MAX / 10)
+            .with_forced_prefix(10)
+            .with_long_names("")
+            .with_approximation_marker("\u{2248}")
+            .with_plus_sign();
+        fits(format_args!("{:.100}", fractional));
+    }
+
+    #[test]
+    fn from() {
+        assert_eq!(
+            format!("{}B", SizeFormatterSI::from(546_987u32)),
+            "546.9kB".to_string()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn to_size_string() {
+        assert_eq!(42_000_000u64.to_si_string(), "42.0MB".to_string());
+        assert_eq!(
+            (42 * 1024 * 1024u64).to_binary_string(),
+            "42.0MiB".to_string()
+        );
+        assert_eq!(255u8.to_si_string(), "255B".to_string());
+        assert_eq!(
+            u128::MAX.to_binary_string_with_precision(0),
+            "281474976710655YiB".to_string()
+        );
+        assert_eq!(
+            1_999_999_999usize.to_si_string_with_precision(4),
+            "1.9999GB".to_string()
+        );
+        assert_eq!(
+            65_535u16.to_binary_string_with_precision(2),
+            "63.99KiB".to_string()
+        );
+    }
+
+    #[test]
+    fn finder_preset() {
+        use presets::Finder;
+
+        assert_eq!(
+            format!("{}", Finder::<PointSeparated>::new(0)),
+            "Zero KB".to_string()
+        );
+        assert_eq!(
+            format!("{}", Finder::<PointSeparated>::new(0).numeric()),
+            "0 bytes".to_string()
+        );
+        assert_eq!(
+            format!("{}", Finder::<PointSeparated>::new(1_000)),
+            "1 KB".to_string()
+        );
+        assert_eq!(
+            format!("{}", Finder::<PointSeparated>::new(999_499)),
+            "999 KB".to_string()
+        );
+        assert_eq!(
+            format!("{}", Finder::<PointSeparated>::new(999_500)),
+            "1 MB".to_string()
+        );
+        assert_eq!(
+            format!("{}", Finder::<PointSeparated>::new(1_405_000)),
+            "1.4 MB".to_string()
+        );
+        assert_eq!(
+            format!("{}", Finder::<PointSeparated>::new(999_949_999)),
+            "999.9 MB".to_string()
+        );
+        assert_eq!(
+            format!("{}", Finder::<PointSeparated>::new(999_950_000)),
+            "1 GB".to_string()
+        );
+        assert_eq!(
+            format!("{}", Finder::<PointSeparated>::new(1_405_000_000)),
+            "1.41 GB".to_string()
+        );
+        assert_eq!(
+            format!("{}", Finder::<PointSeparated>::new(1_500_000_000)),
+            "1.5 GB".to_string()
+        );
+        assert_eq!(
+            format!("{}", Finder::<PointSeparated>::new(999_995_000_000)),
+            "1 TB".to_string()
+        );
+        assert_eq!(
+            format!("{}", Finder::<PointSeparated>::new(u64::MAX)),
+            "18.45 EB".to_string()
+        );
+        assert_eq!(
+            format!("{}", Finder::<LocaleDE>::new(500)),
+            "500 Byte".to_string()
+        );
+        assert_eq!(
+            format!("{:>9}|", Finder::<LocaleFR>::new(1_434_000)),
+            "   1,4\u{A0}Mo|".to_string()
+        );
+    }
+
+    #[test]
+    fn ls_preset() {
+        use presets::LsHuman;
+
+        for &(bytes, output) in &[
+            (0, "0"),
+            (1_023, "1023"),
+            (1_024, "1.0K"),
+            (1_025, "1.1K"),
+            (1_536, "1.5K"),
+            (10_137, "9.9K"),
+            (10_138, "10K"),
+            (10_240, "10K"),
+            (10_241, "11K"),
+            (1_047_552, "1023K"),
+            (1_047_553, "1.0M"),
+            (1 << 30, "1.0G"),
+            (5 << 40, "5.0T"),
+            (u64::MAX, "16E"),
+        ] {
+            assert_eq!(format!("{}", LsHuman::new(bytes)), output, "{}", bytes);
+        }
+
+        assert_eq!(format!("{:>5}|", LsHuman::new(4_096)), " 4.0K|");
+    }
+
+    #[test]
+    fn nginx_preset() {
+        use presets::Nginx;
+
+        for &(input, bytes, output) in &[
+            ("0", 0, "0"),
+            ("1023", 1_023, "1023"),
+            ("1024", 1_024, "1k"),
+            ("8k", 8 << 10, "8k"),
+            ("1024K", 1 << 20, "1m"),
+            ("512m", 512 << 20, "512m"),
+            ("2g", 2 << 30, "2g"),
+            ("17179869183G", 17_179_869_183 << 30, "17179869183g"),
+        ] {
+            let size: Nginx = input.parse().unwrap();
+            assert_eq!(size.bytes(), bytes);
+            assert_eq!(format!("{}", size), output.to_string());
+            assert_eq!(output.parse::<Nginx>(), Ok(size));
+        }
+
+        for &(input, kind, position) in &[
+            ("", ParseErrorKind::InvalidNumber, 0),
+            ("k", ParseErrorKind::InvalidNumber, 0),
+            ("-1k", ParseErrorKind::InvalidNumber, 0),
+            ("+1k", ParseErrorKind::InvalidNumber, 0),
+            ("1.5m", ParseErrorKind::InvalidNumber, 1),
+            ("1 m", ParseErrorKind::InvalidNumber, 1),
+            ("1kb", ParseErrorKind::InvalidNumber, 1),
+            ("1t", ParseErrorKind::InvalidNumber, 1),
+            ("17179869184g", ParseErrorKind::Overflow, 0),
+        ] {
+            assert_eq!(
+                input.parse::<Nginx>(),
+                Err(ParseSizeError::new(kind, position)),
+                "{}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn runtime_locale() {
+        let format = |tag: &str, num: u128| {
+            let options = FormatOptions::new().with_locale(Locale::from_tag(tag).unwrap());
+            format!("{}", SizeFormatterSI128::new(num).with_options(&options))
+        };
+
+        assert_eq!(format("en", 42_500_000), "42.5M".to_string());
+        assert_eq!(format("DE_at", 42_500_000), "42,5 M".to_string());
+        assert_eq!(
+            format("de-CH", 1_234_500_000_000_000_000_000_000_000),
+            "1'234.5 Y".to_string()
+        );
+        assert_eq!(
+            format("fr", 1_234_500_000_000_000_000_000_000_000),
+            "1\u{202F}234,5\u{A0}Y".to_string()
+        );
+        assert!(Locale::from_tag("xx").is_none());
+        assert_eq!(Locale::from_tag("fr-FR").unwrap().byte_unit(), "o");
+
+        let locale = Locale::new(",")
+            .with_group_separator("_")
+            .with_unit_spacing(" ")
+            .with_digits::<FullWidthDigits>();
+        let options = FormatOptions::new().with_locale(locale);
+        assert_eq!(
+            format!(
+                "{:>9}|",
+                SizeFormatterBinary::new(1_536).with_options(&options)
+            ),
+            "１,５ Ｋｉ|".to_string()
+        );
+
+        // Options without a locale keep the separator type of the formatter.
+        assert_eq!(
+            format!(
+                "{}",
+                SizeFormatter::<u64, SIPrefixes, LocaleDE>::new(1_500)
+                    .with_options(&FormatOptions::new())
+            ),
+            "1,5 k".to_string()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn preserved_size() {
+        for &(input, bytes) in &[
+            ("0", 0),
+            ("512B", 512),
+            ("42MB", 42_000_000),
+            ("1.5GiB", 1_610_612_736),
+            ("1.0000001kB", 1_000),
+            ("0.999999999999999999999999999999GiB", 1_073_741_823),
+            ("15EiB", 15 << 60),
+            ("8k", 8_000),
+            ("15.99999999999999999999EiB", u64::MAX),
+        ] {
+            let size: PreservedSize = input.parse().unwrap();
+            assert_eq!(size.bytes(), bytes);
+            assert_eq!(size.original(), Some(input));
+            assert_eq!(format!("{}", size), input.to_string());
+        }
+
+        for input in &[
+            "", "B", ".5k", "1.", "1..5k", "1.5.0k", "1 kB", "1K", "1kb", "16.1EiB", "1ZB",
+        ] {
+            assert!(input.parse::<PreservedSize>().is_err());
+        }
+
+        let mut size: PreservedSize = "1024KiB".parse().unwrap();
+        size.set_bytes(1 << 20);
+        assert_eq!(format!("{:>9}|", size), "  1024KiB|".to_string());
+        assert_eq!(size, PreservedSize::new(1 << 20));
+        size.set_bytes(1_536);
+        assert_eq!(size.original(), None);
+        assert_eq!(format!("{}", size), "1536B".to_string());
+        size.set_bytes(3 << 40);
+        assert_eq!(format!("{}", size), "3TiB".to_string());
+        assert_eq!(format!("{}", PreservedSize::new(0)), "0B".to_string());
+    }
+
+    #[test]
+    fn byte_size_parsing() {
+        for &(input, bytes) in &[
+            ("0", 0),
+            ("512", 512),
+            ("512B", 512),
+            ("8k", 8_000),
+            ("8Ki", 8_192),
+            ("42MB", 42_000_000),
+            ("1.5GiB", 1_610_612_736),
+            ("0.5B", 0),
+            ("1.0000000001kB", 1_000),
+            ("15EiB", 15 << 60),
+            ("18446744073709551615", u64::MAX),
+        ] {
+            assert_eq!(input.parse(), Ok(ByteSize(bytes)), "{}", input);
+        }
+
+        for &(input, kind, position) in &[
+            ("", ParseErrorKind::InvalidNumber, 0),
+            ("B", ParseErrorKind::InvalidNumber, 0),
+            ("1.", ParseErrorKind::InvalidNumber, 2),
+            (".5", ParseErrorKind::InvalidNumber, 0),
+            ("1.5.0GB", ParseErrorKind::InvalidNumber, 3),
+            ("1 GB", ParseErrorKind::UnknownPrefix, 1),
+            ("1gB", ParseErrorKind::UnknownPrefix, 1),
+            ("16EiB", ParseErrorKind::Overflow, 0),
+            ("1kiB", ParseErrorKind::TrailingCharacters, 2),
+            ("1GBB", ParseErrorKind::TrailingCharacters, 3),
+        ] {
+            assert_eq!(
+                input.parse::<ByteSize>(),
+                Err(ParseSizeError::new(kind, position)),
+                "{}",
+                input
+            );
+        }
+
+        for &bytes in &[0, 1, 1_024, 1_536, 1 << 40, (3 << 30) + (1 << 29)] {
+            let size = ByteSize(bytes);
+            assert_eq!(format!("{:.20}", size).parse(), Ok(size));
+        }
+    }
+
+    #[test]
+    fn lenient_parsing() {
+        let lenient = SizeParser::new().with_mode(ParseMode::Lenient);
+
+        for &(input, bytes) in &[
+            ("1.5 gib", 1_610_612_736),
+            (" 42 MB ", 42_000_000),
+            ("42\tmb", 42_000_000),
+            ("8 K", 8_000),
+            ("8kI", 8_192),
+            ("512 b", 512),
+            ("\n512\n", 512),
+            ("1.5GiB", 1_610_612_736),
+        ] {
+            assert_eq!(lenient.parse(input), Ok(ByteSize(bytes)), "{:?}", input);
+        }
+
+        for input in &["", " ", "1 . 5GB", "1.5 G iB", "- 1GB", "1GB x"] {
+            assert!(lenient.parse(input).is_err(), "{:?}", input);
+        }
+
+        assert_eq!(SizeParser::new().mode(), ParseMode::Strict);
+        assert!(SizeParser::new().parse(" 42MB").is_err());
+        assert!(SizeParser::new().parse("42mb").is_err());
+    }
+
+    #[test]
+    fn parse_error_messages() {
+        let lenient = SizeParser::new().with_mode(ParseMode::Lenient);
+
+        for &(input, message) in &[
+            ("  1.x GB", "invalid number at position 4"),
+            (" 1 XB", "unknown prefix at position 3"),
+            ("1 GB x", "unexpected characters at position 4"),
+            (" 99999999999 EB", "size at position 1 is too large"),
+        ] {
+            assert_eq!(lenient.parse(input).unwrap_err().to_string(), message);
+        }
+
+        assert_eq!(
+            parse_range("2G..1G").unwrap_err().to_string(),
+            "invalid range at position 0"
+        );
+        assert_eq!(
+            "12345".parse::<SortableSize>(),
+            Err(ParseSizeError::new(ParseErrorKind::InvalidNumber, 5))
+        );
+        assert_eq!(
+            "000000000000000000001".parse::<SortableSize>(),
+            Err(ParseSizeError::new(ParseErrorKind::TrailingCharacters, 20))
+        );
+    }
+
+    #[test]
+    fn prefix_policy() {
+        let si = SizeParser::new().with_policy(PrefixPolicy::Si);
+        let binary = SizeParser::new().with_policy(PrefixPolicy::Binary);
+        let strict = SizeParser::new();
+
+        for &(input, si_bytes, binary_bytes, strict_bytes) in &[
+            ("10K", Some(10_000), Some(10_240), None),
+            ("10KB", Some(10_000), Some(10_240), None),
+            ("10k", Some(10_000), Some(10_240), Some(10_000)),
+            ("10kB", Some(10_000), Some(10_240), Some(10_000)),
+            ("10KiB", Some(10_240), Some(10_240), Some(10_240)),
+            ("1MB", Some(1_000_000), Some(1 << 20), Some(1_000_000)),
+            (
+                "1.5G",
+                Some(1_500_000_000),
+                Some(3 << 29),
+                Some(1_500_000_000),
+            ),
+            ("512", Some(512), Some(512), Some(512)),
+            ("10kiB", None, None, None),
+        ] {
+            assert_eq!(si.parse(input).ok(), si_bytes.map(ByteSize), "{}", input);
+            assert_eq!(
+                binary.parse(input).ok(),
+                binary_bytes.map(ByteSize),
+                "{}",
+                input
+            );
+            assert_eq!(
+                strict.parse(input).ok(),
+                strict_bytes.map(ByteSize),
+                "{}",
+                input
+            );
+        }
+
+        assert_eq!(strict.policy(), PrefixPolicy::IecStrict);
+        assert_eq!(
+            binary.with_mode(ParseMode::Lenient).parse(" 4 gb "),
+            Ok(ByteSize(4 << 30))
+        );
+        assert_eq!(
+            binary.parse("16E"),
+            Err(ParseSizeError::new(ParseErrorKind::Overflow, 0))
+        );
+    }
+
+    #[test]
+    fn bit_parsing() {
+        let parser = SizeParser::new();
+
+        for &(input, bits) in &[
+            ("100Mbit", 100_000_000),
+            ("100Mbits", 100_000_000),
+            ("1Gbps", 1_000_000_000),
+            ("1Gbit/s", 1_000_000_000),
+            ("1kb", 1_000),
+            ("8Kib", 8_192),
+            ("1Mibit", 1 << 20),
+            ("1MB", 8_000_000),
+            ("1MB/s", 8_000_000),
+            ("2KiBps", 16_384),
+            ("12b", 12),
+            ("12", 12),
+            ("1.5b", 1),
+            ("2EB", 16_000_000_000_000_000_000),
+        ] {
+            assert_eq!(parser.parse_bits(input), Ok(bits), "{}", input);
+        }
+
+        for &(input, kind, position) in &[
+            ("1Kb", ParseErrorKind::UnknownPrefix, 1),
+            ("3EB", ParseErrorKind::Overflow, 0),
+            ("bit", ParseErrorKind::InvalidNumber, 0),
+            ("1 Mbit", ParseErrorKind::UnknownPrefix, 1),
+            ("1Mbitx", ParseErrorKind::TrailingCharacters, 2),
+        ] {
+            assert_eq!(
+                parser.parse_bits(input),
+                Err(ParseSizeError::new(kind, position)),
+                "{}",
+                input
+            );
+        }
+
+        let lenient = parser.with_mode(ParseMode::Lenient);
+        assert_eq!(lenient.parse_bits(" 100 MBit/S "), Ok(100_000_000));
+        assert_eq!(lenient.parse_bits(" 1 mb "), Ok(1_000_000));
+        assert_eq!(lenient.parse_bits(" 1 mB "), Ok(8_000_000));
+        // The case of "b" still tells bits from bytes in longer units.
+        assert_eq!(lenient.parse_bits("1GBps"), Ok(8_000_000_000));
+        assert_eq!(lenient.parse_bits("1gbps"), Ok(1_000_000_000));
+        assert_eq!(lenient.parse_bits("1 gB/s"), Ok(8_000_000_000));
+        assert!(lenient.parse_bits("1Gb/s").is_err());
+        assert_eq!(
+            lenient.parse_bits(" 3 EB"),
+            Err(ParseSizeError::new(ParseErrorKind::Overflow, 1))
+        );
+
+        assert_eq!(
+            parser.parse_bits_as_bytes("100Mbit"),
+            Ok(ByteSize(12_500_000))
+        );
+        assert_eq!(parser.parse_bits_as_bytes("7b"), Ok(ByteSize(0)));
+        assert_eq!(parser.parse_bits_as_bytes("1KiB"), Ok(ByteSize(1_024)));
+    }
+
+    #[test]
+    fn partial_parsing() {
+        for &(input, bytes, rest) in &[
+            ("4GiB;burst=1GiB", 4 << 30, ";burst=1GiB"),
+            ("1.5GB,", 1_500_000_000, ","),
+            ("12;", 12, ";"),
+            ("12abc", 12, "abc"),
+            ("1kBx", 1_000, "x"),
+            ("1 GB", 1, " GB"),
+            ("42", 42, ""),
+            ("8KiB", 8_192, ""),
+            ("5 megabytes left", 5_000_000, " left"),
+            ("1.5 kibibyte, 2", 1_536, ", 2"),
+            ("3bytes;", 3, ";"),
+            ("2 gigabyte", 2_000_000_000, ""),
+            ("5 megabytesx", 5, " megabytesx"),
+        ] {
+            assert_eq!(
+                parse_partial(input),
+                Ok((ByteSize(bytes), rest)),
+                "{}",
+                input
+            );
+        }
+
+        let mut rest = "limit=4GiB;burst=1GiB";
+        let mut sizes = [ByteSize(0); 2];
+        for size in &mut sizes {
+            let value = &rest[rest.find('=').unwrap() + 1..];
+            let (parsed, remaining) = parse_partial(value).unwrap();
+            *size = parsed;
+            rest = remaining.trim_start_matches(';');
+        }
+        assert_eq!(sizes, [ByteSize(4 << 30), ByteSize(1 << 30)]);
+        assert_eq!(rest, "");
+
+        let lenient = SizeParser::new().with_mode(ParseMode::Lenient);
+        assert_eq!(
+            lenient.parse_partial(" 2 MegaBytes free"),
+            Ok((ByteSize(2_000_000), " free"))
+        );
+
+        for &(input, kind, position) in &[
+            ("", ParseErrorKind::InvalidNumber, 0),
+            (";1GB", ParseErrorKind::InvalidNumber, 0),
+            ("1.;", ParseErrorKind::InvalidNumber, 2),
+            ("16EiB;", ParseErrorKind::Overflow, 0),
+        ] {
+            assert_eq!(
+                parse_partial(input),
+                Err(ParseSizeError::new(kind, position)),
+                "{}",
+                input
+            );
+        }
+
+        let lenient = SizeParser::new().with_mode(ParseMode::Lenient);
+        assert_eq!(
+            lenient.parse_partial(" 4 gib ; x"),
+            Ok((ByteSize(4 << 30), " ; x"))
+        );
+    }
+
+    #[test]
+    fn combinator_parsers() {
+        let binary = parser::size::<BinaryPrefixes>();
+        let si = parser::size::<SIPrefixes>();
+
+        assert_eq!(binary("4GiB;"), Ok((";", 4 << 30)));
+        assert_eq!(binary("4Gi"), Ok(("", 4 << 30)));
+        assert_eq!(binary("4GB"), Ok(("GB", 4)));
+        assert_eq!(si("4GiB"), Ok(("iB", 4_000_000_000)));
+        assert_eq!(si("1.5kB "), Ok((" ", 1_500)));
+        assert_eq!(
+            si("x"),
+            Err(ParseSizeError::new(ParseErrorKind::InvalidNumber, 0))
+        );
+        assert_eq!(
+            binary("16EiB"),
+            Err(ParseSizeError::new(ParseErrorKind::Overflow, 0))
+        );
+
+        let byte_size = parser::byte_size();
+        assert_eq!(byte_size("4GiB,1kB"), Ok((",1kB", ByteSize(4 << 30))));
+        assert_eq!(byte_size("1kB"), Ok(("", ByteSize(1_000))));
+    }
+
+    #[test]
+    fn const_parsing() {
+        const LIMIT: u64 = parse_size_const("256MiB");
+        assert_eq!(LIMIT, 256 << 20);
+
+        for input in &[
+            "0",
+            "512",
+            "512B",
+            "8k",
+            "8Ki",
+            "42MB",
+            "1.5GiB",
+            "0.5B",
+            "1.0000000001kB",
+            "15EiB",
+            "18.446744073709551615EB",
+            "18446744073709551615",
+            "0ZB",
+            "0.00YiB",
+        ] {
+            assert_eq!(
+                Ok(ByteSize(parse_size_const(input))),
+                input.parse(),
+                "{}",
+                input
+            );
+        }
+
+        for input in &[
+            "", "B", "1.", ".5", "1K", "1ki", "1kiB", "1GBB", "1 GB", "16EiB", "1ZB", "0.001ZB",
+        ] {
+            assert!(
+                std::panic::catch_unwind(|| parse_size_const(input)).is_err(),
+                "{}",
+                input
+            );
+            assert!(input.parse::<ByteSize>().is_err(), "{}", input);
+        }
+    }
+
+    #[test]
+    fn const_parsing_digit_separators() {
+        for &(input, bytes) in &[
+            ("1_000MB", 1_000_000_000),
+            ("1_0_0", 100),
+            ("1.2_5kB", 1_250),
+            ("0_0ZB", 0),
+        ] {
+            assert_eq!(parse_size_const(input), bytes, "{}", input);
+        }
+
+        for input in &["_1", "1_", "1__0", "1._5", "1_.5", "1_kB"] {
+            assert!(
+                std::panic::catch_unwind(|| parse_size_const(input)).is_err(),
+                "{}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn size_macro() {
+        const LIMIT: u64 = size!(256 MiB);
+
+        assert_eq!(LIMIT, 256 << 20);
+        assert_eq!(size!(1.5 GiB), 1_610_612_736);
+        assert_eq!(size!(1.5GiB), 1_610_612_736);
+        assert_eq!(size!(8 k), 8_000);
+        assert_eq!(size!(8 KiB), 8_192);
+        assert_eq!(size!(0.5 B), 0);
+        assert_eq!(size!(15 EiB), 15 << 60);
+        assert_eq!(size!(512), 512);
+        assert_eq!(size!(18446744073709551615), u64::MAX);
+    }
+
+    #[test]
+    fn unit_word_parsing() {
+        let strict = SizeParser::new();
+        let lenient = strict.with_mode(ParseMode::Lenient);
+
+        for &(input, bytes) in &[
+            ("2 megabytes", 2_000_000),
+            ("1 megabyte", 1_000_000),
+            ("3 kibibytes", 3_072),
+            ("1.5 gigabytes", 1_500_000_000),
+            ("1.5gibibyte", 1_610_612_736),
+            ("512 bytes", 512),
+            ("1 byte", 1),
+            ("15 exbibytes", 15 << 60),
+            ("0.5 kilobytes", 500),
+        ] {
+            assert_eq!(strict.parse(input), Ok(ByteSize(bytes)), "{}", input);
+            assert_eq!(lenient.parse(input), Ok(ByteSize(bytes)), "{}", input);
+        }
+
+        assert_eq!(lenient.parse(" 2 MegaBytes "), Ok(ByteSize(2_000_000)));
+        assert!(strict.parse(" 2 MegaBytes ").is_err());
+        assert_eq!(
+            strict.parse("1 zettabyte"),
+            Err(ParseSizeError::new(ParseErrorKind::Overflow, 0))
+        );
+        assert_eq!(
+            strict.parse("1..5 megabytes"),
+            Err(ParseSizeError::new(ParseErrorKind::InvalidNumber, 2))
+        );
+
+        for input in &[
+            "megabytes",
+            "2 megabytess",
+            "2 kilo bytes",
+            "2 megabits",
+            "2k megabytes",
+        ] {
+            assert!(strict.parse(input).is_err(), "{}", input);
+            assert!(lenient.parse(input).is_err(), "{}", input);
+        }
+    }
+
+    #[test]
+    fn generic_parsing() {
+        for &(input, num) in &[("255", 255), ("0k", 0), ("0.0M", 0)] {
+            assert_eq!(
+                input
+                    .parse::<SizeFormatter<u8, SIPrefixes, PointSeparated>>()
+                    .map(|s| s.num()),
+                Ok(num),
+                "{}",
+                input
+            );
+        }
+        for &(input, kind) in &[
+            ("256", ParseErrorKind::Overflow),
+            ("1k", ParseErrorKind::Overflow),
+            ("0.2k", ParseErrorKind::Overflow),
+            ("1x", ParseErrorKind::UnknownPrefix),
+        ] {
+            assert_eq!(
+                input
+                    .parse::<SizeFormatter<u8, SIPrefixes, PointSeparated>>()
+                    .map(|s| s.num()),
+                Err(ParseSizeError::new(
+                    kind,
+                    if kind == ParseErrorKind::Overflow {
+                        0
+                    } else {
+                        1
+                    }
+                )),
+                "{}",
+                input
+            );
+        }
+
+        assert_eq!(
+            "300Yi".parse::<SizeFormatterBinary128>().map(|s| s.num()),
+            Ok(300 << 80)
+        );
+        assert_eq!(
+            "1.5Ki".parse::<SizeFormatterBinaryUsize>().map(|s| s.num()),
+            Ok(1_536)
+        );
+        assert_eq!(
+            "1,5 M"
+                .parse::<SizeFormatter<u64, SIPrefixes, LocaleDE>>()
+                .map(|s| s.num()),
+            Ok(1_500_000)
+        );
+        assert_eq!(
+            SizeFormatter::<u64, SIPrefixes, LocaleDE>::parse_with_unit("1,5 MB", "B")
+                .map(|s| s.num()),
+            Ok(1_500_000)
+        );
+        assert!("1.5 M"
+            .parse::<SizeFormatter<u64, SIPrefixes, LocaleDE>>()
+            .is_err());
+        assert!("1.5k"
+            .parse::<SizeFormatter<u64, SIPrefixes, CommaSeparated>>()
+            .is_err());
+
+        // The fraction is scaled exactly, even next to the maximum of the type.
+        for &(input, num) in &[
+            ("15.999999999999999999Ei", u64::MAX - 1),
+            ("0.00000000000000000001Ei", 0),
+            ("0.9999999999999999999999Ei", (1 << 60) - 1),
+        ] {
+            assert_eq!(
+                input.parse::<SizeFormatterBinary>().map(|s| s.num()),
+                Ok(num),
+                "{}",
+                input
+            );
+        }
+
+        for &bytes in &[0u128, 1, 999, 1_536, 1 << 70, (3 << 80) + (1 << 79)] {
+            let formatted = format!("{:.40}", SizeFormatterBinary128::new(bytes));
+            assert_eq!(
+                formatted.parse::<SizeFormatterBinary128>().map(|s| s.num()),
+                Ok(bytes),
+                "{}",
+                formatted
+            );
+        }
+    }
+
+    #[test]
+    fn byte_size_conversions() {
+        use core::convert::TryFrom;
+
+        assert_eq!(ByteSize::try_from("512MiB"), Ok(ByteSize(512 << 20)));
+        assert_eq!(ByteSize::try_from("1.5 GB").ok(), None);
+        assert_eq!(ByteSize::from(42), ByteSize(42));
+        assert_eq!(u64::from(ByteSize(42)), 42);
+    }
+
+    #[test]
+    fn unit_suggestions() {
+        for &(input, unit) in &[
+            ("10gb", Some("GB")),
+            ("10Gb", Some("GB")),
+            ("10KB", Some("kB")),
+            ("10K", Some("kB")),
+            ("10g", Some("GB")),
+            ("2 mib", Some("MiB")),
+            (" 2MIB ", Some("MiB")),
+            ("2ki", Some("KiB")),
+            ("1b", Some("B")),
+            ("10GB", None),
+            ("10k", None),
+            ("10Ki", None),
+            ("10", None),
+            ("10xyz", None),
+            ("10GBs", None),
+        ] {
+            assert_eq!(suggest_unit(input), unit, "{}", input);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn size_arguments() {
+        assert_eq!(parse_size_arg("2GiB"), Ok(ByteSize(2 << 30)));
+        assert_eq!(parse_size_arg("1.5kB"), Ok(ByteSize(1_500)));
+        assert_eq!(
+            parse_size_arg("1.5gb"),
+            Err(
+                "invalid size \"1.5gb\": unknown prefix at position 3 (did you mean \"1.5GB\"?)"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            parse_size_arg("lots"),
+            Err("invalid size \"lots\": invalid number at position 0".to_string())
+        );
+    }
+
+    #[test]
+    fn convenience_parsing() {
+        for &(input, bytes) in &[
+            ("1.5G", 1_500_000_000),
+            ("1.5g", 1_500_000_000),
+            ("10K", 10_000),
+            ("10KB", 10_000),
+            ("10KiB", 10_240),
+            (" 512 MiB\n", 512 << 20),
+            ("2 gigabytes", 2_000_000_000),
+            ("4096", 4_096),
+        ] {
+            assert_eq!(parse_size(input), Ok(bytes), "{:?}", input);
+        }
+
+        for input in &["", "lots", "1.5.G", "-1G", "1G 2G"] {
+            assert!(parse_size(input).is_err(), "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn range_parsing() {
+        for &(input, start, end) in &[
+            ("1GiB..10GiB", 1 << 30, 10 << 30),
+            ("1.5kB..=2kB", 1_500, 2_000),
+            ("1G-10G", 1_000_000_000, 10_000_000_000),
+            ("5-5", 5, 5),
+            ("..10GiB", 0, 10 << 30),
+            ("512MiB..", 512 << 20, u64::MAX),
+            ("..", 0, u64::MAX),
+        ] {
+            assert_eq!(parse_range(input), Ok(ByteSize(start)..=ByteSize(end)));
+        }
+
+        for &(input, kind, position) in &[
+            ("", ParseErrorKind::InvalidRange, 0),
+            ("1G", ParseErrorKind::InvalidRange, 0),
+            ("1G--2G", ParseErrorKind::InvalidNumber, 3),
+            ("-1G", ParseErrorKind::InvalidNumber, 0),
+            ("1G-", ParseErrorKind::InvalidNumber, 3),
+            ("1G...2G", ParseErrorKind::InvalidNumber, 4),
+            ("2G..1G", ParseErrorKind::InvalidRange, 0),
+            ("1G..2G..3G", ParseErrorKind::TrailingCharacters, 6),
+        ] {
+            assert_eq!(
+                parse_range(input),
+                Err(ParseSizeError::new(kind, position)),
+                "{}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "float")]
+    fn scaled_size() {
+        assert_eq!(
+            SizeFormatterSI::new(42_500_000).scaled(),
+            ScaledSize {
+                value: 42.5,
+                unit: "M",
+                exact_bytes: 42_500_000,
+            }
+        );
+        assert_eq!(
+            SizeFormatterSI::new(999).scaled(),
+            ScaledSize {
+                value: 999.0,
+                unit: "",
+                exact_bytes: 999,
+            }
+        );
+
+        let scaled = SizeFormatterBinary128::new(u128::MAX).scaled();
+        assert_eq!(scaled.unit, "Yi");
+        assert_eq!(scaled.exact_bytes, u128::MAX);
+        assert_eq!(scaled.value, 281_474_976_710_656.0);
+
+        let forced = SizeFormatterSI::new(1_500_000)
+            .with_forced_prefix(1)
+            .scaled();
+        assert_eq!((forced.value, forced.unit), (1_500.0, "k"));
+    }
+
+    #[test]
+    fn map() {
+        let options = FormatOptions::new().with_locale(Locale::from_tag("de").unwrap());
+        let formatter = SizeFormatterSI::new(2_000_000)
+            .with_options(&options)
+            .with_forced_prefix(1)
+            .map(|num| num - 500_000);
+        assert_eq!(format!("{}B", formatter), "1.500,0 kB".to_string());
+
+        let formatter = SizeFormatterBinary::new(1_536)
+            .require_exact()
+            .map(|num| num + 1);
+        assert!(formatter
+            .write_unpadded(&mut std::string::String::new(), Some(1))
+            .is_err());
+    }
+
+    #[test]
+    fn rate_period() {
+        use std::time::Duration;
+
+        let rate = |bytes_per_second, period| {
+            format!("{}", RateFormatterBinary::new(bytes_per_second).per(period))
+        };
+
+        assert_eq!(rate(1_024, Duration::from_secs(1)), "1.0KiB/s".to_string());
+        assert_eq!(
+            rate(1_024, Duration::from_secs(3_600)),
+            "3.5MiB/h".to_string()
+        );
+        assert_eq!(
+            rate(1_024, Duration::from_secs(604_800)),
+            "590.6MiB/week".to_string()
+        );
+        assert_eq!(
+            rate(1_024, Duration::from_millis(500)),
+            "512B/500ms".to_string()
+        );
+        assert_eq!(
+            rate(1_024, Duration::from_secs(5_400)),
+            "5.2MiB/1h 30min".to_string()
+        );
+        assert_eq!(
+            rate(u64::MAX, Duration::from_secs(60)),
+            "959.9EiB/min".to_string()
+        );
+        assert_eq!(
+            rate(u64::MAX, Duration::new(u64::MAX, 0)),
+            "281474976710655.9YiB/213503982334601d 7h".to_string()
+        );
+    }
+
+    #[test]
+    fn format_ratio() {
+        let locale = Locale::from_tag("fr").unwrap();
+
+        assert_eq!(
+            format!("{:.5}", FormatRatio::new(Ratio::new(1u8, 7))),
+            "0.14285".to_string()
+        );
+        assert_eq!(
+            format!("{:.0}", FormatRatio::new(Ratio::new(u128::MAX, 1))),
+            "340282366920938463463374607431768211455".to_string()
+        );
+        assert_eq!(
+            format!(
+                "{:*^12.2}",
+                FormatRatio::new(Ratio::new(12_345u32, 4)).with_locale(&locale)
+            ),
+            "**3\u{202F}086,25**".to_string()
+        );
+        assert!(FormatRatio::new(Ratio::new(u64::MAX, 1 << 63)).is_exact(63));
+        assert!(!FormatRatio::new(Ratio::new(u64::MAX, 1 << 63)).is_exact(62));
+    }
+
+    #[test]
+    fn clamp() {
+        let clamped = |num, style| SizeFormatterBinary::new(num).clamp(1 << 40, style);
+
+        assert_eq!(
+            format!("{}B", clamped(1 << 40, ClampStyle::GreaterThan)),
+            "1.0TiB".to_string()
+        );
+        assert_eq!(
+            format!("{:>8}B", clamped(u64::MAX, ClampStyle::GreaterThan)),
+            "  >1.0TiB".to_string()
+        );
+        assert_eq!(
+            format!("{:.0}B", clamped((1 << 40) + 1, ClampStyle::Plus)),
+            "1+TiB".to_string()
+        );
+        assert_eq!(
+            clamped(1 << 50, ClampStyle::Plus).chosen_prefix().symbol(),
+            "Ti"
+        );
+
+        let options = FormatOptions::new().with_locale(Locale::from_tag("de").unwrap());
+        assert_eq!(
+            format!(
+                "{:.0}B",
+                SizeFormatterSI::new(5_000_000)
+                    .with_options(&options)
+                    .clamp(999_000, ClampStyle::Plus)
+            ),
+            "999+ kB".to_string()
+        );
+    }
+
+    #[test]
+    fn size_stats() {
+        assert!(SizeStatsSI::new(&[] as &[u64]).is_none());
+
+        let stats = SizeStatsBinary::new(&[3 << 20]).unwrap();
+        assert_eq!(
+            (
+                stats.min(),
+                stats.median(),
+                stats.mean(),
+                stats.max(),
+                stats.count()
+            ),
+            (3 << 20, 3 << 20, 3 << 20, 3 << 20, 1)
+        );
+
+        let sizes = [u64::MAX, 1, u64::MAX - 2, 7, u64::MAX];
+        let stats = SizeStatsSI::new(sizes.iter()).unwrap();
+        assert_eq!(stats.min(), 1);
+        assert_eq!(stats.median(), u64::MAX - 2);
+        assert_eq!(stats.mean(), (u64::MAX / 5) * 3 + 1);
+        assert_eq!(stats.max(), u64::MAX);
+
+        let stats = SizeStatsSI::new(vec![0, u64::MAX]).unwrap();
+        assert_eq!(stats.median(), u64::MAX / 2);
+
+        assert_eq!(
+            format!(
+                "{:.0}",
+                SizeStatsBinary::new(vec![512, 1 << 20, 3 << 20, 1 << 30]).unwrap()
+            ),
+            "min 0Gi, median 0Gi, mean 0Gi, max 1Gi".to_string()
+        );
+        assert_eq!(
+            format!(
+                "{:>7.2}",
+                SizeStatsSI::new(&[1_000, 2_500]).unwrap().with_unit("B")
+            ),
+            "min   1.00kB, median   1.75kB, mean   1.75kB, max   2.50kB".to_string()
+        );
+    }
+
+    #[test]
+    fn prefix_hysteresis() {
+        let mut hysteresis = PrefixHysteresisBinary::new(10);
+        let mut format = |value| format!("{}", hysteresis.format(value));
+
+        assert_eq!(format(1_000), "1000".to_string());
+        assert_eq!(format(1_100), "1100".to_string());
+        assert_eq!(format(1_127), "1.1Ki".to_string());
+        assert_eq!(format(1_000), "0.9Ki".to_string());
+        assert_eq!(format(922), "0.9Ki".to_string());
+        assert_eq!(format(921), "921".to_string());
+        assert_eq!(format(3 << 30), "3.0Gi".to_string());
+        assert_eq!(format(5), "5".to_string());
+        assert_eq!(format(u64::MAX), "15.9Ei".to_string());
+
+        let mut hysteresis = PrefixHysteresisSI::new(0);
+        assert_eq!(format!("{}", hysteresis.format(1_000)), "1.0k".to_string());
+        assert_eq!(format!("{}", hysteresis.format(999)), "999".to_string());
+
+        let mut hysteresis = PrefixHysteresisSI::new(200);
+        assert_eq!(format!("{}", hysteresis.format(1_000)), "1.0k".to_string());
+        assert_eq!(format!("{}", hysteresis.format(1)), "0.0k".to_string());
+        hysteresis.reset();
+        assert_eq!(format!("{}", hysteresis.format(1)), "1".to_string());
+    }
+
+    #[test]
+    fn width_tracker() {
+        let mut tracker = WidthTracker::new();
+
+        assert_eq!(
+            format!(
+                "{:<}|",
+                tracker.pad(format_args!("{}B/s", SizeFormatterSI::new(999_900)))
+            ),
+            "999.9kB/s|".to_string()
+        );
+        assert_eq!(
+            format!(
+                "{:<}|",
+                tracker.pad(format_args!("{}B/s", SizeFormatterSI::new(1_000_000)))
+            ),
+            "1.0MB/s  |".to_string()
+        );
+        assert_eq!(
+            format!("{:*^}|", tracker.pad(FullWidthDigits::map('1'))),
+            "***１****|".to_string()
+        );
+        assert_eq!(tracker.width(), 9);
+
+        tracker.reset();
+        assert_eq!(format!("{}", tracker.pad(42)), "42".to_string());
+    }
+
+    #[test]
+    fn human_size() {
+        assert_eq!(b"".human_size(), ByteSize(0));
+        assert_eq!(
+            [0u8; 3_000][..].human_size().si().to_string(),
+            "3.0k".to_string()
+        );
+        assert_eq!("日本".human_size(), ByteSize(6));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn human_size_alloc() {
+        use alloc::{string::String, vec::Vec};
+
+        assert_eq!(Vec::from(&[1u8, 2, 3][..]).human_size(), ByteSize(3));
+        assert_eq!(String::from("size").human_size(), ByteSize(4));
+    }
+
+    #[test]
+    fn blur() {
+        for &(bytes, digits, expected) in &[
+            (0, 3, 0),
+            (7, 0, 0),
+            (7, 1, 7),
+            (99, 1, 90),
+            (1_000, 1, 1_000),
+            (123_456, 3, 123_000),
+            (123_456, 6, 123_456),
+            (123_456, 20, 123_456),
+            (u64::MAX, 1, 10_000_000_000_000_000_000),
+            (u64::MAX, 19, 18_446_744_073_709_551_610),
+        ] {
+            assert_eq!(ByteSize(bytes).round_to_sigfigs(digits), ByteSize(expected));
+        }
+
+        assert_eq!(ByteSize(0).blur(Granularity::PowerOfTwo), ByteSize(0));
+        assert_eq!(ByteSize(1).blur(Granularity::PowerOfTwo), ByteSize(1));
+        assert_eq!(
+            ByteSize(u64::MAX).blur(Granularity::PowerOfTwo),
+            ByteSize(1 << 63)
+        );
+        assert_eq!(
+            ByteSize(1 << 40).blur(Granularity::PowerOfTwo),
+            ByteSize(1 << 40)
+        );
+        assert_eq!(ByteSize(999).blur(Granularity::Multiple(0)), ByteSize(999));
+        assert_eq!(
+            ByteSize(999).blur(Granularity::Multiple(1_000)),
+            ByteSize(0)
+        );
+        assert_eq!(
+            ByteSize(u64::MAX).blur(Granularity::Multiple(1 << 30)),
+            ByteSize(u64::MAX - ((1 << 30) - 1))
+        );
+    }
+
+    #[test]
+    fn log_uniform() {
+        let mut state = 0x9e37_79b9_7f4a_7c15u64;
+        let mut random = || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+
+        let mut below_mib = 0;
+        for _ in 0..1_000 {
+            let size = ByteSize::log_uniform(ByteSize(1 << 40), &mut random);
+            assert!(size <= ByteSize(1 << 40));
+            if size < ByteSize(1 << 20) {
+                below_mib += 1;
+            }
+        }
+        // About half of the magnitudes are below a mebibyte.
+        assert!(below_mib > 350 && below_mib < 650);
+
+        assert_eq!(ByteSize::log_uniform(ByteSize(0), &mut random), ByteSize(0));
+        assert!(ByteSize::log_uniform(ByteSize(u64::MAX), &mut random) <= ByteSize(u64::MAX));
+        for _ in 0..100 {
+            assert!(ByteSize::log_uniform(ByteSize(5), &mut random) <= ByteSize(5));
+        }
+
+        // Every bit length is equally likely, including zero for the size zero.
+        let mut lengths = [0u32; 65];
+        for _ in 0..65 * 2_000 {
+            let size = ByteSize::log_uniform(ByteSize(u64::MAX), &mut random);
+            lengths[(64 - size.0.leading_zeros()) as usize] += 1;
+        }
+        for (length, &count) in lengths.iter().enumerate() {
+            assert!(count > 1_800 && count < 2_200, "{}: {}", length, count);
+        }
+
+        // The same holds when lengths have to be rejected.
+        let mut lengths = [0u32; 4];
+        for _ in 0..4 * 2_000 {
+            let size = ByteSize::log_uniform(ByteSize(7), &mut random);
+            lengths[(64 - size.0.leading_zeros()) as usize] += 1;
+        }
+        for (length, &count) in lengths.iter().enumerate() {
+            assert!(count > 1_800 && count < 2_200, "{}: {}", length, count);
+        }
+    }
+
+    #[test]
+    fn byte_size_layout() {
+        use core::mem::{align_of, size_of};
+
+        assert_eq!(size_of::<ByteSize>(), size_of::<u64>());
+        assert_eq!(align_of::<ByteSize>(), align_of::<u64>());
+        assert_eq!(
+            ByteSize::from_ne_bytes(ByteSize(u64::MAX - 1).to_ne_bytes()),
+            ByteSize(u64::MAX - 1)
+        );
+    }
+
+    #[test]
+    fn renormalize() {
+        for &(input, precision, expected) in &[
+            ("1.5GB", 1, "1.4GiB"),
+            ("1.5GB", 3, "1.397GiB"),
+            ("1.5GB", 0, "1GiB"),
+            ("1000kB", 1, "976.6KiB"),
+            ("1023.99kB", 0, "1000KiB"),
+            ("1048575B", 1, "1.0MiB"),
+            ("512", 2, "512.00B"),
+            ("0B", 1, "0.0B"),
+            ("18446744073709551615", 1, "16.0EiB"),
+            ("1B", 21, "1.000000000000000000000B"),
+        ] {
+            assert_eq!(
+                format!(
+                    "{}",
+                    super::renormalize::<SIPrefixes, BinaryPrefixes>(input, precision).unwrap()
+                ),
+                expected,
+                "{}",
+                input
+            );
+        }
+
+        assert_eq!(
+            format!(
+                "{:>8}",
+                super::renormalize::<BinaryPrefixes, SIPrefixes>("1Ki", 2).unwrap()
+            ),
+            "  1.02kB"
+        );
+        assert!(super::renormalize::<SIPrefixes, BinaryPrefixes>("1.5GiB", 1).is_err());
+    }
+
+    #[test]
+    fn exact_size() {
+        for &(bytes, expected) in &[
+            (0, "0B"),
+            (1_023, "1023B"),
+            (1_024, "1KiB"),
+            (1_536, "1.5KiB"),
+            (1_025, "1.0009765625KiB"),
+            (3 << 30, "3GiB"),
+            (
+                u64::MAX,
+                "15.999999999999999999132638262011596452794037759304046630859375EiB",
+            ),
+        ] {
+            assert_eq!(ByteSize(bytes).exact().to_string(), expected);
+        }
+
+        let mut state = 0x2545_f491_4f6c_dd1du64;
+        for _ in 0..1_000 {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+
+            for &bytes in &[state, state >> 20, state >> 40, state >> 55] {
+                let size = ByteSize(bytes);
+                assert_eq!(size.exact().to_string().parse(), Ok(size.exact()));
+                assert_eq!(size.exact().to_string().parse(), Ok(size));
+            }
+        }
+
+        assert_eq!(format!("{:>8}", ByteSize(1_536).exact()), "  1.5KiB");
+    }
+
+    #[test]
+    fn runtime_options() {
+        let options = FormatOptions::new()
+            .with_prefix_system(PrefixSystem::SI)
+            .with_precision(3);
+        assert_eq!(format_with(1_234_567, &options).to_string(), "1.234MB");
+        assert_eq!(format!("{:.1}", format_with(1_234_567, &options)), "1.2MB");
+        assert_eq!(format!("{:>9}", format_with(999, &options)), "     999B");
+
+        let options = FormatOptions::new()
+            .with_locale(Locale::from_tag("fr").unwrap())
+            .with_spacing(Spacing::Space);
+        assert_eq!(format_with(1_536, &options).to_string(), "1,5 Kio");
+
+        let options = FormatOptions::new().with_separator(",");
+        assert_eq!(options.locale().unwrap().unit_spacing(), "");
+        assert_eq!(format_with(1 << 30, &options).to_string(), "1,0GiB");
+        assert_eq!(options.prefix_system(), PrefixSystem::Binary);
+        assert_eq!(options.precision(), None);
+
+        // Formatters keep their prefix type, but use the other options.
+        let options = FormatOptions::new().with_spacing(Spacing::Space);
+        assert_eq!(
+            format!("{}", SizeFormatterBinary::new(1_536).with_options(&options)),
+            "1.5 Ki"
+        );
+    }
+
+    #[test]
+    fn formatter_precision() {
+        let formatter = SizeFormatterBinary::new(1_536).with_precision(2);
+        assert_eq!(format!("{}", formatter), "1.50Ki");
+        assert_eq!(format!("{:.0}", formatter), "1Ki");
+        assert_eq!(format!("{:>8}", formatter), "  1.50Ki");
+        // The precision is still capped to the available digits.
+        assert_eq!(
+            format!("{}", SizeFormatterSI::new(999).with_precision(2)),
+            "999"
+        );
+
+        let options = FormatOptions::new().with_precision(3);
+        let formatter = || SizeFormatterSI::new(1_234_567).with_precision(1);
+        assert_eq!(format!("{}", formatter().with_options(&options)), "1.234M");
+        assert_eq!(
+            format!("{}", formatter().with_options(&FormatOptions::new())),
+            "1.2M"
+        );
+    }
+
+    #[test]
+    fn prefix_spacing() {
+        assert_eq!(
+            format!(
+                "{}",
+                SizeFormatter::<u64, BinaryPrefixes, Spaced<PointSeparated>>::new(1_536)
+            ),
+            "1.5 Ki"
+        );
+        assert_eq!(
+            format!(
+                "{}",
+                SizeFormatter::<u64, SIPrefixes, Spaced<LocaleDE>>::new(1_234_500_000)
+            ),
+            "1,2 G"
+        );
+        // The spacing is also written without a prefix, so the unit can follow directly.
+        assert_eq!(
+            format!(
+                "{}B",
+                SizeFormatterSI::new(999).with_spacing(Spacing::Space)
+            ),
+            "999 B"
+        );
+        assert_eq!(
+            format!(
+                "{}",
+                SizeFormatter::<u64, SIPrefixes, LocaleDE>::new(42_500_000)
+                    .with_spacing(Spacing::None)
+            ),
+            "42,5M"
+        );
+        assert_eq!(
+            format!(
+                "{:>8}",
+                SizeFormatterSI::new(1_500).with_spacing(Spacing::Space)
+            ),
+            "   1.5 k"
+        );
+    }
+
+    #[test]
+    fn no_break_spacing() {
+        assert_eq!(
+            format!(
+                "{}B",
+                SizeFormatterSI::new(42_000_000).with_spacing(Spacing::NarrowNoBreakSpace)
+            ),
+            "42.0\u{202F}MB"
+        );
+        assert_eq!(
+            format!(
+                "{}B",
+                SizeFormatterBinary::new(512).with_spacing(Spacing::NoBreakSpace)
+            ),
+            "512\u{A0}B"
+        );
+        // Padding counts the spaces as single characters.
+        assert_eq!(
+            format!(
+                "{:>7}",
+                SizeFormatterSI::new(1_500).with_spacing(Spacing::NarrowNoBreakSpace)
+            ),
+            "  1.5\u{202F}k"
+        );
+
+        let options = FormatOptions::new().with_spacing(Spacing::NarrowNoBreakSpace);
+        assert_eq!(format_with(2_048, &options).to_string(), "2.0\u{202F}KiB");
+    }
+
+    #[test]
+    fn trailing_zeros() {
+        use std::fmt::Write;
+
+        let trimmed = |num| SizeFormatterSI::new(num).trim_trailing_zeros();
+        assert_eq!(format!("{:.3}", trimmed(1_500_000_000)), "1.5G");
+        assert_eq!(format!("{:.3}", trimmed(1_230_000)), "1.23M");
+        assert_eq!(format!("{:.3}", trimmed(1_000_999)), "1M");
+        assert_eq!(format!("{:.3}", trimmed(999)), "999");
+        assert_eq!(format!("{:>6.2}", trimmed(2_050)), " 2.05k");
+        assert_eq!(format!("{:.2}", trimmed(2_000)), "2k");
+
+        let locale = Locale::from_tag("de").unwrap();
+        let trimmed = SizeFormatterBinary::new(1 << 20)
+            .trim_trailing_zeros()
+            .with_options(&FormatOptions::new().with_locale(locale));
+        assert_eq!(format!("{}", trimmed), "1 Mi");
+
+        // Exactness is still checked against the requested precision.
+        let mut output = std::string::String::new();
+        assert!(write!(output, "{:.1}", trimmed_exact(1_250)).is_err());
+        assert!(write!(output, "{:.2}", trimmed_exact(1_250)).is_ok());
+        assert_eq!(output, "1.25k");
+    }
+
+    fn trimmed_exact(num: u64) -> SizeFormatterSI {
+        SizeFormatterSI::new(num)
+            .trim_trailing_zeros()
+            .require_exact()
+    }
+
+    #[test]
+    fn plus_sign() {
+        assert_eq!(format!("{:+}", SizeFormatterBinary::new(1_536)), "+1.5Ki");
+        assert_eq!(format!("{:+.0}", SizeFormatterSI::new(999)), "+999");
+        assert_eq!(format!("{:+>7}", SizeFormatterSI::new(1_500)), "+++1.5k");
+        assert_eq!(format!("{:>+7}", SizeFormatterSI::new(1_500)), "  +1.5k");
+        assert_eq!(format!("{:+}", SizeFormatterSI::new(0)), "0");
+        assert_eq!(format!("{}", SizeFormatterSI::new(0).with_plus_sign()), "0");
+        assert_eq!(
+            format!(
+                "{:.0}",
+                SizeFormatterSI::new(1_500_000_000_000)
+                    .clamp(1_000_000_000_000, ClampStyle::GreaterThan)
+                    .with_plus_sign()
+            ),
+            "+>1T"
+        );
+    }
+
+    #[test]
+    fn prefix_limits() {
+        let limited = |num| {
+            SizeFormatterBinary::new(num)
+                .with_min_prefix(2)
+                .with_max_prefix(4)
+        };
+        assert_eq!(format!("{:.2}", limited(512 * 1024)), "0.50Mi");
+        assert_eq!(format!("{}", limited(0)), "0.0Mi");
+        assert_eq!(format!("{}", limited(3 << 30)), "3.0Gi");
+        assert_eq!(format!("{}", limited(5 << 50)), "5120.0Ti");
+
+        // Limits beyond the available prefixes use the largest prefix.
+        assert_eq!(
+            format!("{}", SizeFormatterSI128::new(1_500).with_min_prefix(20)),
+            "0.0Y"
+        );
+        // The limits also apply to the displayed number of a clamped formatter.
+        assert_eq!(
+            format!(
+                "{:.0}",
+                SizeFormatterSI::new(5_000_000)
+                    .clamp(999_999, ClampStyle::Plus)
+                    .with_max_prefix(1)
+            ),
+            "999+k"
+        );
+    }
+
+    #[test]
+    fn forced_prefix() {
+        let forced = |num| SizeFormatterBinary::new(num).with_forced_prefix(2);
+        assert_eq!(format!("{:.2}", forced(400 * 1024)), "0.39Mi");
+        assert_eq!(format!("{}", forced(5 << 30)), "5120.0Mi");
+        assert_eq!(format!("{:>9}", forced(0)), "    0.0Mi");
+
+        // A forced prefix takes precedence over the prefix limits.
+        assert_eq!(
+            format!(
+                "{}",
+                SizeFormatterSI::new(1_500)
+                    .with_max_prefix(0)
+                    .with_forced_prefix(1)
+            ),
+            "1.5k"
+        );
+        assert_eq!(
+            format!("{}", SizeFormatterSI128::new(1).with_forced_prefix(100)),
+            "0.0Y"
+        );
+    }
+
+    #[test]
+    fn prefix_threshold() {
+        let early = |num| SizeFormatterSI::new(num).with_prefix_threshold(Ratio::new(9, 10));
+        assert_eq!(format!("{:.3}", early(899_999)), "899.999k");
+        assert_eq!(format!("{:.3}", early(900_000)), "0.900M");
+        assert_eq!(format!("{:.0}", early(899)), "899");
+        assert_eq!(format!("{:.1}", early(950)), "0.9k");
+        assert_eq!(format!("{}", early(0)), "0");
+
+        let late = |num| SizeFormatterBinary::new(num).with_prefix_threshold(Ratio::new(3, 2));
+        assert_eq!(format!("{:.0}", late(1_535)), "1535");
+        assert_eq!(format!("{:.1}", late(1_536)), "1.5Ki");
+        assert_eq!(format!("{:.1}", late(1_100 * 1_024)), "1100.0Ki");
+        assert_eq!(format!("{:.1}", late(3 << 20)), "3.0Mi");
+
+        // The threshold is applied before the prefix limits.
+        assert_eq!(
+            format!(
+                "{}",
+                SizeFormatterSI::new(999_000)
+                    .with_prefix_threshold(Ratio::new(9, 10))
+                    .with_max_prefix(1)
+            ),
+            "999.0k"
+        );
+        // Thresholds that do not fit into the base type are ignored.
+        assert_eq!(
+            format!(
+                "{}",
+                SizeFormatter::<u16, SIPrefixes, PointSeparated>::new(999)
+                    .with_prefix_threshold(Ratio::new(99_999, 100_000))
+            ),
+            "999"
+        );
+    }
+
+    #[test]
+    fn prefix_default_precision() {
+        struct PreciseBinary;
+
+        impl PrefixType for PreciseBinary {
+            const PREFIX_SIZE: u32 = BinaryPrefixes::PREFIX_SIZE;
+            const DEFAULT_PRECISION: usize = 2;
+
+            const PREFIXES: &'static [&'static str] = BinaryPrefixes::PREFIXES;
+        }
+
+        type Precise = SizeFormatter<u64, PreciseBinary, PointSeparated>;
+        assert_eq!(format!("{}", Precise::new(1_536)), "1.50Ki");
+        assert_eq!(format!("{:.1}", Precise::new(1_536)), "1.5Ki");
+        assert_eq!(
+            format!("{}", Precise::new(1_536).with_precision(3)),
+            "1.500Ki"
+        );
+        assert_eq!(format!("{}", Precise::new(42)), "42");
+    }
+
+    #[test]
+    fn prefix_precisions() {
+        let table = |num| SizeFormatterBinary::new(num).with_prefix_precisions(&[0, 1, 1, 2]);
+        assert_eq!(format!("{}", table(1_023)), "1023");
+        assert_eq!(format!("{}", table(1_536)), "1.5Ki");
+        assert_eq!(format!("{}", table(3 << 29)), "1.50Gi");
+        assert_eq!(format!("{}", table(3 << 49)), "1.50Pi");
+        assert_eq!(format!("{:.0}", table(3 << 29)), "1Gi");
+        assert_eq!(format!("{}", table(3 << 29).with_precision(3)), "1.500Gi");
+
+        // An empty table keeps the default precision.
+        assert_eq!(
+            format!(
+                "{}",
+                SizeFormatterSI::new(1_500).with_prefix_precisions(&[])
+            ),
+            "1.5k"
+        );
+    }
+
+    #[test]
+    fn runtime_separator() {
+        assert_eq!(
+            format!("{:.2}", SizeFormatterBinary::new(1_536).with_separator(",")),
+            "1,50Ki"
+        );
+        // The other parts of the locale are kept.
+        assert_eq!(
+            format!(
+                "{}",
+                SizeFormatter::<u64, SIPrefixes, LocaleDE>::new(1_234_500_000_000_000)
+                    .with_max_prefix(3)
+                    .with_separator(".")
+            ),
+            "1.234.500.0 G"
+        );
+        assert_eq!(
+            format!("{}", SizeFormatterSI::new(999).with_separator(",")),
+            "999"
+        );
+    }
+
+    #[test]
+    fn multi_character_separator() {
+        struct Marked;
+
+        impl DecimalSeparator for Marked {
+            const SEPARATOR: &'static str = "\u{200F}\u{066B}";
+        }
+
+        type ArabicMark = SizeFormatter<u64, SIPrefixes, Marked>;
+        assert_eq!(format!("{}", ArabicMark::new(1_500)), "1\u{200F}\u{066B}5k");
+        assert_eq!(
+            ArabicMark::parse_with_unit("1\u{200F}\u{066B}25kB", "B").map(|size| size.num()),
+            Ok(1_250)
+        );
+        assert_eq!(
+            ArabicMark::parse_with_unit("1\u{200F}25kB", "B")
+                .err()
+                .map(|error| error.kind()),
+            Some(ParseErrorKind::UnknownPrefix)
+        );
+        assert_eq!(
+            format!("{:.2}", SizeFormatterSI::new(1_500).with_separator(" · ")),
+            "1 · 50k"
+        );
+        assert_eq!(
+            format!("{:>7}", SizeFormatterSI::new(1_500).with_separator("")),
+            "    15k"
+        );
+    }
+
+    #[test]
+    fn half_even_rounding() {
+        use math::{scale, Rounding};
+
+        let even = |num| SizeFormatterSI::new(num).with_rounding(Rounding::HalfEven);
+        assert_eq!(format!("{}", even(1_050)), "1.0k");
+        assert_eq!(format!("{}", even(1_150)), "1.2k");
+        assert_eq!(format!("{}", even(1_149)), "1.1k");
+        assert_eq!(format!("{}", even(1_051)), "1.1k");
+        assert_eq!(format!("{:.0}", even(2_500)), "2k");
+        assert_eq!(format!("{:.0}", even(3_500)), "4k");
+        assert_eq!(format!("{}", even(9_950)), "10.0k");
+        assert_eq!(format!("{}", even(999_950)), "1.0M");
+        assert_eq!(format!("{:.2}", even(1_995).trim_trailing_zeros()), "2k");
+        assert_eq!(
+            format!("{:.3}", even(1_995).trim_trailing_zeros()),
+            "1.995k"
+        );
+
+        // Binary prefixes produce ties only for halves of the precision.
+        let even = |num| SizeFormatterBinary::new(num).with_rounding(Rounding::HalfEven);
+        assert_eq!(format!("{:.2}", even(1_024 + 1_024 / 8)), "1.12Ki");
+        assert_eq!(format!("{:.2}", even(1_024 + 3 * 1_024 / 8)), "1.38Ki");
+
+        let up = |num| SizeFormatterSI::new(num).with_rounding(Rounding::HalfUp);
+        assert_eq!(format!("{}", up(1_050)), "1.1k");
+        assert_eq!(format!("{}", up(1_049)), "1.0k");
+        let ceiling = |num| SizeFormatterSI::new(num).with_rounding(Rounding::Up);
+        assert_eq!(format!("{}", ceiling(1_001)), "1.1k");
+        assert_eq!(format!("{}", ceiling(1_000)), "1.0k");
+
+        // Rounding up to the size of the next prefix moves to that prefix.
+        assert_eq!(format!("{}", up(999_950)), "1.0M");
+        assert_eq!(format!("{}", up(999_949)), "999.9k");
+        assert_eq!(format!("{}", ceiling(999_999)), "1.0M");
+        assert_eq!(format!("{:.0}", ceiling(999_001)), "1M");
+        assert_eq!(format!("{}", up(999_950).with_forced_prefix(1)), "1000.0k");
+        assert_eq!(format!("{}", up(999_950).with_max_prefix(1)), "1000.0k");
+        assert_eq!(
+            format!("{}", up(1_200_000).with_prefix_threshold(Ratio::new(3, 2))),
+            "1200.0k"
+        );
+        assert_eq!(
+            format!(
+                "{}",
+                SizeFormatterBinary::new(1_048_575).with_rounding(Rounding::HalfUp)
+            ),
+            "1.0Mi"
+        );
+
+        // The next prefix is not used if its size does not fit into the base type.
+        struct Hexadecimal;
+        impl PrefixType for Hexadecimal {
+            const PREFIX_SIZE: u32 = 16;
+            const PREFIXES: &'static [&'static str] = &["", "x", "y"];
+        }
+        let hexadecimal = SizeFormatter::<u8, Hexadecimal, PointSeparated>::new(255);
+        assert_eq!(
+            format!("{:.0}", hexadecimal.with_rounding(Rounding::Up)),
+            "16x"
+        );
+
+        assert_eq!(
+            scale::<SIPrefixes, u64>(1_500, 0, 1, Rounding::HalfEven),
+            Some(2)
+        );
+        assert_eq!(
+            scale::<SIPrefixes, u64>(2_500, 0, 1, Rounding::HalfEven),
+            Some(2)
+        );
+        assert_eq!(
+            scale::<SIPrefixes, u64>(2_501, 0, 1, Rounding::HalfEven),
+            Some(3)
+        );
+        assert_eq!(
+            scale::<SIPrefixes, u64>(2_499, 0, 1, Rounding::HalfEven),
+            Some(2)
+        );
+    }
+
+    #[test]
+    fn step_rounding() {
+        let stepped =
+            |num, numer, denom| SizeFormatterBinary::new(num).with_step(Ratio::new(numer, denom));
+        assert_eq!(format!("{:.2}", stepped(1_024 + 100, 1, 4)), "1.00Ki");
+        assert_eq!(format!("{:.2}", stepped(1_024 + 128, 1, 4)), "1.25Ki");
+        assert_eq!(format!("{:.2}", stepped(1_024 + 800, 1, 4)), "1.75Ki");
+        assert_eq!(format!("{:.2}", stepped(1_024 + 1_000, 1, 4)), "2.00Ki");
+        assert_eq!(format!("{:.1}", stepped(3 << 29, 1, 2)), "1.5Gi");
+        assert_eq!(format!("{:.1}", stepped(100 << 20, 1, 2)), "100.0Mi");
+        assert_eq!(format!("{:.0}", stepped(1_000, 1, 2)), "1000");
+        // A denominator that does not divide the prefix size is ignored.
+        assert_eq!(format!("{:.2}", stepped(1_024 + 300, 1, 3)), "1.29Ki");
+
+        let stepped = |num| {
+            SizeFormatterSI::new(num)
+                .with_step(Ratio::new(1, 2))
+                .with_rounding(Rounding::HalfEven)
+        };
+        assert_eq!(format!("{}", stepped(1_250)), "1.0k");
+        assert_eq!(format!("{}", stepped(1_750)), "2.0k");
+        assert_eq!(format!("{}", stepped(1_251)), "1.5k");
+        // Stepping up to the size of the next prefix moves to that prefix.
+        let quarters = |num| SizeFormatterSI::new(num).with_step(Ratio::new(1, 4));
+        assert_eq!(format!("{}", quarters(999_900)), "1.0M");
+        assert_eq!(format!("{}", quarters(999_870)), "999.7k");
+        assert_eq!(
+            format!("{}", quarters(999_900).with_max_prefix(1)),
+            "1000.0k"
+        );
+        assert_eq!(
+            format!(
+                "{:.1}",
+                SizeFormatterBinary::new(1_048_500).with_step(Ratio::new(1, 2))
+            ),
+            "1.0Mi"
+        );
+        let floor = SizeFormatterSI::new(1_999)
+            .with_step(Ratio::new(1, 5))
+            .with_rounding(Rounding::Down);
+        assert_eq!(format!("{:.1}", floor), "1.8k");
+        assert_eq!(
+            format!(
+                "{:.1}",
+                SizeFormatterSI::new(1_800)
+                    .with_step(Ratio::new(1, 5))
+                    .require_exact()
+            ),
+            "1.8k"
+        );
+        let inexact = SizeFormatterSI::new(1_999)
+            .with_step(Ratio::new(1, 5))
+            .require_exact();
+        assert!(inexact
+            .write_unpadded(&mut std::string::String::new(), Some(1))
+            .is_err());
+    }
+
+    #[test]
+    fn approximation_marker() {
+        let marked = |num| SizeFormatterBinary::new(num).with_approximation_marker("≈");
+        assert_eq!(format!("{}", marked(1_536)), "1.5Ki");
+        assert_eq!(format!("{}", marked(1_537)), "≈1.5Ki");
+        assert_eq!(format!("{:.3}", marked(1_537)), "≈1.500Ki");
+        assert_eq!(format!("{:.0}", marked(1_536)), "≈1Ki");
+        assert_eq!(format!("{}", marked(1_023)), "1023");
+        assert_eq!(format!("{:>8}", marked(1_537)), "  ≈1.5Ki");
+        assert_eq!(format!("{:+}", marked(1_537)), "+≈1.5Ki");
+        assert_eq!(
+            format!("{}", marked(1_537).with_rounding(Rounding::HalfUp)),
+            "≈1.5Ki"
+        );
+        assert_eq!(
+            format!("{:.2}", marked(1_600).with_step(Ratio::new(1, 2))),
+            "≈1.50Ki"
+        );
+    }
+
+    #[test]
+    fn annotated_size() {
+        assert_eq!(format!("{}", ByteSize(0).annotated()), "0B (0 bytes)");
+        assert_eq!(
+            format!("{}", ByteSize(u64::MAX).annotated()),
+            "15.9EiB (18,446,744,073,709,551,615 bytes)"
+        );
+        assert_eq!(
+            format!(
+                "{:>30}|",
+                ByteSize(1_000_000)
+                    .annotated()
+                    .with_prefix_system(PrefixSystem::SI)
+                    .with_group_separator("")
+            ),
+            "         1.0MB (1000000 bytes)|"
+        );
+        assert_eq!(
+            format!(
+                "{:.0}",
+                ByteSize(2_048).annotated().with_unit_name("octets")
+            ),
+            "2KiB (2,048 octets)"
+        );
+    }
+
+    #[test]
+    fn extended_prefixes() {
+        assert_eq!(
+            format!(
+                "{}",
+                SizeFormatterSIExtended128::new(10u128.pow(27) * 3 / 2)
+            ),
+            "1.5R"
+        );
+        assert_eq!(
+            format!("{}", SizeFormatterSIExtended128::new(u128::MAX)),
+            "340282366.9Q"
+        );
+        assert_eq!(
+            format!("{}", SizeFormatterSIExtended128::new(999 * 10u128.pow(24))),
+            "999.0Y"
+        );
+        assert_eq!(
+            format!("{}", SizeFormatterBinaryExtended128::new(3 << 89)),
+            "1.5Ri"
+        );
+        assert_eq!(
+            format!("{}", SizeFormatterBinaryExtended128::new(u128::MAX)),
+            "268435455.9Qi"
+        );
+        assert_eq!(
+            SizeFormatterSIExtended128::parse_with_unit("2.5QB", "B").map(|size| size.num()),
+            Ok(25 * 10u128.pow(29))
+        );
+    }
+
+    #[test]
+    fn jedec_prefixes() {
+        assert_eq!(format!("{}", SizeFormatterJEDEC::new(1_023)), "1023");
+        assert_eq!(format!("{}", SizeFormatterJEDEC::new(1_024)), "1.0K");
+        assert_eq!(
+            format!("{:.2}", SizeFormatterJEDEC::new(1_572_864)),
+            "1.50M"
+        );
+        assert_eq!(format!("{}", SizeFormatterJEDEC::new(u64::MAX)), "15.9E");
+        assert_eq!(
+            SizeFormatterJEDEC::parse_with_unit("1.5GB", "B").map(|size| size.num()),
+            Ok(3 << 29)
+        );
+    }
+
+    #[test]
+    fn base_index() {
+        struct Seconds;
+
+        impl PrefixType for Seconds {
+            const PREFIX_SIZE: u32 = 1000;
+            const BASE_INDEX: usize = 3;
+
+            const PREFIXES: &'static [&'static str] = &["n", "µ", "m", ""];
+        }
+
+        type Duration = SizeFormatter<u64, Seconds, PointSeparated>;
+        let fraction = |numer, denom| Duration::from_base_fraction(Ratio::new(numer, denom));
+
+        assert_eq!(format!("{}s", fraction(1, 4).unwrap()), "250.0ms");
+        assert_eq!(format!("{}s", fraction(3, 2).unwrap()), "1.5s");
+        assert_eq!(format!("{}s", fraction(1, 3_000_000_000).unwrap()), "0ns");
+        assert_eq!(format!("{:.2}s", fraction(1, 3).unwrap()), "333.33ms");
+        assert_eq!(
+            format!("{}s", Duration::from_base_units(42).unwrap()),
+            "42.0s"
+        );
+        assert!(Duration::from_base_units(u64::MAX / 1_000).is_none());
+        assert!(fraction(u64::MAX, 2).is_none());
+
+        // Prefix types without a base index count in the first prefix.
+        assert_eq!(
+            SizeFormatterSI::from_base_fraction(Ratio::new(7, 2)).map(|size| size.num()),
+            Some(3)
+        );
+    }
+
+    #[test]
+    fn long_names() {
+        let long = |num| SizeFormatterSI::new(num).with_long_names("bytes");
+        assert_eq!(format!("{}", long(512)), "512 bytes");
+        assert_eq!(format!("{:.2}", long(1_234_567_890)), "1.23 gigabytes");
+        assert_eq!(format!("{:>16}", long(1_500)), "   1.5 kilobytes");
+        assert_eq!(
+            format!(
+                "{}",
+                SizeFormatterJEDEC::new(2_048).with_long_names("bytes")
+            ),
+            "2.0 kilobytes"
+        );
+        assert_eq!(
+            format!(
+                "{}",
+                SizeFormatterBinaryExtended128::new(1 << 100).with_long_names("bytes")
+            ),
+            "1.0 quebibytes"
+        );
+        assert_eq!(
+            format!(
+                "{}",
+                SizeFormatter::<u64, SIPrefixes, LocaleFR>::new(3_000_000)
+                    .with_long_names("octets")
+            ),
+            "3,0\u{A0}megaoctets"
+        );
+        assert_eq!(
+            format!(
+                "{}",
+                SizeFormatter::<u64, JapaneseMyriadPrefixes, PointSeparated>::new(50_000)
+                    .with_long_names("人")
+            ),
+            "5.0 万人"
+        );
+    }
+
+    #[test]
+    fn pluralization() {
+        let named = |num| SizeFormatterBinary::new(num).with_unit_names("byte", "bytes");
+        assert_eq!(format!("{}", named(1)), "1 byte");
+        assert_eq!(format!("{}", named(0)), "0 bytes");
+        assert_eq!(format!("{}", named(2)), "2 bytes");
+        assert_eq!(format!("{:.0}", named(1_024)), "1 kibibyte");
+        assert_eq!(format!("{:.0}", named(1_536)), "1 kibibyte");
+        assert_eq!(format!("{}", named(1_024)), "1.0 kibibytes");
+        assert_eq!(
+            format!("{}", named(1_024).trim_trailing_zeros()),
+            "1 kibibyte"
+        );
+        assert_eq!(format!("{:>12}", named(1)), "      1 byte");
+
+        // The rule sees the number with the separators of the locale.
+        let named = SizeFormatter::<u64, SIPrefixes, LocaleDE>::new(1_500)
+            .with_unit_names("Byte", "Bytes")
+            .with_plural_rule(|number| number != "1,5");
+        assert_eq!(format!("{}", named), "1,5 kiloByte");
+
+        // Without distinct names, the plural rule does not matter.
+        assert_eq!(
+            format!(
+                "{:.0}",
+                SizeFormatterSI::new(1_000).with_long_names("bytes")
+            ),
+            "1 kilobytes"
+        );
+    }
+
+    #[test]
+    fn non_uniform_steps() {
+        use math::{bytes_in, scale, Rounding};
+
+        struct Time;
+
+        impl PrefixType for Time {
+            const PREFIX_SIZE: u32 = 1000;
+            const STEP_SIZES: &'static [u32] = &[1000, 60, 60, 24];
+
+            const PREFIXES: &'static [&'static str] = &["ms", "s", "min", "h", "d"];
+        }
+
+        let time = |ms| SizeFormatter::<u64, Time, PointSeparated>::new(ms);
+        assert_eq!(format!("{}", time(999)), "999ms");
+        assert_eq!(format!("{}", time(59_999)), "59.9s");
+        assert_eq!(format!("{}", time(60_000)), "1.0min");
+        assert_eq!(format!("{:.3}", time(3_599_999)), "59.999min");
+        assert_eq!(format!("{}", time(86_400_000 * 400)), "400.0d");
+        // Steps of 60 and 24 only cover a single digit each.
+        assert_eq!(format!("{:.9}", time(5_400_000)), "1.50000h");
+        assert_eq!(
+            format!(
+                "{}",
+                time(5_400_000).with_prefix_threshold(Ratio::new(2, 1))
+            ),
+            "90.0min"
+        );
+        assert_eq!(
+            format!("{}", time(54_000).with_prefix_threshold(Ratio::new(9, 10))),
+            "0.9min"
+        );
+
+        assert_eq!(bytes_in::<Time, u64>(4), Some(86_400_000));
+        assert_eq!(bytes_in::<Time, u64>(5), Some(86_400_000 * 1000));
+        assert_eq!(bytes_in::<Time, u16>(3), None);
+        assert_eq!(scale::<Time, u64>(90, 2, 3, Rounding::Down), Some(1));
+        assert_eq!(scale::<Time, u64>(90, 2, 3, Rounding::HalfUp), Some(2));
+        assert_eq!(scale::<Time, u64>(89_999, 1, 3, Rounding::HalfUp), Some(25));
+        assert_eq!(scale::<Time, u64>(1_799, 1, 3, Rounding::HalfUp), Some(0));
+        assert_eq!(scale::<Time, u64>(1_800, 1, 3, Rounding::HalfEven), Some(0));
+        assert_eq!(scale::<Time, u64>(3, 4, 1, Rounding::Down), Some(259_200));
+        assert_eq!(scale::<Time, u8>(1, 4, 3, Rounding::Down), Some(24));
+        assert_eq!(scale::<Time, u8>(1, 3, 1, Rounding::Down), None);
+        assert_eq!(scale::<Time, u8>(200, 0, 2, Rounding::HalfUp), None);
+
+        let parsed: SizeFormatter<u64, Time, PointSeparated> = "1.5h".parse().unwrap();
+        assert_eq!(format!("{}", parsed), "1.5h");
+    }
+
+    #[test]
+    fn durations() {
+        use core::time::Duration;
+
+        assert_eq!(format!("{}", DurationFormatter::new(0)), "0ns");
+        assert_eq!(format!("{}", DurationFormatter::new(999)), "999ns");
+        assert_eq!(format!("{}", DurationFormatter::new(1_000)), "1.0µs");
+        assert_eq!(
+            format!("{}", DurationFormatter::new(59_999_999_999)),
+            "59.9s"
+        );
+        assert_eq!(
+            format!("{:.2}", DurationFormatter::new(90_000_000_000)),
+            "1.50min"
+        );
+        assert_eq!(
+            format!(
+                "{}",
+                DurationFormatter::from_base_units(86_400 * 365).unwrap()
+            ),
+            "365.0d"
+        );
+        assert_eq!(
+            format!(
+                "{:>8}",
+                DurationFormatter::from_duration(Duration::from_millis(2_500))
+            ),
+            "    2.5s"
+        );
+        assert_eq!(
+            format!(
+                "{}",
+                SizeFormatter::<u128, DurationUnits, LocaleDE>::from_duration(
+                    Duration::from_millis(2_500)
+                )
+            ),
+            "2,5 s"
+        );
+        assert_eq!(
+            "1.5h".parse::<DurationFormatter>().unwrap().num,
+            5_400_000_000_000
+        );
+
+        let long = |num| DurationFormatter::new(num).with_long_names("seconds");
+        assert_eq!(format!("{}", long(1_500_000)), "1.5 milliseconds");
+        assert_eq!(format!("{}", long(350)), "350 nanoseconds");
+        assert_eq!(format!("{}", long(2_000)), "2.0 microseconds");
+        // Minutes and longer are not prefixes of seconds, so seconds are used instead.
+        assert_eq!(format!("{}", long(90_000_000_000)), "90.0 seconds");
+        assert_eq!(
+            format!(
+                "{:.0}",
+                DurationFormatter::new(1_000_000_000).with_unit_names("second", "seconds")
+            ),
+            "1 second"
+        );
+        assert_eq!(
+            format!("{}", long(90_000_000_000).with_forced_prefix(5)),
+            "90.0 seconds"
+        );
+    }
+
+    #[test]
+    fn frequencies() {
+        let parse = |s: &str| s.parse::<FrequencyFormatter>().map(|f| f.num);
+
+        assert_eq!(format!("{}", FrequencyFormatter::new(0)), "0Hz");
+        assert_eq!(format!("{}", FrequencyFormatter::new(999)), "999Hz");
+        assert_eq!(
+            format!("{:.2}", FrequencyFormatter::new(2_412_000_000)),
+            "2.41GHz"
+        );
+        assert_eq!(
+            format!("{}", FrequencyFormatter::new(1_500_000_000_000)),
+            "1.5THz"
+        );
+        assert_eq!(
+            format!(
+                "{}",
+                FrequencyFormatter::new(100_000_000).with_long_names("hertz")
+            ),
+            "100.0 megahertz"
+        );
+        assert_eq!(
+            format!(
+                "{}",
+                SizeFormatter::<u64, FrequencyUnits, LocaleDE>::new(44_100)
+            ),
+            "44,1 kHz"
+        );
+
+        assert_eq!(parse("44.1kHz"), Ok(44_100));
+        assert_eq!(parse("50Hz"), Ok(50));
+        assert_eq!(parse("3.6GHz"), Ok(3_600_000_000));
+        assert!(parse("3.6GB").is_err());
+        assert!(parse("3.6").is_err());
+    }
+
+    #[test]
+    fn short_scale_counts() {
+        assert_eq!(format!("{}", CountFormatter::new(999)), "999");
+        assert_eq!(format!("{}", CountFormatter::new(1_000)), "1.0k");
+        assert_eq!(
+            format!("{}", CountFormatter::new(1_234).trim_trailing_zeros()),
+            "1.2k"
+        );
+        assert_eq!(
+            format!("{}", CountFormatter::new(2_000).trim_trailing_zeros()),
+            "2k"
+        );
+        assert_eq!(
+            format!("{}", CountFormatter::new(7_100_000_000_000)),
+            "7.1T"
+        );
+        assert_eq!(format!("{}", CountFormatter::new(u64::MAX)), "18446744.0T");
+        assert_eq!("3.4M".parse::<CountFormatter>().unwrap().num, 3_400_000);
+    }
+
+    #[test]
+    fn bits() {
+        assert_eq!(format!("{}", SizeFormatterSIBits::new(0)), "0bit");
+        assert_eq!(format!("{}", SizeFormatterSIBits::new(999)), "999bit");
+        assert_eq!(
+            format!("{:.2}", SizeFormatterBinaryBits::new(1_572_864)),
+            "1.50Mibit"
+        );
+        assert_eq!(
+            format!(
+                "{}",
+                SizeFormatterSIBits::new(2_500).with_long_names("bits")
+            ),
+            "2.5 kilobits"
+        );
+        assert_eq!(
+            SizeFormatterSIBits::from_bytes_as_bits(u64::MAX / 8).map(|f| f.num),
+            Some(u64::MAX / 8 * 8)
+        );
+        assert!(SizeFormatterSIBits::from_bytes_as_bits(u64::MAX / 8 + 1).is_none());
+        assert_eq!(
+            "10Mbit".parse::<SizeFormatterSIBits>().map(|f| f.num),
+            Ok(10_000_000)
+        );
+    }
+
+    #[test]
+    fn baked_in_unit() {
+        let sized = |num| SizeFormatterBinary::new(num).with_unit("B");
+        assert_eq!(format!("{}", sized(0)), "0B");
+        assert_eq!(format!("{}", sized(1_536)), "1.5KiB");
+        assert_eq!(format!("{:>8}", sized(1_536)), "  1.5KiB");
+        assert_eq!(format!("{:<8}|", sized(1_536)), "1.5KiB  |");
+        assert_eq!(
+            format!("{}", sized(1_536).with_long_names("bytes")),
+            "1.5 kibibytes"
+        );
+        assert_eq!(
+            format!(
+                "{}",
+                SizeFormatter::<u64, SIPrefixes, LocaleFR>::new(42_000).with_unit("o")
+            ),
+            "42,0\u{A0}ko"
+        );
+        assert_eq!(
+            format!(
+                "{}",
+                SizeFormatterSI::new(2_000)
+                    .clamp(1_000, ClampStyle::Plus)
+                    .with_unit("B")
+            ),
+            "1.0+kB"
+        );
+    }
+
+    #[test]
+    fn runtime_prefixes() {
+        let custom = |base, prefixes| {
+            FormatOptions::new().with_prefix_system(PrefixSystem::Custom(
+                RuntimePrefixes::new(base, prefixes).unwrap(),
+            ))
+        };
+
+        let options = custom(1024, &["", "Ki", "Mi"]);
+        assert_eq!(format_with(0, &options).to_string(), "0B");
+        assert_eq!(format_with(1_023, &options).to_string(), "1023B");
+        assert_eq!(format_with(1_536, &options).to_string(), "1.5KiB");
+        assert_eq!(format!("{:.3}", format_with(1_536, &options)), "1.500KiB");
+        assert_eq!(format!("{:>8}", format_with(1_536, &options)), "  1.5KiB");
+        assert_eq!(
+            format_with(u64::MAX, &options).to_string(),
+            "17592186044415.9MiB"
+        );
+        assert_eq!(
+            format_with(1_536, &options.with_precision(2)).to_string(),
+            "1.50KiB"
+        );
+        assert_eq!(
+            format_with(1_536, &options.with_locale(Locale::from_tag("fr").unwrap())).to_string(),
+            "1,5\u{A0}Kio"
+        );
+
+        // Without prefixes, plain numbers are written.
+        assert_eq!(
+            format_with(123_456, &custom(1000, &[])).to_string(),
+            "123456B"
+        );
+        assert_eq!(
+            format_with(9, &custom(2, &["", "x", "xx"])).to_string(),
+            "2.2xxB"
+        );
+        assert_eq!(
+            format!("{:.2}", format_with(9, &custom(2, &["", "x", "xx"]))),
+            "2.25xxB"
+        );
+
+        // Runtime prefixes are formatted like the prefix types.
+        let runtime = |num: u128| {
+            SizeFormatterBinary128::new(num)
+                .with_runtime_prefixes(RuntimePrefixes::new(1000, &["", "k", "M"]).unwrap())
+        };
+        assert_eq!(
+            format!("{}", runtime(999_950).with_rounding(Rounding::HalfUp)),
+            "1.0M"
+        );
+        assert_eq!(
+            format!(
+                "{}",
+                runtime(999_000).with_prefix_threshold(Ratio::new(9, 10))
+            ),
+            "0.9M"
+        );
+        assert_eq!(
+            format!("{:+}", runtime(1_500).with_approximation_marker("~")),
+            "+1.5k"
+        );
+        assert_eq!(
+            format!(
+                "{}",
+                runtime(1_234)
+                    .with_approximation_marker("~")
+                    .trim_trailing_zeros()
+            ),
+            "~1.2k"
+        );
+
+        assert!(RuntimePrefixes::new(1, &["", "k"]).is_none());
+        assert_eq!(
+            RuntimePrefixes::new(10, &["", "da"]).map(|p| p.base()),
+            Some(10)
+        );
+    }
+
+    #[test]
+    fn symbol_case() {
+        let round_trip = |num, case| {
+            let formatted = format!(
+                "{}",
+                SizeFormatterBinary::new(num)
+                    .with_unit("B")
+                    .with_symbol_case(case)
+            );
+            let parsed = SizeFormatterBinary::parse_with_case(&formatted, "B", case)
+                .map(|parsed| parsed.num);
+            (formatted, parsed)
+        };
+
+        assert_eq!(
+            round_trip(1_536, SymbolCase::Standard),
+            ("1.5KiB".to_string(), Ok(1_536))
+        );
+        assert_eq!(
+            round_trip(1_536, SymbolCase::Upper),
+            ("1.5KIB".to_string(), Ok(1_536))
+        );
+        assert_eq!(
+            round_trip(1_536, SymbolCase::Lower),
+            ("1.5kib".to_string(), Ok(1_536))
+        );
+        assert_eq!(
+            round_trip(1_536, SymbolCase::Capitalized),
+            ("1.5Kib".to_string(), Ok(1_536))
+        );
+        assert_eq!(
+            round_trip(512, SymbolCase::Lower),
+            ("512b".to_string(), Ok(512))
+        );
+        assert_eq!(
+            round_trip(512, SymbolCase::Capitalized),
+            ("512B".to_string(), Ok(512))
+        );
+
+        // The prefix may be given without the unit, but in the same case.
+        let parse = |s, case| SizeFormatterSI::parse_with_case(s, "B", case).map(|f| f.num);
+        assert_eq!(parse("2K", SymbolCase::Upper), Ok(2_000));
+        assert_eq!(parse("2", SymbolCase::Upper), Ok(2));
+        assert!(parse("2kB", SymbolCase::Upper).is_err());
+        assert!(parse("2Kb", SymbolCase::Lower).is_err());
+        assert_eq!(parse("2kB", SymbolCase::Standard), Ok(2_000));
+
+        // Long names are not affected.
+        assert_eq!(
+            format!(
+                "{}",
+                SizeFormatterSI::new(1_500)
+                    .with_long_names("bytes")
+                    .with_symbol_case(SymbolCase::Upper)
+            ),
+            "1.5 kilobytes"
+        );
+
+        let parser = SizeParser::new().with_mode(ParseMode::Cased(SymbolCase::Upper));
+        assert_eq!(parser.parse("3GIB"), Ok(ByteSize(3 << 30)));
+        assert_eq!(parser.parse("3GB"), Ok(ByteSize(3_000_000_000)));
+        assert!(parser.parse("3GiB").is_err());
+    }
+
+    #[test]
+    fn explorer_preset() {
+        use presets::Explorer;
+
+        let explorer = |bytes| Explorer::<PointSeparated>::new(bytes).to_string();
+        assert_eq!(explorer(0), "0 bytes");
+        assert_eq!(explorer(999), "999 bytes");
+        assert_eq!(explorer(1_024), "1.00 KB");
+        assert_eq!(explorer(10_239), "9.99 KB");
+        assert_eq!(explorer(10_240), "10.0 KB");
+        assert_eq!(explorer(102_400), "100 KB");
+        assert_eq!(explorer(1_023_999), "999 KB");
+        assert_eq!(explorer(1_024_000), "0.97 MB");
+        assert_eq!(explorer(5 << 30), "5.00 GB");
+        assert_eq!(explorer(u64::MAX), "15.9 EB");
+        assert_eq!(Explorer::<LocaleDE>::new(1_340).to_string(), "1,30 KB");
+        assert_eq!(
+            format!("{:>10}", Explorer::<PointSeparated>::new(1_340)),
+            "   1.30 KB"
+        );
+        assert_eq!(Explorer::<LocaleFR>::new(1_340).to_string(), "1,30\u{A0}Ko");
+
+        let preset = Explorer::<PointSeparated>::new(1_340);
+        let copy = preset;
+        assert_eq!(preset, copy);
+        assert_ne!(preset, Explorer::new(1_341));
+        assert_eq!(preset.bytes(), 1_340);
+        assert_eq!(format!("{:?}", preset), "Explorer { bytes: 1340 }");
+    }
+
+    #[test]
+    fn convenience_aliases() {
+        assert_eq!(
+            format!("{}B", SizeFormatterBinaryComma::new(1_536)),
+            "1,5KiB".to_string()
+        );
+        assert_eq!(
+            format!("{}B", SizeFormatterJEDECComma::new(1_536)),
+            "1,5KB".to_string()
+        );
+        assert_eq!(
+            format!("{:.2}B", SizeFormatterSIComma::new(1_234_567)),
+            "1,23MB".to_string()
+        );
+        assert_eq!(
+            format!("{}B", SizeFormatterJEDEC128::new(1 << 70)),
+            "1.0ZB".to_string()
+        );
+    }
+
+    #[test]
+    fn from_u64_for_u128() {
+        let binary: SizeFormatterBinary128 = 1_536u64.into();
+        assert_eq!(format!("{}B", binary), "1.5KiB".to_string());
+
+        let sizes: [SizeFormatterSI128; 2] = [From::from(1_000u64), From::from(u64::MAX)];
+        assert_eq!(format!("{}B", sizes[0]), "1.0kB".to_string());
+        assert_eq!(format!("{}B", sizes[1]), "18.4EB".to_string());
+    }
+
+    #[test]
+    fn coreutils_preset() {
+        use presets::Coreutils;
+
+        for &(bytes, blocks, human, si) in &[
+            (0, "0", "0", "0"),
+            (1, "1", "1", "1"),
+            (999, "1", "999", "999"),
+            (1_000, "1", "1000", "1.0k"),
+            (1_024, "1", "1.0K", "1.1k"),
+            (1_025, "2", "1.1K", "1.1k"),
+            (9_999, "10", "9.8K", "10k"),
+            (999_001, "976", "976K", "1.0M"),
+            (1 << 30, "1048576", "1.0G", "1.1G"),
+            (u64::MAX, "18014398509481984", "16E", "19E"),
+        ] {
+            assert_eq!(Coreutils::new(bytes).to_string(), blocks, "{}", bytes);
+            assert_eq!(
+                Coreutils::new(bytes).human().to_string(),
+                human,
+                "{}",
+                bytes
+            );
+            assert_eq!(Coreutils::new(bytes).si().to_string(), si, "{}", bytes);
+        }
+
+        let megabytes = Coreutils::new(5_000_000).with_block_size(1 << 20);
+        assert_eq!(megabytes.to_string(), "5");
+        assert_eq!(megabytes.si().with_block_size(512).to_string(), "9766");
+        assert_eq!(format!("{:>5}|", Coreutils::new(4_096).human()), " 4.0K|");
+    }
+
+    #[test]
+    fn kubernetes_preset() {
+        use presets::Kubernetes;
+
+        for &(input, output) in &[
+            ("0", "0"),
+            ("512Mi", "512Mi"),
+            ("1024Mi", "1Gi"),
+            ("1536Ki", "1536Ki"),
+            ("1.5Gi", "1536Mi"),
+            ("0.5Ki", "512"),
+            ("0.1Ki", "102400m"),
+            ("100m", "100m"),
+            ("1000m", "1"),
+            ("0.5", "500m"),
+            ("1500", "1500"),
+            ("1.5k", "1500"),
+            ("2000", "2k"),
+            ("+3M", "3M"),
+            ("1E", "1E"),
+            ("1000E", "1000E"),
+            ("1e3", "1e3"),
+            ("1500e-3", "1500e-3"),
+            ("2E6", "2e6"),
+            ("1n", "1n"),
+            ("0.1n", "1n"),
+            ("1e-30", "1e-9"),
+            (".5", "500m"),
+            ("5.", "5"),
+        ] {
+            let quantity: Kubernetes = input.parse().unwrap();
+            assert_eq!(quantity.to_string(), output, "{}", input);
+            assert_eq!(output.parse(), Ok(quantity), "{}", input);
+        }
+
+        for &(input, kind, position) in &[
+            ("", ParseErrorKind::InvalidNumber, 0),
+            ("-1", ParseErrorKind::InvalidNumber, 0),
+            ("Mi", ParseErrorKind::InvalidNumber, 0),
+            ("1.2.3", ParseErrorKind::InvalidNumber, 3),
+            ("1Gb", ParseErrorKind::UnknownPrefix, 1),
+            ("1 Gi", ParseErrorKind::UnknownPrefix, 1),
+            ("1e", ParseErrorKind::UnknownPrefix, 1),
+            ("1e+", ParseErrorKind::UnknownPrefix, 1),
+            ("1e40", ParseErrorKind::Overflow, 0),
+        ] {
+            let error = input.parse::<Kubernetes>().unwrap_err();
+            assert_eq!(
+                (error.kind(), error.position()),
+                (kind, position),
+                "{}",
+                input
+            );
+        }
+
+        assert_eq!(Kubernetes::from_bytes(1_000).to_string(), "1k");
+        assert_eq!(
+            Kubernetes::from_bytes(u64::MAX).to_string(),
+            "18446744073709551615"
+        );
+        assert_eq!(Kubernetes::from_bytes(1 << 62).to_string(), "4Ei");
+        assert_eq!(Kubernetes::from_millis(1_500).millis(), 1_500);
+        assert_eq!(Kubernetes::from_millis(1_500).value(), 2);
+        assert_eq!("1Ki".parse(), Ok(Kubernetes::new(1_024)));
+        assert_eq!(format!("{:>6}|", Kubernetes::from_millis(250)), "  250m|");
+    }
+
+    #[test]
+    fn grafana_preset() {
+        use presets::Grafana;
+
+        for &(bytes, iec, si) in &[
+            (0, "0 B", "0 B"),
+            (999, "999 B", "999 B"),
+            (1_000, "1000 B", "1.00 kB"),
+            (1_024, "1.00 KiB", "1.02 kB"),
+            (10_234, "9.99 KiB", "10.2 kB"),
+            (10_235, "10.0 KiB", "10.2 kB"),
+            (102_348, "99.9 KiB", "102 kB"),
+            (102_349, "100 KiB", "102 kB"),
+            (1_048_575, "1024 KiB", "1.05 MB"),
+            (999_999_999, "954 MiB", "1000 MB"),
+            (u64::MAX, "16.0 EiB", "18.4 EB"),
+        ] {
+            assert_eq!(Grafana::iec(bytes).to_string(), iec, "{}", bytes);
+            assert_eq!(Grafana::si(bytes).to_string(), si, "{}", bytes);
+        }
+
+        assert_eq!(format!("{:>10}|", Grafana::si(1_500)), "   1.50 kB|");
+    }
+
+    #[test]
+    fn formatted_size_alignment() {
+        let formatted = FormattedSize::new(format_args!(
+            "{}",
+            WithDigits::<_, FullWidthDigits>::new(SizeFormatterSI::new(42))
+        ))
+        .unwrap();
+
+        assert_eq!(format!("{:6}|", formatted), "４２  |");
+        assert_eq!(format!("{:>6}|", formatted), "  ４２|");
+    }
+
+    #[test]
+    #[cfg(feature = "unicode-width")]
+    fn unicode_width_tables() {
+        // Outside of the ranges covered by the built-in table.
+        assert_eq!(char_width('\u{1FA90}'), 2);
+        assert_eq!(char_width('\u{1AB0}'), 0);
+        assert_eq!(display_width("1.5\u{1F680}"), 5);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn preserved_size_alignment() {
+        let parsed: PreservedSize = "2KiB".parse().unwrap();
+
+        assert_eq!(format!("[{:>8}]", PreservedSize::new(2048)), "[    2KiB]");
+        assert_eq!(format!("[{:>8}]", parsed), "[    2KiB]");
+        assert_eq!(format!("[{:8}]", PreservedSize::new(2048)), "[    2KiB]");
+        assert_eq!(format!("[{:*<8}]", parsed), "[2KiB****]");
+    }
+
+    #[test]
+    fn clamp_is_inexact() {
+        use std::fmt::Write;
+
+        let mut output = std::string::String::new();
+        let clamped = |style| SizeFormatterSI::new(5_000_000).clamp(1_000_000, style);
+
+        assert!(write!(output, "{}B", clamped(ClampStyle::Plus).require_exact()).is_err());
+        assert!(write!(
+            output,
+            "{}B",
+            clamped(ClampStyle::GreaterThan).require_exact()
+        )
+        .is_err());
+        assert_eq!(
+            format!(
+                "{}B",
+                clamped(ClampStyle::Plus).with_approximation_marker("~")
+            ),
+            "~1.0+MB"
+        );
+        assert_eq!(
+            format!(
+                "{}B",
+                clamped(ClampStyle::GreaterThan).with_approximation_marker("~")
+            ),
+            "~>1.0MB"
+        );
+
+        // Numbers below the maximum are not clamped and stay exact.
+        let unclamped = SizeFormatterSI::new(500_000)
+            .clamp(1_000_000, ClampStyle::Plus)
+            .require_exact();
+        assert!(write!(output, "{}B", unclamped).is_ok());
+        assert_eq!(output, "500.0kB");
+    }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    fn random_sizes() {
+        use rand::{
+            distributions::{Standard, Uniform},
+            rngs::mock::StepRng,
+            Rng,
+        };
+
+        let mut rng = StepRng::new(1, 0x9e37_79b9_7f4a_7c15);
+        let first: ByteSize = rng.sample(Standard);
+        assert_eq!(first, ByteSize(1));
+
+        let range = Uniform::new_inclusive(ByteSize(10), ByteSize(20));
+        for _ in 0..100 {
+            let size = rng.sample(range);
+            assert!(size >= ByteSize(10) && size <= ByteSize(20));
+            let size = rng.gen_range(ByteSize(1 << 10)..ByteSize(1 << 20));
+            assert!(size >= ByteSize(1 << 10) && size < ByteSize(1 << 20));
+        }
+
+        let distribution = LogUniform::new(ByteSize(1 << 40));
+        assert_eq!(distribution.max(), ByteSize(1 << 40));
+        for _ in 0..100 {
+            assert!(rng.sample(distribution) <= ByteSize(1 << 40));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "bytemuck")]
+    fn byte_size_bytemuck() {
+        #[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+        #[repr(C)]
+        struct Header {
+            used: ByteSize,
+            free: ByteSize,
+        }
+
+        let header = Header {
+            used: ByteSize(1_536),
+            free: ByteSize(42),
+        };
+        let words: [u64; 2] = bytemuck::cast(header);
+        assert_eq!(words, [1_536, 42]);
+        assert_eq!(bytemuck::cast::<u64, ByteSize>(7), ByteSize(7));
+        assert_eq!(<ByteSize as bytemuck::Zeroable>::zeroed(), ByteSize(0));
+    }
+
+    #[test]
+    #[cfg(feature = "zerocopy")]
+    fn byte_size_zerocopy() {
+        use zerocopy::{AsBytes, FromBytes};
+
+        #[derive(zerocopy::FromZeroes, zerocopy::FromBytes, zerocopy::AsBytes)]
+        #[repr(C)]
+        struct Header {
+            used: ByteSize,
+            free: ByteSize,
+        }
+
+        let header = Header {
+            used: ByteSize(1_536),
+            free: ByteSize(42),
+        };
+        let read = Header::read_from(header.as_bytes()).unwrap();
+        assert_eq!((read.used, read.free), (ByteSize(1_536), ByteSize(42)));
+        assert_eq!(ByteSize(7).as_bytes(), &7u64.to_ne_bytes());
+    }
+
+    #[test]
+    #[cfg(feature = "nom")]
+    fn nom_combinator() {
+        use nom::{
+            bytes::complete::tag,
+            error::{Error, ErrorKind},
+            sequence::separated_pair,
+            IResult, Parser,
+        };
+
+        let size = parser::combinator::<SIPrefixes>();
+        let range: IResult<&str, (u64, u64)> = separated_pair(size, tag(".."), size)("1kB..2MB;");
+        assert_eq!(range, Ok((";", (1_000, 2_000_000))));
+
+        let bytes: IResult<&str, ByteSize> = parser::byte_size_combinator().parse("1.5GiB)");
+        assert_eq!(bytes, Ok((")", ByteSize(1_610_612_736))));
+
+        let invalid: IResult<&str, u64> = parser::combinator::<SIPrefixes>().parse("x");
+        assert_eq!(
+            invalid,
+            Err(nom::Err::Error(Error::new("x", ErrorKind::MapRes)))
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "winnow")]
+    fn winnow_combinator() {
+        use winnow::{
+            combinator::separated_pair,
+            error::{ContextError, ErrMode},
+            Parser,
+        };
+
+        let size = parser::combinator::<SIPrefixes>();
+        let mut input = "1kB..2MB;";
+        let range: Result<(u64, u64), ErrMode<ContextError>> =
+            separated_pair(size, "..", size).parse_next(&mut input);
+        assert_eq!(range, Ok((1_000, 2_000_000)));
+        assert_eq!(input, ";");
+
+        let bytes =
+            Parser::<_, _, ContextError>::parse(&mut parser::byte_size_combinator(), "1.5GiB");
+        assert_eq!(bytes.unwrap(), ByteSize(1_610_612_736));
+
+        let mut input = "x";
+        let invalid: Result<u64, ErrMode<ContextError>> =
+            parser::combinator::<SIPrefixes>().parse_next(&mut input);
+        assert!(matches!(invalid, Err(ErrMode::Backtrack(_))));
+        assert_eq!(input, "x");
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_byte_size() {
+        use serde::de::{value::Error, Deserialize, IntoDeserializer};
+
+        let parse = |s: &str| ByteSize::deserialize(s.into_deserializer());
+        assert_eq!(parse("512MiB"), Ok::<_, Error>(ByteSize(536_870_912)));
+        assert_eq!(parse("1.5GB"), Ok(ByteSize(1_500_000_000)));
+        assert!(parse("1.5 GB").is_err());
+
+        let bytes = |n: u64| ByteSize::deserialize(n.into_deserializer());
+        assert_eq!(bytes(4_096), Ok::<_, Error>(ByteSize(4_096)));
+
+        let signed = |n: i64| ByteSize::deserialize(n.into_deserializer());
+        assert_eq!(signed(4_096), Ok::<_, Error>(ByteSize(4_096)));
+        assert!(signed(-1).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "clap")]
+    fn clap_value_parser() {
+        use clap::{error::ErrorKind, value_parser, Arg, Command};
+
+        let command = Command::new("cache").arg(
+            Arg::new("size")
+                .long("size")
+                .value_parser(value_parser!(ByteSize)),
+        );
+
+        let matches = command
+            .clone()
+            .try_get_matches_from(["cache", "--size", "1.5GiB"])
+            .unwrap();
+        assert_eq!(
+            matches.get_one::<ByteSize>("size"),
+            Some(&ByteSize(1_610_612_736))
+        );
+
+        let error = command
+            .try_get_matches_from(["cache", "--size", "2gib"])
+            .unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::ValueValidation);
+        assert!(error.to_string().contains("did you mean \"2GiB\"?"));
+        assert!(error.to_string().contains("--size <size>"));
+    }
+
+    #[test]
+    fn sortable_size() {
+        let sizes = [0, 9, 10, 999, 1_000, 1 << 40, u64::MAX - 1, u64::MAX];
+
+        for pair in sizes.windows(2) {
+            let (low, high) = (ByteSize(pair[0]).sortable(), ByteSize(pair[1]).sortable());
+            assert!(low.to_string() < high.to_string());
+            assert_eq!(low.to_string().len(), SortableSize::WIDTH);
+            assert_eq!(high.to_string().parse(), Ok(high));
+        }
+
+        assert_eq!(
+            ByteSize(u64::MAX).sortable().to_string(),
+            "18446744073709551615".to_string()
+        );
+        for input in &[
+            "",
+            "1",
+            "000000000000000000001",
+            "18446744073709551616",
+            "0000000000000000000x",
+        ] {
+            assert!(input.parse::<SortableSize>().is_err());
+        }
+    }
+
+    #[test]
+    fn require_exact() {
+        use std::fmt::Write;
+
+        let mut output = std::string::String::new();
+        write!(output, "{}B", SizeFormatterSI::new(999).require_exact()).unwrap();
+        write!(
+            output,
+            " {:.3}B",
+            SizeFormatterSI::new(1_999).require_exact()
+        )
+        .unwrap();
+        write!(
+            output,
+            " {:.2}B",
+            SizeFormatterBinary::new(1_280).require_exact()
+        )
+        .unwrap();
+        assert_eq!(output, "999B 1.999kB 1.25KiB".to_string());
+
+        assert!(write!(
+            output,
+            "{:.0}B",
+            SizeFormatterSI::new(1_999).require_exact()
+        )
+        .is_err());
+        assert!(write!(output, "{}B", SizeFormatterSI::new(1_999).require_exact()).is_err());
+        assert!(write!(
+            output,
+            "{:.1}B",
+            SizeFormatterBinary::new(1_280).require_exact()
+        )
+        .is_err());
+        assert!(write!(
+            output,
+            "{:.9}B",
+            SizeFormatterBinary::new(1_023 * 1_024 + 1).require_exact()
+        )
+        .is_err());
     }
 
     #[test]
@@ -0,0 +1,139 @@
+//! This module contains the formatter for lists of sizes.
+
+use core::{
+    borrow::Borrow,
+    fmt::{self, Display},
+    marker::PhantomData,
+};
+use num::{integer::Integer, rational::Ratio, traits::cast::FromPrimitive, traits::Pow};
+use {BinaryPrefixes, DecimalSeparator, PointSeparated, PrefixType, SIPrefixes, SizeFormatter};
+
+/// Implements `Display` to format a list of sizes using SI prefixes.
+pub type SizeListSI<Sizes> = SizeList<Sizes, u64, SIPrefixes, PointSeparated>;
+
+/// Implements `Display` to format a list of sizes using binary prefixes.
+pub type SizeListBinary<Sizes> = SizeList<Sizes, u64, BinaryPrefixes, PointSeparated>;
+
+/// Represents a list of sizes that can be formatted.
+///
+/// Any collection or iterator that can be iterated repeatedly works, such as slices.
+/// The format options, like precision and width, apply to every size in the list.
+/// ```
+/// use size_format::SizeListSI;
+///
+/// let sizes = vec![1_200_000, 440_000, 2_000_000_000];
+///
+/// assert_eq!(
+///     format!("{}", SizeListSI::new(&sizes).with_unit("B")),
+///     "1.2MB, 440.0kB, 2.0GB".to_string()
+/// );
+/// assert_eq!(
+///     format!("{:.4}", SizeListSI::new(&sizes).with_unit("B").with_shared_prefix()),
+///     "0.0012GB, 0.0004GB, 2.0000GB".to_string()
+/// );
+/// ```
+pub struct SizeList<Sizes, BaseType, Prefix, Separator>
+where
+    Sizes: IntoIterator + Clone,
+    Sizes::Item: Borrow<BaseType>,
+    BaseType: Clone + Integer + Display + FromPrimitive + Pow<u32, Output = BaseType>,
+    Ratio<BaseType>: FromPrimitive,
+    Prefix: PrefixType,
+    Separator: DecimalSeparator,
+{
+    /// The sizes to be formatted.
+    sizes: Sizes,
+    /// The separator between two sizes.
+    separator: &'static str,
+    /// The unit written after every size.
+    unit: &'static str,
+    /// Whether all sizes use the prefix of the largest size.
+    shared_prefix: bool,
+    _marker: PhantomData<(BaseType, Prefix, Separator)>,
+}
+
+impl<Sizes, BaseType, Prefix, Separator> SizeList<Sizes, BaseType, Prefix, Separator>
+where
+    Sizes: IntoIterator + Clone,
+    Sizes::Item: Borrow<BaseType>,
+    BaseType: Clone + Integer + Display + FromPrimitive + Pow<u32, Output = BaseType>,
+    Ratio<BaseType>: FromPrimitive,
+    Prefix: PrefixType,
+    Separator: DecimalSeparator,
+{
+    /// Creates a new list formatter, separating the sizes by ", ".
+    pub fn new(sizes: Sizes) -> SizeList<Sizes, BaseType, Prefix, Separator> {
+        SizeList {
+            sizes,
+            separator: ", ",
+            unit: "",
+            shared_prefix: false,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Sets the separator between two sizes.
+    pub fn with_separator(
+        mut self,
+        separator: &'static str,
+    ) -> SizeList<Sizes, BaseType, Prefix, Separator> {
+        self.separator = separator;
+        self
+    }
+
+    /// Sets the unit that is written after every size.
+    pub fn with_unit(mut self, unit: &'static str) -> SizeList<Sizes, BaseType, Prefix, Separator> {
+        self.unit = unit;
+        self
+    }
+
+    /// Makes all sizes use the prefix of the largest size, so they are easier to compare.
+    pub fn with_shared_prefix(mut self) -> SizeList<Sizes, BaseType, Prefix, Separator> {
+        self.shared_prefix = true;
+        self
+    }
+}
+
+impl<Sizes, BaseType, Prefix, Separator> Display for SizeList<Sizes, BaseType, Prefix, Separator>
+where
+    Sizes: IntoIterator + Clone,
+    Sizes::Item: Borrow<BaseType>,
+    BaseType: Clone + Integer + Display + FromPrimitive + Pow<u32, Output = BaseType>,
+    Ratio<BaseType>: FromPrimitive,
+    Prefix: PrefixType,
+    Separator: DecimalSeparator,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let shared_prefix = if self.shared_prefix {
+            self.sizes
+                .clone()
+                .into_iter()
+                .map(|size| size.borrow().clone())
+                .max()
+                .map(|max| {
+                    SizeFormatter::<BaseType, Prefix, Separator>::new(max)
+                        .chosen_prefix()
+                        .index()
+                })
+        } else {
+            None
+        };
+
+        for (i, size) in self.sizes.clone().into_iter().enumerate() {
+            if i > 0 {
+                f.write_str(self.separator)?;
+            }
+
+            let mut formatter =
+                SizeFormatter::<BaseType, Prefix, Separator>::new(size.borrow().clone());
+            if let Some(index) = shared_prefix {
+                formatter = formatter.with_forced_prefix(index);
+            }
+
+            Display::fmt(&formatter, f)?;
+            f.write_str(self.unit)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,184 @@
+//! This module contains stateful helpers for displays that are updated continuously.
+
+use align;
+use core::{
+    fmt::{self, Alignment, Display},
+    marker::PhantomData,
+};
+use math::bytes_in;
+use {BinaryPrefixes, DecimalSeparator, PointSeparated, PrefixType, SIPrefixes, SizeFormatter};
+
+/// Chooses prefixes using SI prefixes with hysteresis.
+pub type PrefixHysteresisSI = PrefixHysteresis<SIPrefixes, PointSeparated>;
+
+/// Chooses prefixes using binary prefixes with hysteresis.
+pub type PrefixHysteresisBinary = PrefixHysteresis<BinaryPrefixes, PointSeparated>;
+
+/// Keeps the prefix of a live display stable while the value hovers around a prefix boundary.
+///
+/// The prefix only changes after the value moves past the boundary by the margin, given in
+/// percent of the boundary. This avoids flickering between "999.9kB/s" and "1.0MB/s".
+/// ```
+/// use size_format::PrefixHysteresisSI;
+///
+/// let mut hysteresis = PrefixHysteresisSI::new(5);
+///
+/// assert_eq!(format!("{}B/s", hysteresis.format(999_900)), "999.9kB/s".to_string());
+/// assert_eq!(format!("{}B/s", hysteresis.format(1_000_100)), "1000.1kB/s".to_string());
+/// assert_eq!(format!("{}B/s", hysteresis.format(1_050_000)), "1.0MB/s".to_string());
+/// assert_eq!(format!("{}B/s", hysteresis.format(999_900)), "0.9MB/s".to_string());
+/// assert_eq!(format!("{}B/s", hysteresis.format(940_000)), "940.0kB/s".to_string());
+/// ```
+pub struct PrefixHysteresis<Prefix, Separator>
+where
+    Prefix: PrefixType,
+    Separator: DecimalSeparator,
+{
+    /// How far, in percent of the boundary, a value must move past it to change the prefix.
+    margin_percent: u32,
+    /// The index of the prefix used for the previous value.
+    current: Option<usize>,
+    _marker: PhantomData<(Prefix, Separator)>,
+}
+
+impl<Prefix, Separator> PrefixHysteresis<Prefix, Separator>
+where
+    Prefix: PrefixType,
+    Separator: DecimalSeparator,
+{
+    /// Creates a new hysteresis with the given margin in percent.
+    pub fn new(margin_percent: u32) -> PrefixHysteresis<Prefix, Separator> {
+        PrefixHysteresis {
+            margin_percent,
+            current: None,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns a formatter for the next value, keeping the previous prefix if it is close enough.
+    pub fn format(&mut self, value: u64) -> SizeFormatter<u64, Prefix, Separator> {
+        let formatter = SizeFormatter::new(value);
+        let natural = formatter.chosen_prefix().index();
+
+        let index = match self.current {
+            Some(current) if natural > current => {
+                if value_at_least::<Prefix>(value, natural, 100 + self.margin_percent) {
+                    natural
+                } else {
+                    natural - 1
+                }
+            }
+            Some(current) if natural < current => {
+                if value_at_least::<Prefix>(
+                    value,
+                    current,
+                    100u32.saturating_sub(self.margin_percent),
+                ) {
+                    current
+                } else {
+                    natural
+                }
+            }
+            _ => natural,
+        };
+
+        self.current = Some(index);
+        formatter.with_forced_prefix(index)
+    }
+
+    /// Forgets the previous prefix, so the next value chooses its prefix freely.
+    pub fn reset(&mut self) {
+        self.current = None;
+    }
+}
+
+/// Checks whether `value` is at least `percent` percent of the boundary of the prefix at `index`.
+fn value_at_least<Prefix: PrefixType>(value: u64, index: usize, percent: u32) -> bool {
+    bytes_in::<Prefix, u128>(index)
+        .and_then(|boundary| boundary.checked_mul(u128::from(percent)))
+        .is_some_and(|threshold| u128::from(value) * 100 >= threshold)
+}
+
+/// Keeps the width of a live display from shrinking, so the surrounding layout stays stable.
+///
+/// Every value is padded to the widest value seen so far, which avoids jumps when a
+/// progress display changes from "9.9MB" to "10.0MB" and back to a shorter value.
+/// A new tracker should be used for every transfer.
+/// ```
+/// use size_format::{SizeFormatterSI, WidthTracker};
+///
+/// let mut tracker = WidthTracker::new();
+///
+/// assert_eq!(format!("[{}]", tracker.pad(SizeFormatterSI::new(9_900_000))), "[9.9M]".to_string());
+/// assert_eq!(format!("[{}]", tracker.pad(SizeFormatterSI::new(10_000_000))), "[10.0M]".to_string());
+/// assert_eq!(format!("[{}]", tracker.pad(SizeFormatterSI::new(1_000))), "[ 1.0k]".to_string());
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct WidthTracker {
+    /// The largest display width seen so far.
+    width: usize,
+}
+
+impl WidthTracker {
+    /// Creates a new tracker that has not seen any values yet.
+    pub fn new() -> WidthTracker {
+        WidthTracker { width: 0 }
+    }
+
+    /// Returns the largest display width seen so far.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Forgets all previously seen widths.
+    pub fn reset(&mut self) {
+        self.width = 0;
+    }
+
+    /// Pads the value to the largest width seen so far, including the value itself.
+    ///
+    /// Values are right aligned by default, but the alignment and fill of the format string
+    /// are used if they are given.
+    pub fn pad<T: Display>(&mut self, value: T) -> StableWidth<T> {
+        let value_width = align::width_of(&value);
+        self.width = self.width.max(value_width);
+
+        StableWidth {
+            value,
+            width: self.width,
+            value_width,
+        }
+    }
+}
+
+/// A value padded to the width tracked by a `WidthTracker`.
+pub struct StableWidth<T: Display> {
+    /// The value to be displayed.
+    value: T,
+    /// The width to pad the value to.
+    width: usize,
+    /// The display width of the value itself.
+    value_width: usize,
+}
+
+impl<T: Display> Display for StableWidth<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let padding = self.width.saturating_sub(self.value_width);
+        let (before, after) = match f.align().unwrap_or(Alignment::Right) {
+            Alignment::Left => (0, padding),
+            Alignment::Right => (padding, 0),
+            Alignment::Center => (padding / 2, padding - padding / 2),
+        };
+        let fill = f.fill();
+
+        for _ in 0..before {
+            fmt::Write::write_char(f, fill)?;
+        }
+        write!(f, "{}", self.value)?;
+        for _ in 0..after {
+            fmt::Write::write_char(f, fill)?;
+        }
+
+        Ok(())
+    }
+}
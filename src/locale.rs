@@ -0,0 +1,154 @@
+//! This module contains the runtime representation of locales.
+
+use config::DecimalSeparator;
+use digits::{AsciiDigits, DigitSet};
+use {LocaleCH, LocaleDE, LocaleFR, PointSeparated};
+
+/// Describes how numbers are written in a locale, chosen at runtime.
+///
+/// Unlike the `DecimalSeparator` marker types, a locale is a plain value, so it can be
+/// picked from user settings without creating a formatter type per locale.
+/// It is applied to a formatter using `FormatOptions`.
+/// ```
+/// use size_format::{ArabicIndicDigits, FormatOptions, Locale, SizeFormatterSI};
+///
+/// let locale = Locale::from_tag("de").unwrap();
+/// let options = FormatOptions::new().with_locale(locale);
+/// assert_eq!(
+///     format!("{}B", SizeFormatterSI::new(42_500_000).with_options(&options)),
+///     "42,5 MB".to_string()
+/// );
+///
+/// let locale = Locale::new("٫").with_digits::<ArabicIndicDigits>();
+/// let options = FormatOptions::new().with_locale(locale);
+/// assert_eq!(
+///     format!("{}B", SizeFormatterSI::new(42_000_000).with_options(&options)),
+///     "٤٢٫٠MB".to_string()
+/// );
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Locale {
+    /// The separator between the integer and the fractional part.
+    decimal_separator: &'static str,
+    /// The separator between groups of three digits in the integer part.
+    group_separator: &'static str,
+    /// The spacing between the number and the prefix.
+    unit_spacing: &'static str,
+    /// The symbol used for bytes.
+    byte_unit: &'static str,
+    /// The mapping of the ASCII digits to the digit set of the locale.
+    digits: fn(char) -> char,
+}
+
+impl Locale {
+    /// Creates a new locale with the given decimal separator.
+    ///
+    /// Digits are not grouped, there is no spacing before the prefix and ASCII digits are used.
+    pub fn new(decimal_separator: &'static str) -> Locale {
+        Locale {
+            decimal_separator,
+            group_separator: "",
+            unit_spacing: "",
+            byte_unit: "B",
+            digits: AsciiDigits::map,
+        }
+    }
+
+    /// Creates a locale matching the given compile time separator type.
+    pub fn of<Separator: DecimalSeparator>() -> Locale {
+        Locale {
+            decimal_separator: Separator::SEPARATOR,
+            group_separator: Separator::GROUP_SEPARATOR,
+            unit_spacing: Separator::UNIT_SPACING,
+            byte_unit: Separator::BYTE_UNIT,
+            digits: AsciiDigits::map,
+        }
+    }
+
+    /// Looks up one of the built-in locales by its language tag.
+    ///
+    /// The supported tags are "en", "de", "fr" and "de-CH", compared case insensitively
+    /// and allowing '_' instead of '-'. Other regions of a supported language use the
+    /// locale of the language.
+    pub fn from_tag(tag: &str) -> Option<Locale> {
+        let (language, region) = match tag.find(&['-', '_'][..]) {
+            Some(index) => (&tag[..index], &tag[index + 1..]),
+            None => (tag, ""),
+        };
+
+        if language.eq_ignore_ascii_case("de") && region.eq_ignore_ascii_case("ch") {
+            Some(Locale::of::<LocaleCH>())
+        } else if language.eq_ignore_ascii_case("en") {
+            Some(Locale::of::<PointSeparated>())
+        } else if language.eq_ignore_ascii_case("de") {
+            Some(Locale::of::<LocaleDE>())
+        } else if language.eq_ignore_ascii_case("fr") {
+            Some(Locale::of::<LocaleFR>())
+        } else {
+            None
+        }
+    }
+
+    /// Sets the separator between groups of three digits in the integer part.
+    pub fn with_group_separator(mut self, group_separator: &'static str) -> Locale {
+        self.group_separator = group_separator;
+        self
+    }
+
+    /// Sets the separator between the integer and the fractional part.
+    pub fn with_decimal_separator(mut self, decimal_separator: &'static str) -> Locale {
+        self.decimal_separator = decimal_separator;
+        self
+    }
+
+    /// Sets the spacing between the number and the prefix.
+    pub fn with_unit_spacing(mut self, unit_spacing: &'static str) -> Locale {
+        self.unit_spacing = unit_spacing;
+        self
+    }
+
+    /// Sets the symbol used for bytes.
+    pub fn with_byte_unit(mut self, byte_unit: &'static str) -> Locale {
+        self.byte_unit = byte_unit;
+        self
+    }
+
+    /// Sets the digit set used for the formatted output.
+    pub fn with_digits<Digits: DigitSet>(mut self) -> Locale {
+        self.digits = Digits::map;
+        self
+    }
+
+    /// Returns the separator between the integer and the fractional part.
+    pub fn decimal_separator(&self) -> &'static str {
+        self.decimal_separator
+    }
+
+    /// Returns the separator between groups of three digits in the integer part.
+    pub fn group_separator(&self) -> &'static str {
+        self.group_separator
+    }
+
+    /// Returns the spacing between the number and the prefix.
+    pub fn unit_spacing(&self) -> &'static str {
+        self.unit_spacing
+    }
+
+    /// Returns the symbol used for bytes.
+    ///
+    /// Since the unit is not part of the formatted number, this is meant to be appended by the caller.
+    pub fn byte_unit(&self) -> &'static str {
+        self.byte_unit
+    }
+
+    /// Returns the mapping of the formatted output to the digit set of the locale.
+    pub(crate) fn digits(&self) -> fn(char) -> char {
+        self.digits
+    }
+}
+
+impl Default for Locale {
+    fn default() -> Locale {
+        Locale::of::<PointSeparated>()
+    }
+}
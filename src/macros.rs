@@ -0,0 +1,77 @@
+//! This module contains macros for writing sizes in code.
+
+/// Expands to the number of bytes in a size literal such as `size!(1.5 GiB)`.
+///
+/// The size is parsed by `parse_size_const` at compile time, so invalid prefixes or units
+/// and sizes that do not fit into a `u64` are compile errors.
+///
+/// Unlike a procedural macro, this cannot point at the offending token. Errors are reported as a
+/// failed constant evaluation (E0080) with a message such as "unknown prefix", and the size has
+/// to be written as tokens, since string literals are rejected.
+/// ```
+/// #[macro_use]
+/// extern crate size_format;
+///
+/// # fn main() {
+/// const CACHE_LIMIT: u64 = size!(256 MiB);
+///
+/// assert_eq!(CACHE_LIMIT, 256 * 1024 * 1024);
+/// assert_eq!(size!(1.5 GiB), 1_610_612_736);
+/// assert_eq!(size!(42 MB), 42_000_000);
+/// assert_eq!(size!(1_000 MB), 1_000_000_000);
+/// assert_eq!(size!(512), 512);
+/// # }
+/// ```
+/// A typo in the prefix or unit does not compile:
+/// ```compile_fail,E0080
+/// #[macro_use]
+/// extern crate size_format;
+///
+/// # fn main() {
+/// let limit = size!(256 MIB);
+/// # }
+/// ```
+/// ```compile_fail,E0080
+/// #[macro_use]
+/// extern crate size_format;
+///
+/// # fn main() {
+/// let limit = size!(1.5 GiX);
+/// # }
+/// ```
+/// ```compile_fail,E0080
+/// #[macro_use]
+/// extern crate size_format;
+///
+/// # fn main() {
+/// let limit = size!(1.5 kiB);
+/// # }
+/// ```
+/// Neither do sizes that do not fit into a `u64`, or sizes written as strings:
+/// ```compile_fail,E0080
+/// #[macro_use]
+/// extern crate size_format;
+///
+/// # fn main() {
+/// let limit = size!(16 EiB);
+/// # }
+/// ```
+/// ```compile_fail,E0080
+/// #[macro_use]
+/// extern crate size_format;
+///
+/// # fn main() {
+/// let limit = size!("1.5 GiB");
+/// # }
+/// ```
+#[macro_export]
+macro_rules! size {
+    ($value:literal $unit:ident) => {{
+        const BYTES: u64 = $crate::parse_size_const(concat!(stringify!($value), stringify!($unit)));
+        BYTES
+    }};
+    ($value:literal) => {{
+        const BYTES: u64 = $crate::parse_size_const(stringify!($value));
+        BYTES
+    }};
+}
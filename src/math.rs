@@ -0,0 +1,251 @@
+//! This module contains the arithmetic used for scaling numbers between prefixes.
+//!
+//! None of these functions overflow: they either return `None` or compute the exact result.
+
+use core::cmp::{self, Ordering};
+use num::{integer::Integer, traits::cast::FromPrimitive, traits::CheckedMul};
+use PrefixType;
+
+/// Specifies how a number is rounded when it cannot be represented exactly.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Rounding {
+    /// Rounds towards zero.
+    Down,
+    /// Rounds away from zero.
+    Up,
+    /// Rounds to the nearest number, with ties rounded away from zero.
+    HalfUp,
+    /// Rounds to the nearest number, with ties rounded to the even neighbour.
+    ///
+    /// This is also known as banker's rounding and avoids a bias when many values are rounded.
+    HalfEven,
+}
+
+/// Returns the number of times `num` can be divided by `base`.
+///
+/// This is the integer logarithm of `num` to `base`, except that it is zero for zero.
+///
+/// # Panics
+/// - Panics if `base` is less than 2.
+/// ```
+/// use size_format::math::int_log;
+///
+/// assert_eq!(int_log(999u32, 1000), 0);
+/// assert_eq!(int_log(1_000_000u32, 1000), 2);
+/// ```
+pub fn int_log<T>(mut num: T, base: T) -> usize
+where
+    T: Clone + Integer,
+{
+    assert!(
+        base > T::one(),
+        "the base of a logarithm must be at least 2"
+    );
+
+    let mut divisions = 0;
+
+    while num >= base {
+        num = num / base.clone();
+        divisions += 1;
+    }
+
+    divisions
+}
+
+/// Returns `base` raised to the power of `exp`, or `None` if that overflows.
+/// ```
+/// use size_format::math::checked_pow;
+///
+/// assert_eq!(checked_pow(1024u32, 3), Some(1_073_741_824));
+/// assert_eq!(checked_pow(1024u32, 4), None);
+/// ```
+pub fn checked_pow<T>(base: T, exp: u32) -> Option<T>
+where
+    T: Clone + Integer + CheckedMul,
+{
+    let mut result = T::one();
+
+    for _ in 0..exp {
+        result = result.checked_mul(&base)?;
+    }
+
+    Some(result)
+}
+
+/// Returns the factor between the prefix at `index` and the prefix after it.
+///
+/// This is the entry in `PrefixType::STEP_SIZES` if there is one and the prefix size otherwise.
+/// ```
+/// use size_format::{math::step_size, SIPrefixes};
+///
+/// assert_eq!(step_size::<SIPrefixes>(3), 1000);
+/// ```
+pub fn step_size<Prefix: PrefixType>(index: usize) -> u32 {
+    Prefix::STEP_SIZES
+        .get(index)
+        .cloned()
+        .unwrap_or(Prefix::PREFIX_SIZE)
+}
+
+/// Returns the number of base units in the prefix at `index`, or `None` if that overflows.
+/// ```
+/// use size_format::{math::bytes_in, BinaryPrefixes, SIPrefixes};
+///
+/// assert_eq!(bytes_in::<SIPrefixes, u64>(2), Some(1_000_000));
+/// assert_eq!(bytes_in::<BinaryPrefixes, u64>(3), Some(1_073_741_824));
+/// assert_eq!(bytes_in::<BinaryPrefixes, u64>(7), None);
+/// ```
+pub fn bytes_in<Prefix, T>(index: usize) -> Option<T>
+where
+    Prefix: PrefixType,
+    T: Clone + Integer + CheckedMul + FromPrimitive,
+{
+    let mut result = T::one();
+
+    // Every step is at least 2, so this stops at the first overflow for large indices.
+    for step in 0..index {
+        result = result.checked_mul(&T::from_u32(step_size::<Prefix>(step))?)?;
+    }
+
+    Some(result)
+}
+
+/// Converts `value` from the prefix at index `from` to the prefix at index `to`.
+///
+/// Returns `None` if the result does not fit into the type.
+/// ```
+/// use size_format::{math::{scale, Rounding}, SIPrefixes};
+///
+/// // 1500 kB in MB.
+/// assert_eq!(scale::<SIPrefixes, u64>(1_500, 1, 2, Rounding::Down), Some(1));
+/// assert_eq!(scale::<SIPrefixes, u64>(1_500, 1, 2, Rounding::HalfUp), Some(2));
+/// assert_eq!(scale::<SIPrefixes, u64>(2_500, 1, 2, Rounding::HalfEven), Some(2));
+/// // 3 GB in kB.
+/// assert_eq!(scale::<SIPrefixes, u64>(3, 3, 1, Rounding::Down), Some(3_000_000));
+/// ```
+pub fn scale<Prefix, T>(value: T, from: usize, to: usize, rounding: Rounding) -> Option<T>
+where
+    Prefix: PrefixType,
+    T: Clone + Integer + CheckedMul + FromPrimitive,
+{
+    if to == from {
+        return Some(value);
+    }
+
+    let (low, high) = (cmp::min(from, to), cmp::max(from, to));
+    // Steps beyond the listed step sizes all use the prefix size, so one of them is enough.
+    let last_distinct = cmp::max(low, Prefix::STEP_SIZES.len()) + 1;
+    if (low..cmp::min(high, last_distinct))
+        .any(|step| T::from_u32(step_size::<Prefix>(step)).is_none())
+    {
+        return None;
+    }
+    let factor = |step: usize| {
+        T::from_u32(step_size::<Prefix>(low + step)).expect("the steps were checked to fit")
+    };
+
+    if to < from {
+        return value.checked_mul(&checked_product(&factor, from - to)?);
+    }
+
+    let (quotient, rem) = match checked_product(&factor, to - from) {
+        Some(divisor) => value.div_rem(&divisor),
+        // The divisor is larger than any value.
+        None => (T::zero(), value),
+    };
+
+    Some(round_by(quotient, rem, &factor, to - from, rounding))
+}
+
+/// Returns the product of the first `steps` factors, or `None` if that overflows.
+fn checked_product<T>(factor: &dyn Fn(usize) -> T, steps: usize) -> Option<T>
+where
+    T: Clone + Integer + CheckedMul,
+{
+    let mut result = T::one();
+
+    for step in 0..steps {
+        result = result.checked_mul(&factor(step))?;
+    }
+
+    Some(result)
+}
+
+/// Rounds the `quotient` of a division by `base^steps` that left the remainder `rem`.
+pub(crate) fn round<T>(quotient: T, rem: T, base: T, steps: u32, rounding: Rounding) -> T
+where
+    T: Clone + Integer + CheckedMul,
+{
+    round_by(quotient, rem, &|_| base.clone(), steps as usize, rounding)
+}
+
+/// Rounds the `quotient` of a division by the product of the first `steps` factors.
+fn round_by<T>(
+    quotient: T,
+    rem: T,
+    factor: &dyn Fn(usize) -> T,
+    steps: usize,
+    rounding: Rounding,
+) -> T
+where
+    T: Clone + Integer + CheckedMul,
+{
+    let round_up = match rounding {
+        Rounding::Down => false,
+        Rounding::Up => !rem.is_zero(),
+        Rounding::HalfUp => compare_to_half(rem, factor, steps) != Ordering::Less,
+        Rounding::HalfEven => match compare_to_half(rem, factor, steps) {
+            Ordering::Less => false,
+            Ordering::Equal => quotient.is_odd(),
+            Ordering::Greater => true,
+        },
+    };
+
+    if round_up {
+        quotient + T::one()
+    } else {
+        quotient
+    }
+}
+
+/// Compares `2 * rem` to the product of the first `steps` factors, where `rem` is less than it.
+///
+/// This works even if the product does not fit into the type, by comparing digit by digit.
+fn compare_to_half<T>(rem: T, factor: &dyn Fn(usize) -> T, steps: usize) -> Ordering
+where
+    T: Clone + Integer + CheckedMul,
+{
+    if steps == 0 {
+        return if rem.is_zero() {
+            Ordering::Less
+        } else {
+            Ordering::Greater
+        };
+    }
+
+    // Split `rem` into its leading digit in base `base` and the rest.
+    let base = factor(steps - 1);
+    let lower = match checked_product(factor, steps - 1) {
+        Some(lower) => lower,
+        // Then `rem` is less than the lower product, which is at most half of the full product.
+        None => return Ordering::Less,
+    };
+    let (digit, rest) = rem.div_rem(&lower);
+
+    // Compare `2 * digit` with `base`, without computing `2 * digit`.
+    let other = base.clone() - digit.clone();
+    if digit > other {
+        Ordering::Greater
+    } else if digit == other {
+        if rest.is_zero() {
+            Ordering::Equal
+        } else {
+            Ordering::Greater
+        }
+    } else if digit + T::one() == other {
+        // For odd bases the missing half of a digit is decided by the rest.
+        compare_to_half(rest, factor, steps - 1)
+    } else {
+        Ordering::Less
+    }
+}
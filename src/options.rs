@@ -0,0 +1,259 @@
+//! This module contains the runtime formatting options.
+
+use align;
+use core::fmt::{self, Display};
+use locale::Locale;
+use {SizeFormatterBinary, SizeFormatterBinary128, SizeFormatterSI, Spacing};
+
+/// Formatting options that are chosen at runtime.
+///
+/// The options are applied to a formatter using its `with_options` method and override
+/// the corresponding compile time configuration of the formatter.
+/// They can also be used to format byte sizes directly with `format_with`, in which case
+/// the whole configuration is decided at runtime.
+///
+/// Whether a prefix system was chosen is part of the type, since formatters cannot change
+/// their prefix type. Only options without a prefix system can be applied to formatters.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FormatOptions<System = NoPrefixSystem> {
+    /// The locale to use, if it should override the separator type of the formatter.
+    locale: Option<Locale>,
+    /// The prefix system used by `format_with`.
+    prefix_system: System,
+    /// The precision to use if the format string does not specify one.
+    precision: Option<usize>,
+}
+
+/// Marks options that do not choose a prefix system.
+///
+/// Formatters keep their prefix type with such options and `format_with` uses binary prefixes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct NoPrefixSystem;
+
+impl From<NoPrefixSystem> for PrefixSystem {
+    fn from(_: NoPrefixSystem) -> PrefixSystem {
+        PrefixSystem::Binary
+    }
+}
+
+/// The prefix systems that can be chosen at runtime.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum PrefixSystem {
+    /// SI prefixes in powers of 1000 (kB, MB, GB, ...).
+    SI,
+    /// Binary prefixes in powers of 1024 (KiB, MiB, GiB, ...).
+    #[default]
+    Binary,
+    /// Prefixes defined at runtime.
+    Custom(RuntimePrefixes),
+}
+
+/// A prefix system defined by plain values instead of a `PrefixType` implementation.
+///
+/// This allows unit systems to be defined by plugins or configuration files.
+/// It is chosen for `format_with` using `PrefixSystem::Custom`.
+/// ```
+/// use size_format::{format_with, FormatOptions, PrefixSystem, RuntimePrefixes};
+///
+/// let prefixes = RuntimePrefixes::new(1000, &["", "k", "M"]).unwrap();
+/// let options = FormatOptions::new().with_prefix_system(PrefixSystem::Custom(prefixes));
+///
+/// assert_eq!(format_with(512, &options).to_string(), "512B".to_string());
+/// assert_eq!(format_with(42_500, &options).to_string(), "42.5kB".to_string());
+/// assert_eq!(format_with(42_000_000_000, &options).to_string(), "42000.0MB".to_string());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct RuntimePrefixes {
+    /// The factor between consecutive prefixes.
+    base: u32,
+    /// The prefixes, starting with the one for unscaled numbers.
+    prefixes: &'static [&'static str],
+}
+
+impl RuntimePrefixes {
+    /// Creates a prefix system that scales by `base` between consecutive prefixes.
+    ///
+    /// The first prefix is used for unscaled numbers.
+    /// Returns `None` if the base is less than 2, since numbers could not be scaled then.
+    pub fn new(base: u32, prefixes: &'static [&'static str]) -> Option<RuntimePrefixes> {
+        if base < 2 {
+            return None;
+        }
+
+        Some(RuntimePrefixes { base, prefixes })
+    }
+
+    /// Returns the factor between consecutive prefixes.
+    pub fn base(&self) -> u32 {
+        self.base
+    }
+
+    /// Returns the prefixes, starting with the one for unscaled numbers.
+    pub fn prefixes(&self) -> &'static [&'static str] {
+        self.prefixes
+    }
+}
+
+impl FormatOptions {
+    /// Creates new options that keep the compile time configuration of the formatter.
+    ///
+    /// For `format_with`, this means binary prefixes and the point separated locale.
+    pub fn new() -> FormatOptions {
+        FormatOptions {
+            locale: None,
+            prefix_system: NoPrefixSystem,
+            precision: None,
+        }
+    }
+}
+
+impl<System> FormatOptions<System> {
+    /// Sets the locale used to write the number.
+    pub fn with_locale(mut self, locale: Locale) -> FormatOptions<System> {
+        self.locale = Some(locale);
+        self
+    }
+
+    /// Sets the decimal separator, keeping the other settings of the locale.
+    pub fn with_separator(mut self, separator: &'static str) -> FormatOptions<System> {
+        self.locale = Some(
+            self.locale
+                .unwrap_or_default()
+                .with_decimal_separator(separator),
+        );
+        self
+    }
+
+    /// Sets the spacing between the number and the prefix, keeping the other settings of the locale.
+    pub fn with_spacing(mut self, spacing: Spacing) -> FormatOptions<System> {
+        self.locale = Some(
+            self.locale
+                .unwrap_or_default()
+                .with_unit_spacing(spacing.as_str()),
+        );
+        self
+    }
+
+    /// Sets the prefix system used by `format_with`.
+    ///
+    /// Formatters always use the prefix type they were created with, so the returned options
+    /// can no longer be applied to them.
+    /// ```compile_fail,E0308
+    /// use size_format::{FormatOptions, PrefixSystem, SizeFormatterBinary};
+    ///
+    /// let options = FormatOptions::new().with_prefix_system(PrefixSystem::SI);
+    /// SizeFormatterBinary::new(1_536).with_options(&options);
+    /// ```
+    pub fn with_prefix_system(self, prefix_system: PrefixSystem) -> FormatOptions<PrefixSystem> {
+        FormatOptions {
+            locale: self.locale,
+            prefix_system,
+            precision: self.precision,
+        }
+    }
+
+    /// Sets the precision used if the format string does not specify one.
+    pub fn with_precision(mut self, precision: usize) -> FormatOptions<System> {
+        self.precision = Some(precision);
+        self
+    }
+
+    /// Returns the locale used to write the number, if one was set.
+    pub fn locale(&self) -> Option<Locale> {
+        self.locale
+    }
+
+    /// Returns the precision used if the format string does not specify one, if it was set.
+    pub fn precision(&self) -> Option<usize> {
+        self.precision
+    }
+
+    /// Returns the options without the prefix system, so they can be applied to a formatter.
+    fn without_prefix_system(&self) -> FormatOptions {
+        FormatOptions {
+            locale: self.locale,
+            prefix_system: NoPrefixSystem,
+            precision: self.precision,
+        }
+    }
+}
+
+impl<System: Copy + Into<PrefixSystem>> FormatOptions<System> {
+    /// Returns the prefix system used by `format_with`.
+    pub fn prefix_system(&self) -> PrefixSystem {
+        self.prefix_system.into()
+    }
+}
+
+/// Formats a byte size with options chosen at runtime.
+///
+/// The size is followed by the byte unit of the locale.
+/// ```
+/// use size_format::{format_with, FormatOptions, PrefixSystem, Spacing};
+///
+/// let options = FormatOptions::new()
+///     .with_prefix_system(PrefixSystem::SI)
+///     .with_separator(",")
+///     .with_spacing(Spacing::Space)
+///     .with_precision(2);
+///
+/// assert_eq!(format_with(1_234_567, &options).to_string(), "1,23 MB".to_string());
+/// assert_eq!(format!("{:.0}", format_with(1_234_567, &options)), "1 MB".to_string());
+/// assert_eq!(format_with(1_536, &FormatOptions::new()).to_string(), "1.5KiB".to_string());
+/// ```
+pub fn format_with<System: Copy + Into<PrefixSystem>>(
+    bytes: u64,
+    options: &FormatOptions<System>,
+) -> SizeWithOptions {
+    SizeWithOptions {
+        bytes,
+        options: options
+            .without_prefix_system()
+            .with_prefix_system(options.prefix_system()),
+    }
+}
+
+/// A byte size together with the options used to format it, created by `format_with`.
+#[derive(Clone, Copy, Debug)]
+pub struct SizeWithOptions {
+    /// The number of bytes.
+    bytes: u64,
+    /// The options used for formatting.
+    options: FormatOptions<PrefixSystem>,
+}
+
+impl SizeWithOptions {
+    /// Writes the size without any padding.
+    pub(crate) fn write_unpadded(
+        &self,
+        f: &mut dyn fmt::Write,
+        precision: Option<usize>,
+    ) -> fmt::Result {
+        let options = self.options.without_prefix_system();
+
+        match self.options.prefix_system {
+            PrefixSystem::SI => SizeFormatterSI::new(self.bytes)
+                .with_options(&options)
+                .write_unpadded(f, precision),
+            PrefixSystem::Binary => SizeFormatterBinary::new(self.bytes)
+                .with_options(&options)
+                .write_unpadded(f, precision),
+            PrefixSystem::Custom(prefixes) => SizeFormatterBinary128::new(u128::from(self.bytes))
+                .with_runtime_prefixes(prefixes)
+                .with_options(&options)
+                .write_unpadded(f, precision),
+        }?;
+
+        f.write_str(self.options.locale.unwrap_or_default().byte_unit())
+    }
+}
+
+impl Display for SizeWithOptions {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let precision = f.precision();
+
+        align::pad(f, fmt::Alignment::Right, |w| {
+            self.write_unpadded(w, precision)
+        })
+    }
+}
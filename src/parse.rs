@@ -0,0 +1,1084 @@
+//! This module contains parsers for sizes.
+
+use config::{PrefixType, SymbolCase};
+use core::{
+    fmt::{self, Display},
+    ops::RangeInclusive,
+};
+use math::bytes_in;
+use num::{
+    integer::Integer,
+    traits::{cast::FromPrimitive, CheckedAdd, CheckedMul},
+};
+use {BinaryPrefixes, ByteSize, SIPrefixes};
+
+/// The error returned when a size could not be parsed.
+///
+/// Besides the kind of the error, it records the position in the input where the problem was found.
+/// ```
+/// use size_format::{ByteSize, ParseErrorKind};
+///
+/// let error = "1.5GBx".parse::<ByteSize>().unwrap_err();
+/// assert_eq!(error.kind(), ParseErrorKind::TrailingCharacters);
+/// assert_eq!(error.position(), 5);
+/// assert_eq!(error.to_string(), "unexpected characters at position 5");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ParseSizeError {
+    /// What went wrong.
+    kind: ParseErrorKind,
+    /// The byte offset in the input where the problem was found.
+    position: usize,
+}
+
+/// Describes why a size could not be parsed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ParseErrorKind {
+    /// The number is missing or malformed.
+    InvalidNumber,
+    /// The text after the number is not a known prefix or unit.
+    UnknownPrefix,
+    /// The size does not fit into the result type.
+    Overflow,
+    /// A valid size is followed by further characters.
+    TrailingCharacters,
+    /// A range has no separator or its start exceeds its end.
+    InvalidRange,
+}
+
+impl ParseSizeError {
+    /// Creates a new parse error.
+    pub(crate) fn new(kind: ParseErrorKind, position: usize) -> ParseSizeError {
+        ParseSizeError { kind, position }
+    }
+
+    /// Returns why the size could not be parsed.
+    pub fn kind(&self) -> ParseErrorKind {
+        self.kind
+    }
+
+    /// Returns the byte offset in the input where the problem was found.
+    ///
+    /// For overflows and invalid ranges, this is the start of the offending size or range.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Moves the position by `offset`, for errors found in a part of the input.
+    pub(crate) fn shifted(mut self, offset: usize) -> ParseSizeError {
+        self.position += offset;
+        self
+    }
+
+    /// Returns the more helpful of two errors for the same input.
+    ///
+    /// An overflow means the input was understood, otherwise the error found later wins.
+    fn most_specific(self, other: ParseSizeError) -> ParseSizeError {
+        match (self.kind, other.kind) {
+            (ParseErrorKind::Overflow, _) => self,
+            (_, ParseErrorKind::Overflow) => other,
+            _ if other.position > self.position => other,
+            _ => self,
+        }
+    }
+}
+
+impl Display for ParseSizeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            ParseErrorKind::InvalidNumber => {
+                write!(f, "invalid number at position {}", self.position)
+            }
+            ParseErrorKind::UnknownPrefix => {
+                write!(f, "unknown prefix at position {}", self.position)
+            }
+            ParseErrorKind::Overflow => {
+                write!(f, "size at position {} is too large", self.position)
+            }
+            ParseErrorKind::TrailingCharacters => {
+                write!(f, "unexpected characters at position {}", self.position)
+            }
+            ParseErrorKind::InvalidRange => {
+                write!(f, "invalid range at position {}", self.position)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseSizeError {}
+
+/// Parses a non-empty string of ASCII digits, failing on overflow.
+pub(crate) fn parse_digits(digits: &str) -> Result<u64, ParseSizeError> {
+    if digits.is_empty() {
+        return Err(ParseSizeError::new(ParseErrorKind::InvalidNumber, 0));
+    }
+
+    digits
+        .bytes()
+        .enumerate()
+        .try_fold(0u64, |value, (index, byte)| match byte {
+            b'0'..=b'9' => value
+                .checked_mul(10)
+                .and_then(|value| value.checked_add(u64::from(byte - b'0')))
+                .ok_or_else(|| ParseSizeError::new(ParseErrorKind::Overflow, 0)),
+            _ => Err(ParseSizeError::new(ParseErrorKind::InvalidNumber, index)),
+        })
+}
+
+/// Parses a byte size such as "1.5GiB", "42MB", "8k" or "512".
+///
+/// The number may have a fractional part and is followed by an optional SI or binary prefix
+/// and an optional "B". Fractions of a byte are rounded down.
+pub(crate) fn parse_bytes(s: &str) -> Result<u64, ParseSizeError> {
+    SizeParser::new().parse(s).map(ByteSize::bytes)
+}
+
+/// Describes how strictly sizes are parsed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ParseMode {
+    /// Accepts only the exact notation produced by the formatters, such as "1.5GiB".
+    #[default]
+    Strict,
+    /// Additionally ignores surrounding whitespace, whitespace between the number and
+    /// the prefix and the case of the prefix and unit, such as " 1.5 gib ".
+    Lenient,
+    /// Accepts the notation of formatters that write symbols in the given case, such as "1.5GIB".
+    Cased(SymbolCase),
+}
+
+/// Describes how the ambiguous single letter prefixes such as "K" in "10K" or "10KB" are interpreted.
+///
+/// Binary prefixes such as "Ki" always refer to powers of 1024.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum PrefixPolicy {
+    /// Single letter prefixes refer to powers of 1000 and "K" is accepted for kilo.
+    Si,
+    /// Single letter prefixes refer to powers of 1024, as in "10K" meaning 10240 bytes.
+    Binary,
+    /// Only the IEC and SI symbols are accepted, so "10kB" is 10000 bytes and "10K" is rejected.
+    #[default]
+    IecStrict,
+}
+
+/// The single letter prefixes with an uppercase kilo, in powers of 1000.
+struct DecimalLetterPrefixes;
+
+impl PrefixType for DecimalLetterPrefixes {
+    const PREFIX_SIZE: u32 = 1000;
+
+    const PREFIXES: &'static [&'static str] = &["", "K", "M", "G", "T", "P", "E", "Z", "Y"];
+}
+
+/// The single letter prefixes with an uppercase kilo, in powers of 1024.
+struct BinaryLetterPrefixes;
+
+impl PrefixType for BinaryLetterPrefixes {
+    const PREFIX_SIZE: u32 = 1024;
+
+    const PREFIXES: &'static [&'static str] = &["", "K", "M", "G", "T", "P", "E", "Z", "Y"];
+}
+
+/// The SI prefix symbols, in powers of 1024.
+struct BinarySIPrefixes;
+
+impl PrefixType for BinarySIPrefixes {
+    const PREFIX_SIZE: u32 = 1024;
+
+    const PREFIXES: &'static [&'static str] = SIPrefixes::PREFIXES;
+}
+
+/// The units accepted for bits with the number of bits they denote, longest first.
+///
+/// The last entry tells whether the unit is matched ignoring case in lenient mode.
+/// Units that only differ from another one in the case of "b" never are.
+const BIT_UNITS: [(&str, u64, bool); 8] = [
+    ("bit/s", 1, true),
+    ("bits", 1, true),
+    ("B/s", 8, false),
+    ("bit", 1, true),
+    ("bps", 1, false),
+    ("Bps", 8, false),
+    ("b", 1, false),
+    ("B", 8, false),
+];
+
+/// A parser for a number followed by a prefix and the unit.
+type PrefixParser = fn(&str, &str, ParseMode) -> Result<u64, ParseSizeError>;
+
+/// A configurable parser for byte sizes with SI or binary prefixes.
+/// ```
+/// use size_format::{ByteSize, ParseMode, PrefixPolicy, SizeParser};
+///
+/// let lenient = SizeParser::new().with_mode(ParseMode::Lenient);
+///
+/// assert_eq!(lenient.parse("1.5 gib"), Ok(ByteSize(1_610_612_736)));
+/// assert_eq!(lenient.parse(" 42 MB "), Ok(ByteSize(42_000_000)));
+/// assert!(SizeParser::new().parse("1.5 gib").is_err());
+///
+/// let binary = SizeParser::new().with_policy(PrefixPolicy::Binary);
+///
+/// assert_eq!(binary.parse("10K"), Ok(ByteSize(10_240)));
+/// assert!(SizeParser::new().parse("10K").is_err());
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SizeParser {
+    /// How strictly sizes are parsed.
+    mode: ParseMode,
+    /// How single letter prefixes are interpreted.
+    policy: PrefixPolicy,
+}
+
+impl SizeParser {
+    /// Creates a new strict parser that only accepts IEC and SI symbols.
+    pub fn new() -> SizeParser {
+        SizeParser {
+            mode: ParseMode::Strict,
+            policy: PrefixPolicy::IecStrict,
+        }
+    }
+
+    /// Sets how strictly sizes are parsed.
+    pub fn with_mode(mut self, mode: ParseMode) -> SizeParser {
+        self.mode = mode;
+        self
+    }
+
+    /// Sets how single letter prefixes are interpreted.
+    pub fn with_policy(mut self, policy: PrefixPolicy) -> SizeParser {
+        self.policy = policy;
+        self
+    }
+
+    /// Returns how strictly sizes are parsed.
+    pub fn mode(&self) -> ParseMode {
+        self.mode
+    }
+
+    /// Returns how single letter prefixes are interpreted.
+    pub fn policy(&self) -> PrefixPolicy {
+        self.policy
+    }
+
+    /// Parses a byte size such as "1.5GiB", "42MB", "8k" or "512".
+    ///
+    /// The number may have a fractional part and is followed by an optional SI or binary prefix
+    /// and an optional "B". Fractions of a byte are rounded down.
+    ///
+    /// Spelled out units such as "2 megabytes" or "1 kibibyte" are accepted as well,
+    /// with optional whitespace before them.
+    /// ```
+    /// use size_format::{ByteSize, SizeParser};
+    ///
+    /// assert_eq!(SizeParser::new().parse("2 megabytes"), Ok(ByteSize(2_000_000)));
+    /// assert_eq!(SizeParser::new().parse("3 kibibytes"), Ok(ByteSize(3_072)));
+    /// ```
+    pub fn parse(&self, s: &str) -> Result<ByteSize, ParseSizeError> {
+        if let Some(result) = parse_unit_word(s, self.mode) {
+            return result.map(ByteSize);
+        }
+
+        self.parse_in_unit(s, "B").map(ByteSize)
+    }
+
+    /// Parses a byte size at the start of the string and returns it with the unconsumed rest.
+    ///
+    /// The longest valid size is parsed, so this can be used inside larger grammars.
+    /// Spelled out units are recognized if they end at a word boundary ("5 megabytes left").
+    /// ```
+    /// use size_format::{ByteSize, SizeParser};
+    ///
+    /// let (limit, rest) = SizeParser::new().parse_partial("4GiB;burst=1GiB").unwrap();
+    /// assert_eq!((limit, rest), (ByteSize(4 << 30), ";burst=1GiB"));
+    /// ```
+    pub fn parse_partial<'a>(&self, s: &'a str) -> Result<(ByteSize, &'a str), ParseSizeError> {
+        if let Some(end) = unit_word_end(s, self.mode) {
+            let (size, rest) = s.split_at(end);
+            return self.parse(size).map(|size| (size, rest));
+        }
+
+        let error = match self.parse(s) {
+            Ok(size) => return Ok((size, "")),
+            Err(error) => error,
+        };
+
+        match error.kind() {
+            ParseErrorKind::TrailingCharacters | ParseErrorKind::UnknownPrefix => {
+                let (size, rest) = s.split_at(error.position());
+                self.parse(size).map(|size| (size, rest))
+            }
+            _ => Err(error),
+        }
+    }
+
+    /// Parses a number of bits such as "100Mbit", "1Gbps", "8Kib" or "512".
+    ///
+    /// The units "b", "bit", "bits", "bps" and "bit/s" denote bits, while "B", "Bps" and "B/s"
+    /// denote bytes of eight bits. A number without a unit is a number of bits.
+    /// Fractions of a bit are rounded down.
+    ///
+    /// Since the case of "b" distinguishes bits from bytes, "b", "B", "bps", "Bps" and "B/s" are
+    /// case sensitive even in lenient mode.
+    /// ```
+    /// use size_format::{PrefixPolicy, SizeParser};
+    ///
+    /// let parser = SizeParser::new();
+    ///
+    /// assert_eq!(parser.parse_bits("100Mbit"), Ok(100_000_000));
+    /// assert_eq!(parser.parse_bits("1Gbps"), Ok(1_000_000_000));
+    /// assert_eq!(parser.parse_bits("1kB"), Ok(8_000));
+    /// assert_eq!(
+    ///     parser.with_policy(PrefixPolicy::Si).parse_bits("10Kb"),
+    ///     Ok(10_000)
+    /// );
+    /// ```
+    pub fn parse_bits(&self, s: &str) -> Result<u64, ParseSizeError> {
+        let s = match self.mode {
+            ParseMode::Strict | ParseMode::Cased(_) => s,
+            ParseMode::Lenient => s.trim_end(),
+        };
+        let offset = s.len() - s.trim_start().len();
+
+        for &(unit, bits, ignores_case) in &BIT_UNITS {
+            let matches = s.len() >= unit.len()
+                && s.is_char_boundary(s.len() - unit.len())
+                && match self.mode {
+                    ParseMode::Lenient if ignores_case => {
+                        s[s.len() - unit.len()..].eq_ignore_ascii_case(unit)
+                    }
+                    _ => s.ends_with(unit),
+                };
+
+            if matches {
+                return self
+                    .parse_in_unit(&s[..s.len() - unit.len()], "")?
+                    .checked_mul(bits)
+                    .ok_or(ParseSizeError::new(ParseErrorKind::Overflow, offset));
+            }
+        }
+
+        self.parse_in_unit(s, "")
+    }
+
+    /// Parses a number of bits like `parse_bits` and converts it to whole bytes.
+    ///
+    /// Bits that do not make up a whole byte are rounded down.
+    /// ```
+    /// use size_format::{ByteSize, SizeParser};
+    ///
+    /// assert_eq!(SizeParser::new().parse_bits_as_bytes("100Mbit"), Ok(ByteSize(12_500_000)));
+    /// assert_eq!(SizeParser::new().parse_bits_as_bytes("12b"), Ok(ByteSize(1)));
+    /// ```
+    pub fn parse_bits_as_bytes(&self, s: &str) -> Result<ByteSize, ParseSizeError> {
+        self.parse_bits(s).map(|bits| ByteSize(bits / 8))
+    }
+
+    /// Parses a number followed by a prefix and the unit, trying the prefixes of the policy.
+    fn parse_in_unit(&self, s: &str, unit: &str) -> Result<u64, ParseSizeError> {
+        let parsers: &[PrefixParser] = match self.policy {
+            PrefixPolicy::Si => &[
+                parse_with::<SIPrefixes>,
+                parse_with::<DecimalLetterPrefixes>,
+                parse_with::<BinaryPrefixes>,
+            ],
+            PrefixPolicy::Binary => &[
+                parse_with::<BinarySIPrefixes>,
+                parse_with::<BinaryLetterPrefixes>,
+                parse_with::<BinaryPrefixes>,
+            ],
+            PrefixPolicy::IecStrict => &[parse_with::<SIPrefixes>, parse_with::<BinaryPrefixes>],
+        };
+
+        let mut error: Option<ParseSizeError> = None;
+        for parser in parsers {
+            match parser(s, unit, self.mode) {
+                Ok(value) => return Ok(value),
+                Err(next) => {
+                    error = Some(error.map_or(next, |error| error.most_specific(next)));
+                }
+            }
+        }
+
+        // There is always at least one parser.
+        Err(error.unwrap_or(ParseSizeError::new(ParseErrorKind::UnknownPrefix, 0)))
+    }
+}
+
+/// Parses a byte size such as "1.5G", " 512 MiB" or "2 gigabytes" into a number of bytes.
+///
+/// This is a convenience for reading limits from environment variables and configuration values.
+/// It parses leniently, ignoring whitespace and the case of prefixes and units, and interprets
+/// single letter prefixes as SI prefixes. Use `SizeParser` for other settings.
+/// ```
+/// use size_format::parse_size;
+///
+/// assert_eq!(parse_size("1.5G"), Ok(1_500_000_000));
+/// assert_eq!(parse_size(" 512 MiB\n"), Ok(512 * 1024 * 1024));
+/// assert_eq!(parse_size("2 gigabytes"), Ok(2_000_000_000));
+/// assert!(parse_size("lots").is_err());
+/// ```
+pub fn parse_size(s: &str) -> Result<u64, ParseSizeError> {
+    SizeParser::new()
+        .with_mode(ParseMode::Lenient)
+        .with_policy(PrefixPolicy::Si)
+        .parse(s)
+        .map(ByteSize::bytes)
+}
+
+/// Parses a byte size at the start of the string and returns it with the unconsumed rest.
+///
+/// This uses the default `SizeParser`, see `SizeParser::parse_partial` for details.
+/// ```
+/// use size_format::{parse_partial, ByteSize};
+///
+/// assert_eq!(parse_partial("512MB, 1GB"), Ok((ByteSize(512_000_000), ", 1GB")));
+/// assert_eq!(parse_partial("8k"), Ok((ByteSize(8_000), "")));
+/// ```
+pub fn parse_partial(s: &str) -> Result<(ByteSize, &str), ParseSizeError> {
+    SizeParser::new().parse_partial(s)
+}
+
+/// The units suggested by `suggest_unit`.
+const SUGGESTED_UNITS: [&str; 15] = [
+    "B", "kB", "MB", "GB", "TB", "PB", "EB", "KiB", "MiB", "GiB", "TiB", "PiB", "EiB", "ZiB", "YiB",
+];
+
+/// Suggests the unit that was probably meant in a size with a unit in the wrong case.
+///
+/// This recognizes units such as "gb", "KB" or "mib" and prefixes without a unit such as "g".
+/// It is meant for hints in error messages and returns `None` for units that are already valid.
+/// ```
+/// use size_format::suggest_unit;
+///
+/// assert_eq!(suggest_unit("10gb"), Some("GB"));
+/// assert_eq!(suggest_unit("2 mib"), Some("MiB"));
+/// assert_eq!(suggest_unit("10GB"), None);
+/// assert_eq!(suggest_unit("10xyz"), None);
+/// ```
+pub fn suggest_unit(s: &str) -> Option<&'static str> {
+    let suffix = s
+        .trim()
+        .trim_start_matches(|c: char| c.is_ascii_digit() || c == '.')
+        .trim_start();
+
+    SUGGESTED_UNITS
+        .iter()
+        .find(|&&unit| {
+            let prefix = &unit[..unit.len() - 1];
+            (suffix.eq_ignore_ascii_case(unit) && suffix != unit)
+                || (!prefix.is_empty() && suffix.eq_ignore_ascii_case(prefix) && suffix != prefix)
+        })
+        .cloned()
+}
+
+/// Parses a number followed by a prefix of the prefix system and the unit.
+///
+/// The result is in the unit of the first prefix, so this is the inverse of formatting
+/// with `SizeFormatter`. The number may have a fractional part and fractions of the first
+/// prefix are rounded down. The unit may be omitted.
+/// ```
+/// extern crate size_format;
+///
+/// use size_format::{parse_prefixed, BinaryPrefixes, PrefixType};
+///
+/// struct Millimeter;
+///
+/// impl PrefixType for Millimeter {
+///     const PREFIX_SIZE: u32 = 1000;
+///
+///     const PREFIXES: &'static [&'static str] = &["m", "", "k"];
+/// }
+///
+/// # fn main() {
+/// assert_eq!(parse_prefixed::<Millimeter>("1.5km", "m"), Ok(1_500_000));
+/// assert_eq!(parse_prefixed::<Millimeter>("2m", "m"), Ok(2_000));
+/// assert_eq!(parse_prefixed::<Millimeter>("7mm", "m"), Ok(7));
+/// assert_eq!(parse_prefixed::<BinaryPrefixes>("1.5Ki", "B"), Ok(1_536));
+/// assert!(parse_prefixed::<BinaryPrefixes>("1.5kB", "B").is_err());
+/// # }
+/// ```
+pub fn parse_prefixed<Prefix: PrefixType>(s: &str, unit: &str) -> Result<u64, ParseSizeError> {
+    parse_with::<Prefix>(s, unit, ParseMode::Strict)
+}
+
+/// Parses a number followed by a prefix and the unit in the given mode.
+fn parse_with<Prefix: PrefixType>(
+    s: &str,
+    unit: &str,
+    mode: ParseMode,
+) -> Result<u64, ParseSizeError> {
+    parse_number::<u64, Prefix>(s, unit, mode, ".", "")
+}
+
+/// Parses a number with the given decimal separator, followed by a prefix and the unit.
+///
+/// The spacing may appear between the number and the prefix.
+pub(crate) fn parse_number<BaseType, Prefix>(
+    s: &str,
+    unit: &str,
+    mode: ParseMode,
+    separator: &str,
+    spacing: &str,
+) -> Result<BaseType, ParseSizeError>
+where
+    BaseType: Clone + Integer + CheckedMul + CheckedAdd + FromPrimitive,
+    Prefix: PrefixType,
+{
+    let (offset, s) = match mode {
+        ParseMode::Strict | ParseMode::Cased(_) => (0, s),
+        ParseMode::Lenient => (s.len() - s.trim_start().len(), s.trim()),
+    };
+
+    let mut number_end = 0;
+    loop {
+        let rest = &s[number_end..];
+        if rest.starts_with(|c: char| c.is_ascii_digit()) {
+            number_end += 1;
+        } else if !separator.is_empty() && rest.starts_with(separator) {
+            number_end += separator.len();
+        } else {
+            break;
+        }
+    }
+    let (number, suffix) = s.split_at(number_end);
+    let suffix_start = match mode {
+        ParseMode::Strict | ParseMode::Cased(_)
+            if !spacing.is_empty() && suffix.starts_with(spacing) =>
+        {
+            number_end + spacing.len()
+        }
+        ParseMode::Strict | ParseMode::Cased(_) => number_end,
+        ParseMode::Lenient => s.len() - suffix.trim_start().len(),
+    };
+    let suffix = &s[suffix_start..];
+
+    let (integer, fraction) = split_number(number, separator, offset)?;
+
+    // The unit is removed first, so units that look like prefixes are still recognized.
+    let multiplier = match suffix_multiplier::<BaseType, Prefix>(suffix, unit, mode) {
+        Some(Some(multiplier)) => multiplier,
+        // The prefix is too large for the type, which only zero can be scaled to.
+        Some(None) if integer.bytes().chain(fraction.bytes()).all(|b| b == b'0') => {
+            return Ok(BaseType::zero());
+        }
+        Some(None) => return Err(ParseSizeError::new(ParseErrorKind::Overflow, offset)),
+        None => {
+            let recognized = (1..suffix.len())
+                .rev()
+                .filter(|&len| suffix.is_char_boundary(len))
+                .find(|&len| {
+                    suffix_multiplier::<BaseType, Prefix>(&suffix[..len], unit, mode).is_some()
+                });
+
+            return Err(match recognized {
+                Some(len) => ParseSizeError::new(
+                    ParseErrorKind::TrailingCharacters,
+                    offset + suffix_start + len,
+                ),
+                None => ParseSizeError::new(ParseErrorKind::UnknownPrefix, offset + suffix_start),
+            });
+        }
+    };
+
+    scale_number(integer, fraction, multiplier, offset)
+}
+
+/// Splits a number starting at `offset` into its integer and fractional digits.
+fn split_number<'a>(
+    number: &'a str,
+    separator: &str,
+    offset: usize,
+) -> Result<(&'a str, &'a str), ParseSizeError> {
+    let split = if separator.is_empty() {
+        None
+    } else {
+        number.find(separator)
+    };
+    let (integer, fraction) = match split {
+        Some(index) => (&number[..index], &number[index + separator.len()..]),
+        None => (number, ""),
+    };
+    if let Some(index) = fraction.find(separator) {
+        return Err(ParseSizeError::new(
+            ParseErrorKind::InvalidNumber,
+            offset + integer.len() + separator.len() + index,
+        ));
+    }
+    if integer.is_empty() || (fraction.is_empty() && integer.len() < number.len()) {
+        return Err(ParseSizeError::new(
+            ParseErrorKind::InvalidNumber,
+            offset + if integer.is_empty() { 0 } else { number.len() },
+        ));
+    }
+
+    Ok((integer, fraction))
+}
+
+/// Returns the number with the given digits multiplied by the multiplier, rounded down.
+fn scale_number<BaseType>(
+    integer: &str,
+    fraction: &str,
+    multiplier: BaseType,
+    offset: usize,
+) -> Result<BaseType, ParseSizeError>
+where
+    BaseType: Clone + Integer + CheckedMul + CheckedAdd + FromPrimitive,
+{
+    let overflow = ParseSizeError::new(ParseErrorKind::Overflow, offset);
+    let ten = BaseType::from_u8(10).ok_or(overflow)?;
+
+    let integer = integer.bytes().try_fold(BaseType::zero(), |value, byte| {
+        value
+            .checked_mul(&ten)?
+            .checked_add(&BaseType::from_u8(byte - b'0')?)
+    });
+    let fraction = if fraction.is_empty() {
+        Some(BaseType::zero())
+    } else {
+        scale_fraction(fraction, multiplier.clone())
+    };
+
+    integer
+        .and_then(|integer| integer.checked_mul(&multiplier))
+        .and_then(|value| value.checked_add(&fraction?))
+        .ok_or(overflow)
+}
+
+/// The spelled out units with the size of their prefix and its exponent.
+///
+/// "byte" is last, since every other word ends with it.
+const UNIT_WORDS: [(&str, u64, u32); 17] = [
+    ("kilobyte", 1000, 1),
+    ("megabyte", 1000, 2),
+    ("gigabyte", 1000, 3),
+    ("terabyte", 1000, 4),
+    ("petabyte", 1000, 5),
+    ("exabyte", 1000, 6),
+    ("zettabyte", 1000, 7),
+    ("yottabyte", 1000, 8),
+    ("kibibyte", 1024, 1),
+    ("mebibyte", 1024, 2),
+    ("gibibyte", 1024, 3),
+    ("tebibyte", 1024, 4),
+    ("pebibyte", 1024, 5),
+    ("exbibyte", 1024, 6),
+    ("zebibyte", 1024, 7),
+    ("yobibyte", 1024, 8),
+    ("byte", 1000, 0),
+];
+
+/// Parses a number followed by a spelled out unit such as "2 megabytes".
+///
+/// Returns `None` if the string does not end with a spelled out unit after a number.
+fn parse_unit_word(s: &str, mode: ParseMode) -> Option<Result<u64, ParseSizeError>> {
+    let (offset, s) = match mode {
+        ParseMode::Strict | ParseMode::Cased(_) => (0, s),
+        ParseMode::Lenient => (s.len() - s.trim_start().len(), s.trim()),
+    };
+    let singular = match mode {
+        ParseMode::Strict | ParseMode::Cased(_) => s.strip_suffix('s'),
+        ParseMode::Lenient => s.strip_suffix(&['s', 'S'][..]),
+    }
+    .unwrap_or(s);
+
+    let (word_start, base, exponent) = UNIT_WORDS.iter().find_map(|&(word, base, exponent)| {
+        let start = singular.len().checked_sub(word.len())?;
+        let matches = singular.is_char_boundary(start)
+            && match mode {
+                ParseMode::Strict | ParseMode::Cased(_) => &singular[start..] == word,
+                ParseMode::Lenient => singular[start..].eq_ignore_ascii_case(word),
+            };
+        let number = s[..start].trim_end();
+
+        if matches && !number.is_empty() && number.bytes().all(|b| b.is_ascii_digit() || b == b'.')
+        {
+            Some((start, base, exponent))
+        } else {
+            None
+        }
+    })?;
+
+    Some(
+        split_number(s[..word_start].trim_end(), ".", offset).and_then(|(integer, fraction)| {
+            let multiplier = base
+                .checked_pow(exponent)
+                .ok_or(ParseSizeError::new(ParseErrorKind::Overflow, offset))?;
+            scale_number(integer, fraction, multiplier, offset)
+        }),
+    )
+}
+
+/// Returns the end of a number followed by a spelled out unit at the start of the string.
+///
+/// The unit has to end at a word boundary, so "5 megabytes left" ends after "megabytes".
+fn unit_word_end(s: &str, mode: ParseMode) -> Option<usize> {
+    let number_start = match mode {
+        ParseMode::Strict | ParseMode::Cased(_) => 0,
+        ParseMode::Lenient => s.len() - s.trim_start().len(),
+    };
+    let number_end = s[number_start..]
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .map_or(s.len(), |index| number_start + index);
+    if number_end == number_start {
+        return None;
+    }
+    let word_start = s.len() - s[number_end..].trim_start().len();
+    let rest = &s[word_start..];
+
+    UNIT_WORDS.iter().find_map(|&(word, _, _)| {
+        let matches = rest.len() >= word.len()
+            && rest.is_char_boundary(word.len())
+            && match mode {
+                ParseMode::Strict | ParseMode::Cased(_) => rest.starts_with(word),
+                ParseMode::Lenient => rest[..word.len()].eq_ignore_ascii_case(word),
+            };
+        if !matches {
+            return None;
+        }
+
+        let mut end = word.len();
+        match (mode, rest[end..].chars().next()) {
+            (_, Some('s')) | (ParseMode::Lenient, Some('S')) => end += 1,
+            _ => {}
+        }
+        match rest[end..].chars().next() {
+            Some(c) if c.is_alphanumeric() => None,
+            _ => Some(word_start + end),
+        }
+    })
+}
+
+/// Returns the multiplier of a suffix consisting of a prefix and an optional unit.
+///
+/// The multiplier is `None` if the suffix is valid, but its multiplier does not fit into the type.
+fn suffix_multiplier<BaseType, Prefix>(
+    suffix: &str,
+    unit: &str,
+    mode: ParseMode,
+) -> Option<Option<BaseType>>
+where
+    BaseType: Clone + Integer + CheckedMul + FromPrimitive,
+    Prefix: PrefixType,
+{
+    if let ParseMode::Cased(case) = mode {
+        return cased_suffix_multiplier::<BaseType, Prefix>(suffix, unit, case);
+    }
+
+    strip_unit(suffix, unit, mode)
+        .and_then(|prefix| prefix_multiplier::<BaseType, Prefix>(prefix, mode))
+        .or_else(|| prefix_multiplier::<BaseType, Prefix>(suffix, mode))
+}
+
+/// Returns the multiplier of a suffix consisting of a prefix and an optional unit in the given case.
+///
+/// The case applies to the prefix and the unit together, so they are matched as a whole.
+fn cased_suffix_multiplier<BaseType, Prefix>(
+    suffix: &str,
+    unit: &str,
+    case: SymbolCase,
+) -> Option<Option<BaseType>>
+where
+    BaseType: Clone + Integer + CheckedMul + FromPrimitive,
+    Prefix: PrefixType,
+{
+    // Without prefixes, only plain numbers are valid.
+    if Prefix::PREFIXES.is_empty() {
+        return if suffix.is_empty() || case.matches(&[unit], suffix) {
+            Some(Some(BaseType::one()))
+        } else {
+            None
+        };
+    }
+
+    let index = Prefix::PREFIXES.iter().position(|&candidate| {
+        case.matches(&[candidate, unit], suffix) || case.matches(&[candidate], suffix)
+    })?;
+
+    Some(bytes_in::<Prefix, BaseType>(index))
+}
+
+/// Parses a number followed by a prefix and the unit at the start of the string.
+///
+/// Returns the value together with the unconsumed rest of the string.
+pub(crate) fn parse_partial_with<'a, Prefix: PrefixType>(
+    s: &'a str,
+    unit: &str,
+    mode: ParseMode,
+) -> Result<(u64, &'a str), ParseSizeError> {
+    let error = match parse_with::<Prefix>(s, unit, mode) {
+        Ok(value) => return Ok((value, "")),
+        Err(error) => error,
+    };
+
+    match error.kind() {
+        ParseErrorKind::TrailingCharacters | ParseErrorKind::UnknownPrefix => {
+            let (value, rest) = s.split_at(error.position());
+            parse_with::<Prefix>(value, unit, mode).map(|value| (value, rest))
+        }
+        _ => Err(error),
+    }
+}
+
+/// Returns the suffix without the unit at its end, if it ends with the unit.
+fn strip_unit<'a>(suffix: &'a str, unit: &str, mode: ParseMode) -> Option<&'a str> {
+    match mode {
+        ParseMode::Strict | ParseMode::Cased(_) => suffix.strip_suffix(unit),
+        ParseMode::Lenient => {
+            let index = suffix.len().checked_sub(unit.len())?;
+            if suffix.is_char_boundary(index) && suffix[index..].eq_ignore_ascii_case(unit) {
+                Some(&suffix[..index])
+            } else {
+                None
+            }
+        }
+    }
+}
+
+/// Returns the multiplier of the prefix in the prefix system, if it is part of it.
+///
+/// The multiplier is `None` if it does not fit into the type.
+fn prefix_multiplier<BaseType, Prefix>(prefix: &str, mode: ParseMode) -> Option<Option<BaseType>>
+where
+    BaseType: Clone + Integer + CheckedMul + FromPrimitive,
+    Prefix: PrefixType,
+{
+    // Without prefixes, only plain numbers are valid.
+    if Prefix::PREFIXES.is_empty() {
+        return if prefix.is_empty() {
+            Some(Some(BaseType::one()))
+        } else {
+            None
+        };
+    }
+
+    let index = Prefix::PREFIXES.iter().position(|&candidate| match mode {
+        ParseMode::Strict | ParseMode::Cased(_) => candidate == prefix,
+        ParseMode::Lenient => candidate.eq_ignore_ascii_case(prefix),
+    })?;
+
+    Some(bytes_in::<Prefix, BaseType>(index))
+}
+
+/// Returns the fractional digits multiplied by the multiplier, rounded down.
+///
+/// The digits are processed from the last one, dividing by ten in each step.
+/// Since rounding down in every step does not change the final result, this is exact.
+/// The multiplier is split into its tens and ones, so no intermediate value exceeds it.
+///
+/// The digits must all be ASCII digits.
+fn scale_fraction<BaseType>(digits: &str, multiplier: BaseType) -> Option<BaseType>
+where
+    BaseType: Clone + Integer + FromPrimitive,
+{
+    let ten = BaseType::from_u8(10)?;
+    let (tens, ones) = multiplier.div_rem(&ten);
+
+    digits
+        .bytes()
+        .rev()
+        .try_fold(BaseType::zero(), |scaled, byte| {
+            let digit = BaseType::from_u8(byte - b'0')?;
+            let (scaled_tens, scaled_ones) = scaled.div_rem(&ten);
+
+            Some(
+                digit.clone() * tens.clone()
+                    + scaled_tens
+                    + (scaled_ones + digit * ones.clone()) / ten.clone(),
+            )
+        })
+}
+
+/// Parses a byte size such as "256MiB" or "1.5GB" in a constant expression.
+///
+/// This accepts the same sizes as `ByteSize::from_str`, that is an optional fractional part
+/// followed by an optional SI or binary prefix and an optional "B". Fractions of a byte are
+/// rounded down. Like in Rust literals, underscores between digits are skipped ("1_000MB").
+///
+/// # Panics
+/// - Panics if the size is invalid or does not fit into a `u64`.
+///   In a constant, this is reported at compile time.
+/// ```
+/// use size_format::parse_size_const;
+///
+/// const CACHE_LIMIT: u64 = parse_size_const("256MiB");
+/// assert_eq!(CACHE_LIMIT, 256 * 1024 * 1024);
+/// ```
+/// ```compile_fail,E0080
+/// use size_format::parse_size_const;
+///
+/// const CACHE_LIMIT: u64 = parse_size_const("256MIB");
+/// ```
+pub const fn parse_size_const(s: &str) -> u64 {
+    let bytes = s.as_bytes();
+    let mut index = 0;
+
+    let mut integer = 0u64;
+    while index < bytes.len() && bytes[index].is_ascii_digit() {
+        integer = match integer.checked_mul(10) {
+            Some(value) => match value.checked_add((bytes[index] - b'0') as u64) {
+                Some(value) => value,
+                None => panic!("the size is too large"),
+            },
+            None => panic!("the size is too large"),
+        };
+        index += 1;
+        if is_digit_separator(bytes, index) {
+            index += 1;
+        }
+    }
+    if index == 0 {
+        panic!("the size must start with a digit");
+    }
+
+    let fraction_start = index + 1;
+    if index < bytes.len() && bytes[index] == b'.' {
+        index += 1;
+        while index < bytes.len() && bytes[index].is_ascii_digit() {
+            index += 1;
+            if is_digit_separator(bytes, index) {
+                index += 1;
+            }
+        }
+        if index == fraction_start {
+            panic!("the decimal point must be followed by a digit");
+        }
+    }
+    let fraction_end = index;
+
+    let mut exponent = 0;
+    let mut base = 1000u64;
+    if index < bytes.len() {
+        exponent = match bytes[index] {
+            b'k' | b'K' => 1,
+            b'M' => 2,
+            b'G' => 3,
+            b'T' => 4,
+            b'P' => 5,
+            b'E' => 6,
+            b'Z' => 7,
+            b'Y' => 8,
+            _ => 0,
+        };
+        if exponent > 0 {
+            let binary = index + 1 < bytes.len() && bytes[index + 1] == b'i';
+            // SI uses a lowercase kilo and binary prefixes use an uppercase one.
+            if (binary && bytes[index] == b'k') || (!binary && bytes[index] == b'K') {
+                panic!("unknown prefix");
+            }
+            if binary {
+                base = 1024;
+                index += 1;
+            }
+            index += 1;
+        }
+    }
+    if index < bytes.len() && bytes[index] == b'B' {
+        index += 1;
+    }
+    if index != bytes.len() {
+        panic!("unknown prefix or unit");
+    }
+
+    let multiplier = match base.checked_pow(exponent) {
+        Some(multiplier) => multiplier,
+        None => {
+            // Zero fits with any prefix, like in `parse_bytes`.
+            let mut digit = fraction_start;
+            while digit < fraction_end {
+                if bytes[digit] != b'0' && bytes[digit] != b'_' {
+                    panic!("the size is too large");
+                }
+                digit += 1;
+            }
+            if integer != 0 {
+                panic!("the size is too large");
+            }
+            return 0;
+        }
+    };
+
+    // The fraction is scaled from the last digit, like in `scale_fraction`.
+    let mut scaled = 0u128;
+    let mut digit = fraction_end;
+    while digit > fraction_start {
+        digit -= 1;
+        if bytes[digit] == b'_' {
+            continue;
+        }
+        scaled = (bytes[digit] - b'0') as u128 * multiplier as u128 + scaled / 10;
+    }
+
+    match integer.checked_mul(multiplier) {
+        Some(value) => match value.checked_add((scaled / 10) as u64) {
+            Some(value) => value,
+            None => panic!("the size is too large"),
+        },
+        None => panic!("the size is too large"),
+    }
+}
+
+/// Checks whether the byte at `index` is an underscore between two digits.
+const fn is_digit_separator(bytes: &[u8], index: usize) -> bool {
+    index > 0
+        && index + 1 < bytes.len()
+        && bytes[index] == b'_'
+        && bytes[index - 1].is_ascii_digit()
+        && bytes[index + 1].is_ascii_digit()
+}
+
+/// Parses a range of byte sizes such as "1GiB..10GiB" or "1G-10G".
+///
+/// Both bounds are inclusive. With "..", either bound may be omitted, in which case
+/// the range is open on that side. A range whose start exceeds its end is rejected.
+/// ```
+/// use size_format::{parse_range, ByteSize};
+///
+/// assert_eq!(
+///     parse_range("1GiB..10GiB"),
+///     Ok(ByteSize(1 << 30)..=ByteSize(10 << 30))
+/// );
+/// assert_eq!(
+///     parse_range("1G-10G"),
+///     Ok(ByteSize(1_000_000_000)..=ByteSize(10_000_000_000))
+/// );
+/// assert_eq!(parse_range("..10GiB"), Ok(ByteSize(0)..=ByteSize(10 << 30)));
+/// assert_eq!(parse_range("1kB.."), Ok(ByteSize(1_000)..=ByteSize(u64::MAX)));
+/// assert!(parse_range("10G-1G").is_err());
+/// ```
+pub fn parse_range(s: &str) -> Result<RangeInclusive<ByteSize>, ParseSizeError> {
+    let (start, end) = if let Some(index) = s.find("..") {
+        let end_start = if s[index + 2..].starts_with('=') {
+            index + 3
+        } else {
+            index + 2
+        };
+
+        (
+            parse_bound(&s[..index], 0)?,
+            parse_bound(&s[end_start..], u64::MAX).map_err(|error| error.shifted(end_start))?,
+        )
+    } else if let Some(index) = s.find('-') {
+        (
+            parse_bytes(&s[..index])?,
+            parse_bytes(&s[index + 1..]).map_err(|error| error.shifted(index + 1))?,
+        )
+    } else {
+        return Err(ParseSizeError::new(ParseErrorKind::InvalidRange, 0));
+    };
+
+    if start > end {
+        return Err(ParseSizeError::new(ParseErrorKind::InvalidRange, 0));
+    }
+
+    Ok(ByteSize(start)..=ByteSize(end))
+}
+
+/// Parses a bound of a range, using `open` if it is omitted.
+fn parse_bound(s: &str, open: u64) -> Result<u64, ParseSizeError> {
+    if s.is_empty() {
+        Ok(open)
+    } else {
+        parse_bytes(s)
+    }
+}
@@ -0,0 +1,148 @@
+//! This module contains size parsers in the style of parser combinator libraries.
+//!
+//! Each parser takes the input and returns the remaining input together with the parsed value,
+//! like the parsers of `nom` and `winnow`, so they can be composed into larger grammars.
+//! ```
+//! use size_format::{parser, SIPrefixes};
+//!
+//! let size = parser::size::<SIPrefixes>();
+//!
+//! let (rest, limit) = size("4GB;burst=1GB").unwrap();
+//! let (rest, burst) = size(rest.trim_start_matches(";burst=")).unwrap();
+//!
+//! assert_eq!((limit, burst, rest), (4_000_000_000, 1_000_000_000, ""));
+//! ```
+//!
+//! To be used inside of `nom` or `winnow` grammars, the parsers returned by `combinator` and
+//! `byte_size_combinator` implement the parser traits of these crates with the `nom` and `winnow`
+//! features. Errors are passed on through `FromExternalError`.
+
+use config::PrefixType;
+use core::marker::PhantomData;
+#[cfg(feature = "nom")]
+use nom;
+use parse::{parse_partial_with, ParseMode, ParseSizeError, SizeParser};
+#[cfg(feature = "winnow")]
+use winnow;
+use ByteSize;
+
+/// A parser returning the remaining input and the parsed value.
+pub type Parser<Output> = fn(&str) -> Result<(&str, Output), ParseSizeError>;
+
+/// Returns a parser for a size with a prefix of the prefix system and an optional "B".
+///
+/// The longest valid size at the start of the input is parsed.
+/// Fractions of a byte are rounded down.
+pub fn size<Prefix: PrefixType>() -> Parser<u64> {
+    parse_size::<Prefix>
+}
+
+/// Returns a parser for a byte size with either SI or binary prefixes.
+///
+/// This accepts the same sizes as the default `SizeParser`.
+/// ```
+/// use size_format::{parser, ByteSize};
+///
+/// assert_eq!(parser::byte_size()("1.5GiB)"), Ok((")", ByteSize(1_610_612_736))));
+/// ```
+pub fn byte_size() -> Parser<ByteSize> {
+    parse_byte_size
+}
+
+/// Parses a size with a prefix of the prefix system at the start of the input.
+fn parse_size<Prefix: PrefixType>(input: &str) -> Result<(&str, u64), ParseSizeError> {
+    parse_partial_with::<Prefix>(input, "B", ParseMode::Strict).map(|(value, rest)| (rest, value))
+}
+
+/// Parses a byte size at the start of the input.
+fn parse_byte_size(input: &str) -> Result<(&str, ByteSize), ParseSizeError> {
+    SizeParser::new()
+        .parse_partial(input)
+        .map(|(size, rest)| (rest, size))
+}
+
+/// A parser for a size with a prefix of the prefix system and an optional "B", created by `combinator`.
+///
+/// It implements the parser traits of `nom` and `winnow` with the respective features.
+pub struct SizeCombinator<Prefix>(PhantomData<Prefix>);
+
+impl<Prefix> Clone for SizeCombinator<Prefix> {
+    fn clone(&self) -> SizeCombinator<Prefix> {
+        *self
+    }
+}
+
+impl<Prefix> Copy for SizeCombinator<Prefix> {}
+
+/// Returns a parser for a size with a prefix of the prefix system, for use in `nom` or `winnow`.
+///
+/// It accepts the same sizes as the parser returned by `size`.
+/// ```ignore
+/// use nom::{bytes::complete::tag, sequence::separated_pair, IResult};
+/// use size_format::{parser, SIPrefixes};
+///
+/// let size = parser::combinator::<SIPrefixes>();
+/// let range: IResult<&str, (u64, u64)> = separated_pair(size, tag(".."), size)("1kB..2MB");
+///
+/// assert_eq!(range, Ok(("", (1_000, 2_000_000))));
+/// ```
+pub fn combinator<Prefix: PrefixType>() -> SizeCombinator<Prefix> {
+    SizeCombinator(PhantomData)
+}
+
+/// A parser for a byte size with either SI or binary prefixes, created by `byte_size_combinator`.
+///
+/// It implements the parser traits of `nom` and `winnow` with the respective features.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ByteSizeCombinator;
+
+/// Returns a parser for a byte size with either SI or binary prefixes, for use in `nom` or `winnow`.
+///
+/// It accepts the same sizes as the parser returned by `byte_size`.
+pub fn byte_size_combinator() -> ByteSizeCombinator {
+    ByteSizeCombinator
+}
+
+/// Returns the input starting at the position of the error.
+#[cfg(any(feature = "nom", feature = "winnow"))]
+fn error_input<'a>(input: &'a str, error: &ParseSizeError) -> &'a str {
+    input.get(error.position()..).unwrap_or(input)
+}
+
+/// Implements the parser traits of `nom` and `winnow` for a combinator, based on its parse function.
+macro_rules! impl_combinator_traits {
+    ($combinator:ty, $output:ty, $parse:expr, $($generics:tt)*) => {
+        #[cfg(feature = "nom")]
+        impl<'a, $($generics)* E> nom::Parser<&'a str, $output, E> for $combinator
+        where
+            E: nom::error::FromExternalError<&'a str, ParseSizeError>,
+        {
+            fn parse(&mut self, input: &'a str) -> nom::IResult<&'a str, $output, E> {
+                $parse(input).map_err(|error| {
+                    nom::Err::Error(E::from_external_error(
+                        error_input(input, &error),
+                        nom::error::ErrorKind::MapRes,
+                        error,
+                    ))
+                })
+            }
+        }
+
+        #[cfg(feature = "winnow")]
+        impl<'a, $($generics)* E> winnow::Parser<&'a str, $output, E> for $combinator
+        where
+            E: winnow::error::FromExternalError<&'a str, ParseSizeError>,
+        {
+            fn parse_next(&mut self, input: &mut &'a str) -> Result<$output, E> {
+                let (rest, value) =
+                    $parse(*input).map_err(|error| E::from_external_error(input, error))?;
+                *input = rest;
+
+                Ok(value)
+            }
+        }
+    };
+}
+
+impl_combinator_traits!(SizeCombinator<Prefix>, u64, parse_size::<Prefix>, Prefix: PrefixType,);
+impl_combinator_traits!(ByteSizeCombinator, ByteSize, parse_byte_size,);
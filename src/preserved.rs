@@ -0,0 +1,112 @@
+//! This module contains a size that remembers how it was written.
+
+use align;
+use alloc::string::{String, ToString};
+use core::{
+    fmt::{self, Display},
+    str::FromStr,
+};
+use parse::{parse_bytes, ParseSizeError};
+use {BinaryPrefixes, PrefixType};
+
+/// A byte size that keeps the string it was parsed from.
+///
+/// When a configuration is loaded, modified and written back, unchanged sizes are written
+/// exactly as they were read instead of being normalized. Changed sizes are written using
+/// the largest binary prefix that represents them exactly, so they parse back to the same value.
+/// ```
+/// use size_format::PreservedSize;
+///
+/// let mut size: PreservedSize = "1.5GiB".parse().unwrap();
+/// assert_eq!(size.bytes(), 1_610_612_736);
+/// assert_eq!(size.to_string(), "1.5GiB".to_string());
+///
+/// size.set_bytes(2 * 1024 * 1024 * 1024);
+/// assert_eq!(size.to_string(), "2GiB".to_string());
+/// ```
+#[derive(Clone, Debug)]
+pub struct PreservedSize {
+    /// The number of bytes.
+    bytes: u64,
+    /// The string the size was parsed from, as long as it was not changed.
+    original: Option<String>,
+}
+
+impl PreservedSize {
+    /// Creates a new size that was not parsed from a string.
+    pub fn new(bytes: u64) -> PreservedSize {
+        PreservedSize {
+            bytes,
+            original: None,
+        }
+    }
+
+    /// Returns the number of bytes.
+    pub fn bytes(&self) -> u64 {
+        self.bytes
+    }
+
+    /// Changes the number of bytes.
+    ///
+    /// The original string is only discarded if the number of bytes actually changes.
+    pub fn set_bytes(&mut self, bytes: u64) {
+        if bytes != self.bytes {
+            self.bytes = bytes;
+            self.original = None;
+        }
+    }
+
+    /// Returns the string the size was parsed from, if it is still unchanged.
+    pub fn original(&self) -> Option<&str> {
+        self.original.as_deref()
+    }
+
+    /// Writes the size using the largest binary prefix that represents it exactly.
+    fn write_normalized(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        let prefixes = BinaryPrefixes::PREFIXES;
+        let mut index = 0;
+        let mut value = self.bytes;
+
+        while value != 0 && value.is_multiple_of(1024) && index + 1 < prefixes.len() {
+            value /= 1024;
+            index += 1;
+        }
+
+        write!(f, "{}{}B", value, prefixes[index])
+    }
+}
+
+impl FromStr for PreservedSize {
+    type Err = ParseSizeError;
+
+    fn from_str(s: &str) -> Result<PreservedSize, ParseSizeError> {
+        Ok(PreservedSize {
+            bytes: parse_bytes(s)?,
+            original: Some(s.to_string()),
+        })
+    }
+}
+
+impl Display for PreservedSize {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        align::pad(f, fmt::Alignment::Right, |w| match self.original {
+            Some(ref original) => w.write_str(original),
+            None => self.write_normalized(w),
+        })
+    }
+}
+
+impl PartialEq for PreservedSize {
+    /// Sizes are equal if they have the same number of bytes, regardless of how they were written.
+    fn eq(&self, other: &PreservedSize) -> bool {
+        self.bytes == other.bytes
+    }
+}
+
+impl Eq for PreservedSize {}
+
+impl From<u64> for PreservedSize {
+    fn from(bytes: u64) -> PreservedSize {
+        PreservedSize::new(bytes)
+    }
+}
@@ -0,0 +1,875 @@
+//! This module contains formatters that mimic the output of well known tools.
+
+use align;
+use config::{DecimalSeparator, JEDECPrefixes, PrefixType, SIPrefixes};
+use core::{
+    convert::TryFrom,
+    fmt::{self, Display},
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+    str::FromStr,
+};
+use parse::{parse_digits, ParseErrorKind, ParseSizeError};
+
+/// The single letter prefixes shared by several tools, regardless of whether they scale by 1000
+/// or by 1024.
+const LETTER_PREFIXES: &[&str] = JEDECPrefixes::PREFIXES;
+
+/// Implements the common traits for a preset that is generic over its separator.
+///
+/// Deriving them would require the separator to implement them as well,
+/// although it is only a marker.
+macro_rules! impl_separator_preset_traits {
+    ($name:ident { $($field:ident),* }) => {
+        impl<Separator> Clone for $name<Separator> {
+            fn clone(&self) -> $name<Separator> {
+                *self
+            }
+        }
+
+        impl<Separator> Copy for $name<Separator> {}
+
+        impl<Separator> fmt::Debug for $name<Separator> {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.debug_struct(stringify!($name))
+                    $(.field(stringify!($field), &self.$field))*
+                    .finish()
+            }
+        }
+
+        impl<Separator> PartialEq for $name<Separator> {
+            fn eq(&self, other: &$name<Separator>) -> bool {
+                true $(&& self.$field == other.$field)*
+            }
+        }
+
+        impl<Separator> Eq for $name<Separator> {}
+
+        impl<Separator> Hash for $name<Separator> {
+            fn hash<H: Hasher>(&self, state: &mut H) {
+                $(self.$field.hash(state);)*
+            }
+        }
+    };
+}
+
+/// Returns the spacing between a number and its unit for a separator, which is at least a space.
+fn preset_spacing<Separator: DecimalSeparator>() -> &'static str {
+    if Separator::UNIT_SPACING.is_empty() {
+        " "
+    } else {
+        Separator::UNIT_SPACING
+    }
+}
+
+/// Formats a byte size the way the macOS Finder shows it ("1.43 MB").
+///
+/// Sizes use SI prefixes and are rounded to the nearest value, using no decimal places for
+/// kilobytes, one for megabytes and up to two above that. Trailing zeros are omitted.
+/// Sizes below a kilobyte are shown as a plain byte count, except for zero, which is shown
+/// as "Zero KB".
+///
+/// The decimal separator, the unit spacing and the byte unit are taken from the `Separator`.
+/// ```
+/// use size_format::{presets::Finder, LocaleFR, PointSeparated};
+///
+/// assert_eq!(format!("{}", Finder::<PointSeparated>::new(1_434_000)), "1.4 MB".to_string());
+/// assert_eq!(format!("{}", Finder::<PointSeparated>::new(2_000_000)), "2 MB".to_string());
+/// assert_eq!(format!("{}", Finder::<PointSeparated>::new(3_517_000_000)), "3.52 GB".to_string());
+/// assert_eq!(format!("{}", Finder::<PointSeparated>::new(14_499)), "14 KB".to_string());
+/// assert_eq!(format!("{}", Finder::<PointSeparated>::new(512)), "512 bytes".to_string());
+/// assert_eq!(format!("{}", Finder::<PointSeparated>::new(0)), "Zero KB".to_string());
+/// assert_eq!(format!("{}", Finder::<LocaleFR>::new(1_434_000)), "1,4\u{A0}Mo".to_string());
+/// ```
+pub struct Finder<Separator> {
+    /// The number of bytes to be formatted.
+    bytes: u64,
+    /// Whether zero is written as a number instead of "Zero KB".
+    numeric: bool,
+    /// The marker for the separator.
+    _marker: PhantomData<Separator>,
+}
+
+impl<Separator: DecimalSeparator> Finder<Separator> {
+    /// Creates a new Finder style formatter for the given number of bytes.
+    pub fn new(bytes: u64) -> Finder<Separator> {
+        Finder {
+            bytes,
+            numeric: false,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the number of bytes.
+    pub fn bytes(&self) -> u64 {
+        self.bytes
+    }
+
+    /// Writes zero as a plain byte count instead of "Zero KB".
+    ///
+    /// Since "Zero" is not translated, this is useful for locales other than English.
+    /// ```
+    /// use size_format::{presets::Finder, LocaleDE};
+    ///
+    /// assert_eq!(format!("{}", Finder::<LocaleDE>::new(0).numeric()), "0 Byte".to_string());
+    /// ```
+    pub fn numeric(mut self) -> Finder<Separator> {
+        self.numeric = true;
+        self
+    }
+
+    /// Writes the formatted size without any padding.
+    fn write_unpadded(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        let spacing = preset_spacing::<Separator>();
+
+        if self.bytes == 0 && !self.numeric {
+            return write!(
+                f,
+                "Zero{}{}{}",
+                spacing,
+                LETTER_PREFIXES[1],
+                Separator::BYTE_UNIT
+            );
+        }
+        if self.bytes < 1000 {
+            return write!(f, "{}{}{}", self.bytes, spacing, Separator::BYTE_NAME);
+        }
+
+        let mut index = 1;
+        while index + 1 < LETTER_PREFIXES.len()
+            && u128::from(self.bytes) >= 1000u128.pow(index as u32 + 1)
+        {
+            index += 1;
+        }
+
+        // Rounding may carry into the next prefix, so this is repeated at most once.
+        let (mut rounded, mut digits) = finder_round(self.bytes, index);
+        if rounded >= 1000 * 10u128.pow(digits) && index + 1 < LETTER_PREFIXES.len() {
+            index += 1;
+            let (next_rounded, next_digits) = finder_round(self.bytes, index);
+            rounded = next_rounded;
+            digits = next_digits;
+        }
+
+        let scale = 10u128.pow(digits);
+        let (integer, mut fraction) = (rounded / scale, rounded % scale);
+        while digits > 0 && fraction % 10 == 0 {
+            fraction /= 10;
+            digits -= 1;
+        }
+
+        write!(f, "{}", integer)?;
+        if digits > 0 {
+            write!(
+                f,
+                "{}{:0width$}",
+                Separator::SEPARATOR,
+                fraction,
+                width = digits as usize
+            )?;
+        }
+        write!(
+            f,
+            "{}{}{}",
+            spacing,
+            LETTER_PREFIXES[index],
+            Separator::BYTE_UNIT
+        )
+    }
+}
+
+/// Rounds the bytes to the precision Finder uses for the prefix at `index`.
+///
+/// Returns the rounded value in units of the last shown digit and the number of decimal places.
+fn finder_round(bytes: u64, index: usize) -> (u128, u32) {
+    let digits = match index {
+        1 => 0,
+        2 => 1,
+        _ => 2,
+    };
+    let divisor = 1000u128.pow(index as u32);
+
+    (
+        (u128::from(bytes) * 10u128.pow(digits) + divisor / 2) / divisor,
+        digits,
+    )
+}
+
+impl_separator_preset_traits!(Finder { bytes, numeric });
+
+impl<Separator: DecimalSeparator> Display for Finder<Separator> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        align::pad(f, fmt::Alignment::Right, |w| self.write_unpadded(w))
+    }
+}
+
+/// Formats a byte size the way Windows Explorer shows it ("1.30 KB", "22.9 MB").
+///
+/// Sizes use powers of 1024 labeled with SI style letters and show three significant digits,
+/// with further digits cut off. The next prefix is already used from 1000 on, so a number never
+/// has more than three digits. Sizes below 1000 bytes are shown as a plain byte count.
+///
+/// The decimal separator, the unit spacing and the byte unit are taken from the `Separator`.
+/// ```
+/// use size_format::{presets::Explorer, PointSeparated};
+///
+/// assert_eq!(format!("{}", Explorer::<PointSeparated>::new(1_340)), "1.30 KB".to_string());
+/// assert_eq!(format!("{}", Explorer::<PointSeparated>::new(23_506)), "22.9 KB".to_string());
+/// assert_eq!(format!("{}", Explorer::<PointSeparated>::new(2_400_016)), "2.28 MB".to_string());
+/// assert_eq!(format!("{}", Explorer::<PointSeparated>::new(1_000)), "0.97 KB".to_string());
+/// assert_eq!(format!("{}", Explorer::<PointSeparated>::new(532)), "532 bytes".to_string());
+/// ```
+pub struct Explorer<Separator> {
+    /// The number of bytes to be formatted.
+    bytes: u64,
+    /// The marker for the separator.
+    _marker: PhantomData<Separator>,
+}
+
+impl<Separator: DecimalSeparator> Explorer<Separator> {
+    /// Creates a new Explorer style formatter for the given number of bytes.
+    pub fn new(bytes: u64) -> Explorer<Separator> {
+        Explorer {
+            bytes,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the number of bytes.
+    pub fn bytes(&self) -> u64 {
+        self.bytes
+    }
+
+    /// Writes the formatted size without any padding.
+    fn write_unpadded(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        let spacing = preset_spacing::<Separator>();
+
+        if self.bytes < 1000 {
+            return write!(f, "{}{}{}", self.bytes, spacing, Separator::BYTE_NAME);
+        }
+
+        let bytes = u128::from(self.bytes);
+        let mut index = 1;
+        while index + 1 < LETTER_PREFIXES.len() && bytes >= 1000 << (10 * index) {
+            index += 1;
+        }
+
+        // The value in hundredths, cut down to three significant digits.
+        let hundredths = (bytes * 100) >> (10 * index);
+        let digits = match hundredths {
+            0..=999 => 2,
+            1_000..=9_999 => 1,
+            _ => 0,
+        };
+        let value = hundredths / 10u128.pow(2 - digits);
+        let scale = 10u128.pow(digits);
+
+        write!(f, "{}", value / scale)?;
+        if digits > 0 {
+            write!(
+                f,
+                "{}{:0width$}",
+                Separator::SEPARATOR,
+                value % scale,
+                width = digits as usize
+            )?;
+        }
+        write!(
+            f,
+            "{}{}{}",
+            spacing,
+            LETTER_PREFIXES[index],
+            Separator::BYTE_UNIT
+        )
+    }
+}
+
+impl_separator_preset_traits!(Explorer { bytes });
+
+impl<Separator: DecimalSeparator> Display for Explorer<Separator> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        align::pad(f, fmt::Alignment::Right, |w| self.write_unpadded(w))
+    }
+}
+
+/// The suffixes used in web server configurations, from largest to smallest.
+const SERVER_SUFFIXES: [(char, u64); 3] = [('g', 1 << 30), ('m', 1 << 20), ('k', 1 << 10)];
+
+/// Formats and parses sizes as used in nginx and Apache configuration files ("8k", "512m", "2g").
+///
+/// Suffixes are single letters with binary semantics and no unit letter.
+/// Formatting uses the largest suffix that represents the size exactly, so a formatted size
+/// always parses back to the same value. Parsing also accepts uppercase suffixes.
+/// ```
+/// use size_format::presets::Nginx;
+///
+/// assert_eq!(format!("{}", Nginx::new(8 * 1024)), "8k".to_string());
+/// assert_eq!(format!("{}", Nginx::new(1_536)), "1536".to_string());
+/// assert_eq!("512m".parse::<Nginx>().unwrap().bytes(), 512 * 1024 * 1024);
+/// assert_eq!("2G".parse::<Nginx>().unwrap().bytes(), 2 * 1024 * 1024 * 1024);
+/// assert!("1.5m".parse::<Nginx>().is_err());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Nginx {
+    /// The number of bytes.
+    bytes: u64,
+}
+
+impl Nginx {
+    /// Creates a new web server configuration size from the given number of bytes.
+    pub fn new(bytes: u64) -> Nginx {
+        Nginx { bytes }
+    }
+
+    /// Returns the number of bytes.
+    pub fn bytes(&self) -> u64 {
+        self.bytes
+    }
+
+    /// Writes the formatted size without any padding.
+    fn write_unpadded(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        let suffix = SERVER_SUFFIXES
+            .iter()
+            .find(|&&(_, size)| self.bytes != 0 && self.bytes.is_multiple_of(size));
+
+        match suffix {
+            Some(&(suffix, size)) => write!(f, "{}{}", self.bytes / size, suffix),
+            None => write!(f, "{}", self.bytes),
+        }
+    }
+}
+
+impl FromStr for Nginx {
+    type Err = ParseSizeError;
+
+    fn from_str(s: &str) -> Result<Nginx, ParseSizeError> {
+        let last = s.chars().next_back().map(|c| c.to_ascii_lowercase());
+        let suffix = SERVER_SUFFIXES
+            .iter()
+            .find(|&&(suffix, _)| Some(suffix) == last);
+
+        let bytes = match suffix {
+            Some(&(_, size)) => parse_digits(&s[..s.len() - 1])?
+                .checked_mul(size)
+                .ok_or_else(|| ParseSizeError::new(ParseErrorKind::Overflow, 0))?,
+            None => parse_digits(s)?,
+        };
+
+        Ok(Nginx::new(bytes))
+    }
+}
+
+impl Display for Nginx {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        align::pad(f, fmt::Alignment::Right, |w| self.write_unpadded(w))
+    }
+}
+
+/// Formats a byte size exactly like GNU `ls -lh` does ("4.0K", "12M").
+///
+/// Sizes use single letter binary prefixes without a unit and are always rounded up.
+/// One decimal place is shown if the number is below ten, otherwise none.
+/// Sizes below 1024 bytes are shown as a plain byte count.
+/// ```
+/// use size_format::presets::LsHuman;
+///
+/// assert_eq!(format!("{}", LsHuman::new(4_096)), "4.0K".to_string());
+/// assert_eq!(format!("{}", LsHuman::new(4_097)), "4.1K".to_string());
+/// assert_eq!(format!("{}", LsHuman::new(12_345_678)), "12M".to_string());
+/// assert_eq!(format!("{}", LsHuman::new(512)), "512".to_string());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LsHuman {
+    /// The number of bytes.
+    bytes: u64,
+}
+
+impl LsHuman {
+    /// Creates a new `ls -lh` style formatter for the given number of bytes.
+    pub fn new(bytes: u64) -> LsHuman {
+        LsHuman { bytes }
+    }
+
+    /// Returns the number of bytes.
+    pub fn bytes(&self) -> u64 {
+        self.bytes
+    }
+
+    /// Writes the formatted size without any padding.
+    fn write_unpadded(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        write_human_readable(f, self.bytes, 1024, LETTER_PREFIXES)
+    }
+}
+
+impl Display for LsHuman {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        align::pad(f, fmt::Alignment::Right, |w| self.write_unpadded(w))
+    }
+}
+
+/// Writes the bytes the way GNU coreutils does for human readable output.
+///
+/// The bytes are scaled by powers of `base` and always rounded up, showing one decimal place
+/// if the number is below ten.
+fn write_human_readable(
+    f: &mut dyn fmt::Write,
+    bytes: u64,
+    base: u128,
+    prefixes: &[&str],
+) -> fmt::Result {
+    let bytes = u128::from(bytes);
+    if bytes < base {
+        return write!(f, "{}", bytes);
+    }
+
+    let mut index = 1;
+    let mut divisor = base;
+    while index + 1 < prefixes.len() && bytes / divisor >= base {
+        divisor *= base;
+        index += 1;
+    }
+
+    if bytes < 10 * divisor {
+        let tenths = (bytes * 10).div_ceil(divisor);
+        if tenths < 100 {
+            return write!(f, "{}.{}{}", tenths / 10, tenths % 10, prefixes[index]);
+        }
+        return write!(f, "10{}", prefixes[index]);
+    }
+
+    let amount = bytes.div_ceil(divisor);
+    if amount == base && index + 1 < prefixes.len() {
+        // Rounding up reached the next prefix, which is then shown with a decimal place.
+        write!(f, "1.0{}", prefixes[index + 1])
+    } else {
+        write!(f, "{}{}", amount, prefixes[index])
+    }
+}
+
+/// How `Coreutils` scales the bytes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+enum CoreutilsScale {
+    /// The number of blocks of the given size, rounded up.
+    Blocks(u64),
+    /// Human readable output in powers of 1024, as with `-h`.
+    Human,
+    /// Human readable output in powers of 1000, as with `--si`.
+    Si,
+}
+
+/// Formats a byte size the way GNU `du` and `df` do.
+///
+/// By default, the size is written as the number of 1024 byte blocks, rounded up.
+/// The block size can be changed, similar to `--block-size`. `human` and `si` switch to the
+/// human readable output of `-h` and `--si`, which uses single letter prefixes without a unit.
+/// As in coreutils, the last of these choices wins.
+/// ```
+/// use size_format::presets::Coreutils;
+///
+/// assert_eq!(format!("{}", Coreutils::new(5_000)), "5".to_string());
+/// assert_eq!(format!("{}", Coreutils::new(5_000).with_block_size(1)), "5000".to_string());
+/// assert_eq!(format!("{}", Coreutils::new(5_000).human()), "4.9K".to_string());
+/// assert_eq!(format!("{}", Coreutils::new(5_000).si()), "5.0k".to_string());
+/// assert_eq!(format!("{}", Coreutils::new(12_345_678).si()), "13M".to_string());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Coreutils {
+    /// The number of bytes.
+    bytes: u64,
+    /// How the bytes are scaled.
+    scale: CoreutilsScale,
+}
+
+impl Coreutils {
+    /// Creates a new coreutils style formatter that counts 1024 byte blocks.
+    pub fn new(bytes: u64) -> Coreutils {
+        Coreutils {
+            bytes,
+            scale: CoreutilsScale::Blocks(1024),
+        }
+    }
+
+    /// Returns the number of bytes.
+    pub fn bytes(&self) -> u64 {
+        self.bytes
+    }
+
+    /// Counts blocks of `block_size` bytes, like `--block-size`.
+    ///
+    /// # Panics
+    /// - Panics if the block size is zero.
+    pub fn with_block_size(mut self, block_size: u64) -> Coreutils {
+        assert!(block_size > 0, "block size must not be zero");
+        self.scale = CoreutilsScale::Blocks(block_size);
+        self
+    }
+
+    /// Uses human readable output in powers of 1024, like `-h`.
+    pub fn human(mut self) -> Coreutils {
+        self.scale = CoreutilsScale::Human;
+        self
+    }
+
+    /// Uses human readable output in powers of 1000, like `--si`.
+    pub fn si(mut self) -> Coreutils {
+        self.scale = CoreutilsScale::Si;
+        self
+    }
+
+    /// Writes the formatted size without any padding.
+    fn write_unpadded(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        match self.scale {
+            CoreutilsScale::Blocks(block_size) => {
+                write!(f, "{}", self.bytes.div_ceil(block_size))
+            }
+            CoreutilsScale::Human => write_human_readable(f, self.bytes, 1024, LETTER_PREFIXES),
+            CoreutilsScale::Si => write_human_readable(f, self.bytes, 1000, SIPrefixes::PREFIXES),
+        }
+    }
+}
+
+impl Display for Coreutils {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        align::pad(f, fmt::Alignment::Right, |w| self.write_unpadded(w))
+    }
+}
+
+/// The number of nano units in one unit, which is the finest resolution of Kubernetes quantities.
+const KUBERNETES_NANOS: u128 = 1_000_000_000;
+
+/// The decimal suffixes of Kubernetes quantities with their powers of ten.
+const KUBERNETES_DECIMAL_SUFFIXES: [(&str, i32); 10] = [
+    ("n", -9),
+    ("u", -6),
+    ("m", -3),
+    ("", 0),
+    ("k", 3),
+    ("M", 6),
+    ("G", 9),
+    ("T", 12),
+    ("P", 15),
+    ("E", 18),
+];
+
+/// The binary suffixes of Kubernetes quantities, in units of 1024.
+const KUBERNETES_BINARY_SUFFIXES: [&str; 7] = ["", "Ki", "Mi", "Gi", "Ti", "Pi", "Ei"];
+
+/// The notation of a Kubernetes quantity, which is kept when it is formatted again.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum KubernetesFormat {
+    /// Binary suffixes, such as "512Mi".
+    Binary,
+    /// Decimal suffixes, such as "100m" or "2k".
+    Decimal,
+    /// Decimal exponents, such as "2e3".
+    Exponent,
+}
+
+/// Formats and parses Kubernetes resource quantities ("512Mi", "2Gi", "100m").
+///
+/// Quantities are non-negative and exact down to nano units, finer fractions are rounded up.
+/// As with `kubectl`, a quantity is written in its canonical form: it keeps the notation it was
+/// parsed or created with and uses the largest suffix that represents it with an integer.
+/// Binary quantities that are fractional or below 1024 are written with decimal suffixes.
+/// ```
+/// use size_format::presets::Kubernetes;
+///
+/// assert_eq!(format!("{}", Kubernetes::from_bytes(512 * 1024 * 1024)), "512Mi".to_string());
+/// assert_eq!(format!("{}", Kubernetes::from_millis(100)), "100m".to_string());
+/// assert_eq!(format!("{}", Kubernetes::new(2_000)), "2k".to_string());
+/// assert_eq!("1.5Gi".parse::<Kubernetes>().unwrap().to_string(), "1536Mi".to_string());
+/// assert_eq!("0.5".parse::<Kubernetes>().unwrap().to_string(), "500m".to_string());
+/// assert_eq!("2Gi".parse::<Kubernetes>().unwrap().value(), 2 * 1024 * 1024 * 1024);
+/// assert_eq!("250m".parse::<Kubernetes>().unwrap().millis(), 250);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Kubernetes {
+    /// The quantity in nano units.
+    nanos: u128,
+    /// The notation used to write the quantity.
+    format: KubernetesFormat,
+}
+
+impl Kubernetes {
+    /// Creates a new quantity of whole units, written with decimal suffixes.
+    pub fn new(value: u64) -> Kubernetes {
+        Kubernetes {
+            nanos: u128::from(value) * KUBERNETES_NANOS,
+            format: KubernetesFormat::Decimal,
+        }
+    }
+
+    /// Creates a new quantity of bytes, written with binary suffixes.
+    pub fn from_bytes(bytes: u64) -> Kubernetes {
+        Kubernetes {
+            nanos: u128::from(bytes) * KUBERNETES_NANOS,
+            format: KubernetesFormat::Binary,
+        }
+    }
+
+    /// Creates a new quantity of thousandths of a unit, such as CPU millicores.
+    pub fn from_millis(millis: u64) -> Kubernetes {
+        Kubernetes {
+            nanos: u128::from(millis) * (KUBERNETES_NANOS / 1000),
+            format: KubernetesFormat::Decimal,
+        }
+    }
+
+    /// Returns the quantity in whole units, rounded up.
+    pub fn value(&self) -> u128 {
+        self.nanos.div_ceil(KUBERNETES_NANOS)
+    }
+
+    /// Returns the quantity in thousandths of a unit, rounded up.
+    pub fn millis(&self) -> u128 {
+        self.nanos.div_ceil(KUBERNETES_NANOS / 1000)
+    }
+
+    /// Writes the formatted quantity without any padding.
+    fn write_unpadded(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        if self.format == KubernetesFormat::Binary
+            && self.nanos.is_multiple_of(KUBERNETES_NANOS)
+            && self.nanos >= 1024 * KUBERNETES_NANOS
+        {
+            let value = self.nanos / KUBERNETES_NANOS;
+            let mut index = 0;
+            while index + 1 < KUBERNETES_BINARY_SUFFIXES.len()
+                && value.trailing_zeros() >= 10 * (index as u32 + 1)
+            {
+                index += 1;
+            }
+            return write!(
+                f,
+                "{}{}",
+                value >> (10 * index),
+                KUBERNETES_BINARY_SUFFIXES[index]
+            );
+        }
+
+        if self.nanos == 0 {
+            return f.write_str("0");
+        }
+
+        let largest = KUBERNETES_DECIMAL_SUFFIXES[KUBERNETES_DECIMAL_SUFFIXES.len() - 1].1;
+        let (mut mantissa, mut exponent) = (self.nanos, -9);
+        while mantissa.is_multiple_of(1000)
+            && (exponent < largest || self.format == KubernetesFormat::Exponent)
+        {
+            mantissa /= 1000;
+            exponent += 3;
+        }
+
+        if self.format == KubernetesFormat::Exponent {
+            if exponent == 0 {
+                write!(f, "{}", mantissa)
+            } else {
+                write!(f, "{}e{}", mantissa, exponent)
+            }
+        } else {
+            let suffix = KUBERNETES_DECIMAL_SUFFIXES
+                .iter()
+                .find(|&&(_, power)| power == exponent)
+                .map_or("", |&(suffix, _)| suffix);
+            write!(f, "{}{}", mantissa, suffix)
+        }
+    }
+}
+
+/// Parses the suffix of a Kubernetes quantity.
+///
+/// Returns the notation, the power of ten and the power of 1024 that the suffix stands for.
+fn kubernetes_suffix(suffix: &str) -> Option<(KubernetesFormat, i64, u32)> {
+    if let Some(index) = KUBERNETES_BINARY_SUFFIXES
+        .iter()
+        .skip(1)
+        .position(|&binary| binary == suffix)
+    {
+        return Some((KubernetesFormat::Binary, 0, index as u32 + 1));
+    }
+    if let Some(&(_, power)) = KUBERNETES_DECIMAL_SUFFIXES
+        .iter()
+        .find(|&&(decimal, _)| decimal == suffix)
+    {
+        return Some((KubernetesFormat::Decimal, i64::from(power), 0));
+    }
+
+    let exponent = suffix.strip_prefix(['e', 'E'])?;
+    if !exponent.starts_with(|c: char| c.is_ascii_digit() || c == '+' || c == '-') {
+        return None;
+    }
+    exponent
+        .parse::<i32>()
+        .ok()
+        .map(|power| (KubernetesFormat::Exponent, i64::from(power), 0))
+}
+
+impl FromStr for Kubernetes {
+    type Err = ParseSizeError;
+
+    /// Parses a quantity as accepted by Kubernetes, such as "512Mi", "1.5G", "100m" or "1e3".
+    ///
+    /// Negative quantities are rejected.
+    fn from_str(s: &str) -> Result<Kubernetes, ParseSizeError> {
+        let body = s.strip_prefix('+').unwrap_or(s);
+        let offset = s.len() - body.len();
+        let number_end = body
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .unwrap_or(body.len());
+        let (number, suffix) = body.split_at(number_end);
+        let (integer, fraction) = match number.find('.') {
+            Some(point) => (&number[..point], &number[point + 1..]),
+            None => (number, ""),
+        };
+
+        if integer.is_empty() && fraction.is_empty() {
+            return Err(ParseSizeError::new(ParseErrorKind::InvalidNumber, offset));
+        }
+        if let Some(point) = fraction.find('.') {
+            return Err(ParseSizeError::new(
+                ParseErrorKind::InvalidNumber,
+                offset + integer.len() + 1 + point,
+            ));
+        }
+
+        let (format, power, binary_power) = kubernetes_suffix(suffix).ok_or_else(|| {
+            ParseSizeError::new(ParseErrorKind::UnknownPrefix, offset + number_end)
+        })?;
+
+        let overflow = || ParseSizeError::new(ParseErrorKind::Overflow, 0);
+        let digits = integer
+            .bytes()
+            .chain(fraction.bytes())
+            .try_fold(0u128, |value, digit| {
+                value
+                    .checked_mul(10)
+                    .and_then(|value| value.checked_add(u128::from(digit - b'0')))
+            })
+            .and_then(|digits| digits.checked_mul(1u128.checked_shl(10 * binary_power)?))
+            .ok_or_else(overflow)?;
+
+        let scale = power + 9 - fraction.len() as i64;
+        let nanos = if scale >= 0 {
+            u32::try_from(scale)
+                .ok()
+                .and_then(|scale| 10u128.checked_pow(scale))
+                .and_then(|factor| digits.checked_mul(factor))
+                .ok_or_else(overflow)?
+        } else {
+            match u32::try_from(-scale)
+                .ok()
+                .and_then(|scale| 10u128.checked_pow(scale))
+            {
+                Some(divisor) => digits.div_ceil(divisor),
+                // Anything below a nano unit is rounded up to it.
+                None => u128::from(digits > 0),
+            }
+        };
+
+        Ok(Kubernetes { nanos, format })
+    }
+}
+
+impl PartialEq for Kubernetes {
+    /// Compares the quantities, regardless of their notation.
+    fn eq(&self, other: &Kubernetes) -> bool {
+        self.nanos == other.nanos
+    }
+}
+
+impl Eq for Kubernetes {}
+
+impl Display for Kubernetes {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        align::pad(f, fmt::Alignment::Right, |w| self.write_unpadded(w))
+    }
+}
+
+/// The units of Grafana's "bytes(IEC)" unit, in steps of 1024.
+const GRAFANA_IEC_UNITS: [&str; 7] = ["B", "KiB", "MiB", "GiB", "TiB", "PiB", "EiB"];
+
+/// The units of Grafana's "bytes(SI)" unit, in steps of 1000.
+const GRAFANA_SI_UNITS: [&str; 7] = ["B", "kB", "MB", "GB", "TB", "PB", "EB"];
+
+/// Formats a byte size the way Grafana renders the "bytes(IEC)" and "bytes(SI)" units ("1.50 KiB").
+///
+/// Scaled sizes are rounded to the nearest value with three significant digits and are
+/// separated from the unit by a space. Sizes below the first step are shown as a plain byte count.
+/// ```
+/// use size_format::presets::Grafana;
+///
+/// assert_eq!(format!("{}", Grafana::iec(1_536)), "1.50 KiB".to_string());
+/// assert_eq!(format!("{}", Grafana::iec(15_360)), "15.0 KiB".to_string());
+/// assert_eq!(format!("{}", Grafana::iec(157_286_400)), "150 MiB".to_string());
+/// assert_eq!(format!("{}", Grafana::si(1_234_567)), "1.23 MB".to_string());
+/// assert_eq!(format!("{}", Grafana::si(512)), "512 B".to_string());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Grafana {
+    /// The number of bytes.
+    bytes: u64,
+    /// Whether the SI units are used instead of the IEC units.
+    si: bool,
+}
+
+impl Grafana {
+    /// Creates a new formatter for Grafana's "bytes(IEC)" unit.
+    pub fn iec(bytes: u64) -> Grafana {
+        Grafana { bytes, si: false }
+    }
+
+    /// Creates a new formatter for Grafana's "bytes(SI)" unit.
+    pub fn si(bytes: u64) -> Grafana {
+        Grafana { bytes, si: true }
+    }
+
+    /// Returns the number of bytes.
+    pub fn bytes(&self) -> u64 {
+        self.bytes
+    }
+
+    /// Writes the formatted size without any padding.
+    fn write_unpadded(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        let (base, units) = if self.si {
+            (1000, &GRAFANA_SI_UNITS)
+        } else {
+            (1024, &GRAFANA_IEC_UNITS)
+        };
+
+        let bytes = u128::from(self.bytes);
+        if bytes < base {
+            return write!(f, "{} {}", bytes, units[0]);
+        }
+
+        let mut index = 1;
+        let mut divisor = base;
+        while index + 1 < units.len() && bytes / divisor >= base {
+            divisor *= base;
+            index += 1;
+        }
+
+        let mut digits = match bytes / divisor {
+            0..=9 => 2,
+            10..=99 => 1,
+            _ => 0,
+        };
+        let mut rounded = (bytes * 10u128.pow(digits) + divisor / 2) / divisor;
+        // Rounding up may add a digit, which is then taken from the decimal places.
+        while digits > 0 && rounded >= 1000 {
+            digits -= 1;
+            rounded /= 10;
+        }
+
+        let scale = 10u128.pow(digits);
+        write!(f, "{}", rounded / scale)?;
+        if digits > 0 {
+            write!(f, ".{:0width$}", rounded % scale, width = digits as usize)?;
+        }
+        write!(f, " {}", units[index])
+    }
+}
+
+impl Display for Grafana {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        align::pad(f, fmt::Alignment::Right, |w| self.write_unpadded(w))
+    }
+}
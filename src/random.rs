@@ -0,0 +1,103 @@
+//! This module contains the integration with the `rand` crate.
+
+use rand::{
+    distributions::{
+        uniform::{SampleBorrow, SampleUniform, UniformInt, UniformSampler},
+        Distribution, Standard,
+    },
+    Rng,
+};
+use ByteSize;
+
+impl Distribution<ByteSize> for Standard {
+    /// Draws a size uniformly from all possible sizes.
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> ByteSize {
+        ByteSize(rng.gen())
+    }
+}
+
+/// Draws sizes uniformly from a range, which allows using `ByteSize` with `Uniform` and `gen_range`.
+/// ```
+/// extern crate rand;
+/// extern crate size_format;
+///
+/// use rand::{distributions::Uniform, rngs::mock::StepRng, Rng};
+/// use size_format::ByteSize;
+///
+/// let mut rng = StepRng::new(0, 0x9e37_79b9_7f4a_7c15);
+/// let size = rng.sample(Uniform::new(ByteSize(1 << 10), ByteSize(1 << 20)));
+///
+/// assert!(size >= ByteSize(1 << 10) && size < ByteSize(1 << 20));
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct UniformByteSize(UniformInt<u64>);
+
+impl UniformSampler for UniformByteSize {
+    type X = ByteSize;
+
+    fn new<B1, B2>(low: B1, high: B2) -> UniformByteSize
+    where
+        B1: SampleBorrow<ByteSize> + Sized,
+        B2: SampleBorrow<ByteSize> + Sized,
+    {
+        UniformByteSize(UniformInt::<u64>::new(low.borrow().0, high.borrow().0))
+    }
+
+    fn new_inclusive<B1, B2>(low: B1, high: B2) -> UniformByteSize
+    where
+        B1: SampleBorrow<ByteSize> + Sized,
+        B2: SampleBorrow<ByteSize> + Sized,
+    {
+        UniformByteSize(UniformInt::<u64>::new_inclusive(
+            low.borrow().0,
+            high.borrow().0,
+        ))
+    }
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> ByteSize {
+        ByteSize(self.0.sample(rng))
+    }
+}
+
+impl SampleUniform for ByteSize {
+    type Sampler = UniformByteSize;
+}
+
+/// Draws sizes up to a maximum, spreading them evenly across magnitudes.
+///
+/// See `ByteSize::log_uniform` for details.
+/// ```
+/// extern crate rand;
+/// extern crate size_format;
+///
+/// use rand::{rngs::mock::StepRng, Rng};
+/// use size_format::{ByteSize, LogUniform};
+///
+/// let mut rng = StepRng::new(0, 0x9e37_79b9_7f4a_7c15);
+/// let size = rng.sample(LogUniform::new(ByteSize(1 << 40)));
+///
+/// assert!(size <= ByteSize(1 << 40));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct LogUniform {
+    /// The largest size that is drawn.
+    max: ByteSize,
+}
+
+impl LogUniform {
+    /// Creates a new distribution of sizes up to and including `max`.
+    pub fn new(max: ByteSize) -> LogUniform {
+        LogUniform { max }
+    }
+
+    /// Returns the largest size that is drawn.
+    pub fn max(&self) -> ByteSize {
+        self.max
+    }
+}
+
+impl Distribution<ByteSize> for LogUniform {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> ByteSize {
+        ByteSize::log_uniform(self.max, || rng.next_u64())
+    }
+}
@@ -0,0 +1,301 @@
+//! This module contains formatters and trackers for transfer rates.
+
+use align;
+use core::{
+    fmt::{self, Display},
+    marker::PhantomData,
+    time::Duration,
+};
+use {
+    BinaryPrefixes, DecimalSeparator, ElapsedFormatter, PointSeparated, PrefixType, SIPrefixes,
+    SizeFormatter,
+};
+
+/// Implements `Display` to format the contained rate using SI prefixes.
+pub type RateFormatterSI = RateFormatter<SIPrefixes, PointSeparated>;
+
+/// Implements `Display` to format the contained rate using binary prefixes.
+pub type RateFormatterBinary = RateFormatter<BinaryPrefixes, PointSeparated>;
+
+/// Represents a rate in bytes per second that can be formatted.
+///
+/// Unlike `SizeFormatter`, this includes the unit.
+/// ```
+/// use size_format::RateFormatterSI;
+///
+/// assert_eq!(format!("{}", RateFormatterSI::new(87_300_000)), "87.3MB/s".to_string());
+/// ```
+pub struct RateFormatter<Prefix, Separator>
+where
+    Prefix: PrefixType,
+    Separator: DecimalSeparator,
+{
+    /// The number of bytes per second.
+    bytes_per_second: u64,
+    /// The period the rate is displayed for.
+    period: Duration,
+    _marker: PhantomData<(Prefix, Separator)>,
+}
+
+/// The periods that have a name of their own, in seconds.
+const NAMED_PERIODS: [(u64, &str); 6] = [
+    (1, "s"),
+    (60, "min"),
+    (3_600, "h"),
+    (86_400, "day"),
+    (604_800, "week"),
+    (2_592_000, "month"),
+];
+
+impl<Prefix, Separator> RateFormatter<Prefix, Separator>
+where
+    Prefix: PrefixType,
+    Separator: DecimalSeparator,
+{
+    /// Creates a new rate formatter for the given number of bytes per second.
+    pub fn new(bytes_per_second: u64) -> RateFormatter<Prefix, Separator> {
+        RateFormatter {
+            bytes_per_second,
+            period: Duration::from_secs(1),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Creates a new rate formatter for a number of bytes transferred over the given interval.
+    ///
+    /// An empty interval results in a rate of zero.
+    pub fn from_interval(bytes: u64, interval: Duration) -> RateFormatter<Prefix, Separator> {
+        let rate = (u128::from(bytes) * 1_000_000_000)
+            .checked_div(interval.as_nanos())
+            .unwrap_or(0);
+
+        RateFormatter::new(saturate(rate))
+    }
+
+    /// Creates a new rate formatter from two samples of a monotonically increasing byte counter.
+    ///
+    /// If the counter decreased between the samples, `reset` decides how that is interpreted.
+    /// ```
+    /// use size_format::{CounterReset, RateFormatterSI};
+    /// use std::time::Duration;
+    ///
+    /// let interval = Duration::from_secs(10);
+    /// assert_eq!(
+    ///     format!("{}", RateFormatterSI::from_counter(1_000, 501_000, interval, CounterReset::Restart)),
+    ///     "50.0kB/s".to_string()
+    /// );
+    /// assert_eq!(
+    ///     format!("{}", RateFormatterSI::from_counter(600_000, 500_000, interval, CounterReset::Restart)),
+    ///     "50.0kB/s".to_string()
+    /// );
+    /// assert_eq!(
+    ///     format!(
+    ///         "{}",
+    ///         RateFormatterSI::from_counter(
+    ///             4_294_767_295,
+    ///             300_000,
+    ///             interval,
+    ///             CounterReset::Wrap(u64::from(u32::MAX))
+    ///         )
+    ///     ),
+    ///     "50.0kB/s".to_string()
+    /// );
+    /// ```
+    pub fn from_counter(
+        previous: u64,
+        current: u64,
+        interval: Duration,
+        reset: CounterReset,
+    ) -> RateFormatter<Prefix, Separator> {
+        RateFormatter::from_interval(reset.delta(previous, current), interval)
+    }
+
+    /// Displays the rate as the number of bytes transferred per `period` instead of per second.
+    ///
+    /// Periods of a second, minute, hour, day, week and month (30 days) are shown by name,
+    /// others are shown as a duration.
+    /// ```
+    /// use size_format::RateFormatterSI;
+    /// use std::time::Duration;
+    ///
+    /// let rate = |period| RateFormatterSI::new(13_900_000).per(Duration::from_secs(period));
+    ///
+    /// assert_eq!(format!("{}", rate(86_400)), "1.2TB/day".to_string());
+    /// assert_eq!(format!("{}", rate(2_592_000)), "36.0TB/month".to_string());
+    /// assert_eq!(format!("{}", rate(10)), "139.0MB/10s".to_string());
+    /// ```
+    pub fn per(mut self, period: Duration) -> RateFormatter<Prefix, Separator> {
+        self.period = period;
+        self
+    }
+
+    /// Returns the number of bytes per second.
+    pub fn bytes_per_second(&self) -> u64 {
+        self.bytes_per_second
+    }
+
+    /// Writes the formatted rate without any padding.
+    pub(crate) fn write_unpadded(
+        &self,
+        f: &mut dyn fmt::Write,
+        precision: Option<usize>,
+    ) -> fmt::Result {
+        let bytes = (u128::from(self.bytes_per_second))
+            .checked_mul(self.period.as_nanos())
+            .map_or(u128::MAX, |bytes| bytes / 1_000_000_000);
+
+        SizeFormatter::<u128, Prefix, Separator>::new(bytes).write_unpadded(f, precision)?;
+
+        let name = NAMED_PERIODS
+            .iter()
+            .find(|&&(secs, _)| self.period == Duration::from_secs(secs));
+
+        match name {
+            Some(&(_, name)) => write!(f, "B/{}", name),
+            None => write!(f, "B/{}", ElapsedFormatter::new(self.period)),
+        }
+    }
+}
+
+/// Describes how a byte counter behaves when it decreases between two samples.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CounterReset {
+    /// The counter was restarted from zero, so its whole current value is new.
+    Restart,
+    /// The counter wrapped around after reaching the contained maximum value.
+    ///
+    /// For 32 bit counters, as used by SNMP, that would be `u32::MAX`.
+    Wrap(u64),
+}
+
+impl CounterReset {
+    /// Returns the number of bytes counted between the two samples.
+    pub fn delta(self, previous: u64, current: u64) -> u64 {
+        match current.checked_sub(previous) {
+            Some(delta) => delta,
+            None => match self {
+                CounterReset::Restart => current,
+                CounterReset::Wrap(max) => max
+                    .saturating_sub(previous)
+                    .saturating_add(current)
+                    .saturating_add(1),
+            },
+        }
+    }
+}
+
+/// Converts a rate to `u64`, saturating at the maximum.
+fn saturate(rate: u128) -> u64 {
+    if rate > u128::from(u64::MAX) {
+        u64::MAX
+    } else {
+        rate as u64
+    }
+}
+
+impl<Prefix, Separator> Display for RateFormatter<Prefix, Separator>
+where
+    Prefix: PrefixType,
+    Separator: DecimalSeparator,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let precision = f.precision();
+
+        align::pad(f, fmt::Alignment::Right, |w| {
+            self.write_unpadded(w, precision)
+        })
+    }
+}
+
+/// Tracks an exponentially weighted moving average of a transfer rate.
+///
+/// Samples consist of the total number of bytes transferred so far and the time at which
+/// that total was observed, measured from an arbitrary but fixed starting point.
+/// The weight of each sample grows with the time it covers, so that the average
+/// reacts to changes within roughly the configured time constant.
+/// ```
+/// use size_format::RateTracker;
+/// use std::time::Duration;
+///
+/// let mut tracker = RateTracker::new(Duration::from_secs(5));
+/// tracker.sample(0, Duration::from_secs(0));
+/// tracker.sample(100_000_000, Duration::from_secs(1));
+/// assert_eq!(format!("{}", tracker), "100.0MB/s".to_string());
+///
+/// // A short stall only slowly drags the average down.
+/// tracker.sample(100_000_000, Duration::from_secs(2));
+/// assert_eq!(format!("{}", tracker), "83.3MB/s".to_string());
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct RateTracker {
+    /// The time over which older samples lose most of their weight.
+    time_constant: Duration,
+    /// The previous sample as the total number of bytes and the time it was taken.
+    last: Option<(u64, Duration)>,
+    /// The current average in bytes per second, if a rate was observed yet.
+    average: Option<u128>,
+}
+
+impl RateTracker {
+    /// Creates a new rate tracker that smoothes the rate over the given time constant.
+    pub fn new(time_constant: Duration) -> RateTracker {
+        RateTracker {
+            time_constant,
+            last: None,
+            average: None,
+        }
+    }
+
+    /// Adds a sample of the total bytes transferred at the given time.
+    ///
+    /// Samples that are not newer than the previous one are ignored.
+    /// If the total decreases, the transfer is assumed to have restarted.
+    pub fn sample(&mut self, total: u64, at: Duration) {
+        let (last_total, last_at) = match self.last {
+            Some(last) => last,
+            None => {
+                self.last = Some((total, at));
+                return;
+            }
+        };
+
+        if at <= last_at {
+            return;
+        }
+        self.last = Some((total, at));
+
+        let elapsed = (at - last_at).as_nanos();
+        let transferred = u128::from(total.checked_sub(last_total).unwrap_or(total));
+        let current = transferred * 1_000_000_000 / elapsed;
+
+        // The new sample is weighted by `elapsed / (time_constant + elapsed)`.
+        let time_constant = self.time_constant.as_nanos();
+        self.average = Some(match self.average {
+            Some(average) => {
+                average.saturating_mul(time_constant) / (time_constant + elapsed)
+                    + current.saturating_mul(elapsed) / (time_constant + elapsed)
+            }
+            None => current,
+        });
+    }
+
+    /// Returns the smoothed rate in bytes per second.
+    pub fn bytes_per_second(&self) -> u64 {
+        saturate(self.average.unwrap_or(0))
+    }
+
+    /// Returns a formatter for the smoothed rate.
+    pub fn rate<Prefix, Separator>(&self) -> RateFormatter<Prefix, Separator>
+    where
+        Prefix: PrefixType,
+        Separator: DecimalSeparator,
+    {
+        RateFormatter::new(self.bytes_per_second())
+    }
+}
+
+impl Display for RateTracker {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Display::fmt(&self.rate::<SIPrefixes, PointSeparated>(), f)
+    }
+}
@@ -0,0 +1,120 @@
+//! This module contains the conversion of formatted sizes between prefix systems.
+
+use align;
+use config::PrefixType;
+use core::{
+    fmt::{self, Display},
+    marker::PhantomData,
+};
+use math::{bytes_in, round, step_size, Rounding};
+use parse::{parse_prefixed, ParseSizeError};
+
+/// The largest number of decimal places that is computed, since `10^19` still fits into a `u64`.
+const MAX_COMPUTED_PRECISION: usize = 19;
+
+/// Re-expresses a formatted byte size in another prefix system.
+///
+/// The string is parsed exactly in the `From` system and then rounded to the nearest value with
+/// `precision` decimal places in the `To` system, with ties rounded up. Parsing follows
+/// `parse_prefixed`, so fractions of a byte are rounded down.
+/// ```
+/// use size_format::{renormalize, BinaryPrefixes, SIPrefixes};
+///
+/// let binary = renormalize::<SIPrefixes, BinaryPrefixes>("1.5GB", 1).unwrap();
+/// assert_eq!(format!("{}", binary), "1.4GiB".to_string());
+/// assert_eq!(binary.bytes(), 1_500_000_000);
+///
+/// let si = renormalize::<BinaryPrefixes, SIPrefixes>("2TiB", 2).unwrap();
+/// assert_eq!(format!("{}", si), "2.20TB".to_string());
+/// ```
+pub fn renormalize<From: PrefixType, To: PrefixType>(
+    s: &str,
+    precision: usize,
+) -> Result<Renormalized<To>, ParseSizeError> {
+    Ok(Renormalized {
+        bytes: parse_prefixed::<From>(s, "B")?,
+        precision,
+        _marker: PhantomData,
+    })
+}
+
+/// A byte size that was converted to the `Prefix` system by `renormalize`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Renormalized<Prefix> {
+    /// The exact number of bytes that was parsed.
+    bytes: u64,
+    /// The number of decimal places shown.
+    precision: usize,
+    /// The marker for the prefix system.
+    _marker: PhantomData<Prefix>,
+}
+
+impl<Prefix: PrefixType> Renormalized<Prefix> {
+    /// Returns the exact number of bytes that was parsed.
+    pub fn bytes(&self) -> u64 {
+        self.bytes
+    }
+
+    /// Returns the prefix index and the value in units of the last computed decimal place.
+    fn rounded(&self, computed: usize) -> (usize, u128) {
+        let prefix_count = Prefix::PREFIXES.len().max(1);
+        let scale = 10u128.pow(computed as u32);
+
+        let mut index = 0;
+        while index + 1 < prefix_count
+            && bytes_in::<Prefix, u64>(index + 1).is_some_and(|size| size <= self.bytes)
+        {
+            index += 1;
+        }
+
+        // Rounding may reach the next prefix, in which case it is used instead.
+        loop {
+            // Prefixes beyond the range of a `u64` are never chosen above.
+            let divisor = u128::from(bytes_in::<Prefix, u64>(index).unwrap_or(u64::MAX));
+            let value = u128::from(self.bytes) * scale;
+            let value = round(
+                value / divisor,
+                value % divisor,
+                divisor,
+                1,
+                Rounding::HalfUp,
+            );
+
+            let next_threshold = u128::from(step_size::<Prefix>(index)) * scale;
+            if value >= next_threshold
+                && index + 1 < prefix_count
+                && bytes_in::<Prefix, u64>(index + 1).is_some()
+            {
+                index += 1;
+            } else {
+                return (index, value);
+            }
+        }
+    }
+
+    /// Writes the converted size without any padding.
+    fn write_unpadded(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        let computed = self.precision.min(MAX_COMPUTED_PRECISION);
+        let (index, value) = self.rounded(computed);
+        let scale = 10u128.pow(computed as u32);
+
+        write!(f, "{}", value / scale)?;
+        if self.precision > 0 {
+            write!(f, ".{:0width$}", value % scale, width = computed)?;
+            for _ in computed..self.precision {
+                f.write_char('0')?;
+            }
+        }
+
+        if let Some(prefix) = Prefix::PREFIXES.get(index) {
+            f.write_str(prefix)?;
+        }
+        f.write_char('B')
+    }
+}
+
+impl<Prefix: PrefixType> Display for Renormalized<Prefix> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        align::pad(f, fmt::Alignment::Right, |w| self.write_unpadded(w))
+    }
+}
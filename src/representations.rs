@@ -0,0 +1,56 @@
+//! This module contains the iterator over all representations of a number.
+
+use core::{iter::FusedIterator, marker::PhantomData};
+use math::step_size;
+use PrefixType;
+
+/// Iterates over a number expressed in every prefix of a prefix system.
+///
+/// This is created by `SizeFormatter::representations` and yields the scaled value together
+/// with the prefix, from the smallest to the largest prefix.
+pub struct Representations<Prefix: PrefixType> {
+    /// The number to be represented.
+    num: f64,
+    /// The size of one unit of the next prefix.
+    divisor: f64,
+    /// The index of the next prefix.
+    index: usize,
+    /// The marker for the prefix system.
+    _marker: PhantomData<Prefix>,
+}
+
+impl<Prefix: PrefixType> Representations<Prefix> {
+    /// Creates a new iterator over the representations of `num`.
+    pub(crate) fn new(num: f64) -> Representations<Prefix> {
+        Representations {
+            num,
+            divisor: 1.0,
+            index: 0,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<Prefix: PrefixType> Iterator for Representations<Prefix> {
+    type Item = (f64, &'static str);
+
+    fn next(&mut self) -> Option<(f64, &'static str)> {
+        let prefix = *Prefix::PREFIXES.get(self.index)?;
+        let value = self.num / self.divisor;
+
+        self.divisor *= f64::from(step_size::<Prefix>(self.index));
+        self.index += 1;
+
+        Some((value, prefix))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = Prefix::PREFIXES.len().saturating_sub(self.index);
+
+        (remaining, Some(remaining))
+    }
+}
+
+impl<Prefix: PrefixType> ExactSizeIterator for Representations<Prefix> {}
+
+impl<Prefix: PrefixType> FusedIterator for Representations<Prefix> {}
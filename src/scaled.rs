@@ -0,0 +1,24 @@
+//! This module contains the structured result of the prefix selection.
+
+/// A size split into its scaled value and unit, for rendering outside of this crate.
+///
+/// This is created by `SizeFormatter::scaled`, so the unit is always the one the formatter
+/// would display. All fields are plain values, which makes it easy to pass to frontends.
+/// ```
+/// use size_format::SizeFormatterBinary;
+///
+/// let scaled = SizeFormatterBinary::new(1_536).scaled();
+///
+/// assert_eq!(scaled.value, 1.5);
+/// assert_eq!(scaled.unit, "Ki");
+/// assert_eq!(scaled.exact_bytes, 1_536);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ScaledSize {
+    /// The number divided by the size of the chosen prefix.
+    pub value: f64,
+    /// The symbol of the chosen prefix.
+    pub unit: &'static str,
+    /// The exact, unscaled number.
+    pub exact_bytes: u128,
+}
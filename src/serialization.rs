@@ -0,0 +1,51 @@
+//! This module contains the integration with the `serde` crate.
+
+use core::fmt;
+use serde::{
+    de::{self, Deserialize, Deserializer, Unexpected, Visitor},
+    ser::{Serialize, Serializer},
+};
+use ByteSize;
+
+impl Serialize for ByteSize {
+    /// Serializes the size as its number of bytes, which is lossless in every format.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(self.0)
+    }
+}
+
+/// Accepts either a size string or a number of bytes.
+struct ByteSizeVisitor;
+
+impl<'de> Visitor<'de> for ByteSizeVisitor {
+    type Value = ByteSize;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a size such as \"512MiB\" or a number of bytes")
+    }
+
+    fn visit_u64<E: de::Error>(self, bytes: u64) -> Result<ByteSize, E> {
+        Ok(ByteSize(bytes))
+    }
+
+    fn visit_i64<E: de::Error>(self, bytes: i64) -> Result<ByteSize, E> {
+        if bytes < 0 {
+            return Err(E::invalid_value(Unexpected::Signed(bytes), &self));
+        }
+
+        Ok(ByteSize(bytes as u64))
+    }
+
+    fn visit_str<E: de::Error>(self, s: &str) -> Result<ByteSize, E> {
+        s.parse().map_err(E::custom)
+    }
+}
+
+impl<'de> Deserialize<'de> for ByteSize {
+    /// Deserializes a size from a string such as "512MiB" or from a number of bytes.
+    ///
+    /// Strings are parsed like `ByteSize::from_str`.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<ByteSize, D::Error> {
+        deserializer.deserialize_any(ByteSizeVisitor)
+    }
+}
@@ -0,0 +1,240 @@
+//! This module contains the `ByteSize` value type.
+
+use align;
+use core::{
+    convert::TryFrom,
+    fmt::{self, Display},
+    str::FromStr,
+};
+use math::{checked_pow, int_log};
+use parse::{parse_bytes, ParseSizeError};
+use {AnnotatedSize, ExactSize, SizeFormatterBinary, SizeFormatterSI, SortableSize};
+
+/// Represents a number of bytes.
+///
+/// It is displayed using binary prefixes and includes the unit.
+/// ```
+/// use size_format::ByteSize;
+///
+/// assert_eq!(format!("{}", ByteSize(1_536)), "1.5KiB".to_string());
+/// assert_eq!(format!("{}B", ByteSize(1_500).si()), "1.5kB".to_string());
+/// ```
+///
+/// The type is guaranteed to have the same layout as `u64`, so it can be stored in
+/// shared memory or memory mapped files.
+/// The `bytemuck` and `zerocopy` features implement the traits of these crates for such uses.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "bytemuck", derive(::bytemuck::Pod, ::bytemuck::Zeroable))]
+#[cfg_attr(
+    feature = "zerocopy",
+    derive(::zerocopy::FromZeroes, ::zerocopy::FromBytes, ::zerocopy::AsBytes)
+)]
+#[repr(transparent)]
+pub struct ByteSize(pub u64);
+
+impl ByteSize {
+    /// Returns the number of bytes.
+    pub fn bytes(self) -> u64 {
+        self.0
+    }
+
+    /// Returns the size as bytes in native byte order.
+    pub fn to_ne_bytes(self) -> [u8; 8] {
+        self.0.to_ne_bytes()
+    }
+
+    /// Creates a size from bytes in native byte order.
+    ///
+    /// Every bit pattern is a valid size.
+    /// ```
+    /// use size_format::ByteSize;
+    ///
+    /// let size = ByteSize(1 << 30);
+    /// assert_eq!(ByteSize::from_ne_bytes(size.to_ne_bytes()), size);
+    /// ```
+    pub fn from_ne_bytes(bytes: [u8; 8]) -> ByteSize {
+        ByteSize(u64::from_ne_bytes(bytes))
+    }
+
+    /// Rounds the size down to the given number of significant decimal digits.
+    ///
+    /// With zero significant digits, the size becomes zero.
+    /// ```
+    /// use size_format::ByteSize;
+    ///
+    /// assert_eq!(ByteSize(1_234_567).round_to_sigfigs(2), ByteSize(1_200_000));
+    /// assert_eq!(ByteSize(987).round_to_sigfigs(1), ByteSize(900));
+    /// ```
+    pub fn round_to_sigfigs(self, digits: u32) -> ByteSize {
+        self.blur(Granularity::SignificantFigures(digits))
+    }
+
+    /// Coarsens the size deterministically, so it can be reported without revealing the exact value.
+    ///
+    /// Sizes are always rounded down to the bucket that contains them.
+    /// ```
+    /// use size_format::{ByteSize, Granularity};
+    ///
+    /// assert_eq!(ByteSize(5_000).blur(Granularity::PowerOfTwo), ByteSize(4_096));
+    /// assert_eq!(ByteSize(5_000).blur(Granularity::Multiple(1_024)), ByteSize(4_096));
+    /// assert_eq!(ByteSize(5_000).blur(Granularity::SignificantFigures(1)), ByteSize(5_000));
+    /// ```
+    pub fn blur(self, granularity: Granularity) -> ByteSize {
+        let bytes = self.0;
+
+        match granularity {
+            Granularity::SignificantFigures(0) => ByteSize(0),
+            Granularity::SignificantFigures(digits) => {
+                let total_digits = int_log(bytes, 10) as u32 + 1;
+                match total_digits
+                    .checked_sub(digits)
+                    .and_then(|dropped| checked_pow(10u64, dropped))
+                {
+                    Some(factor) => ByteSize(bytes - bytes % factor),
+                    None => self,
+                }
+            }
+            Granularity::PowerOfTwo if bytes == 0 => self,
+            Granularity::PowerOfTwo => ByteSize(1 << (63 - bytes.leading_zeros())),
+            Granularity::Multiple(0) => self,
+            Granularity::Multiple(step) => ByteSize(bytes - bytes % step),
+        }
+    }
+
+    /// Draws a random size up to `max`, spreading the samples evenly across magnitudes.
+    ///
+    /// Uniformly drawn sizes almost always have the largest possible magnitude. Instead, the
+    /// number of significant bits is chosen uniformly here, so small and large sizes are
+    /// equally likely. `random` must return uniformly distributed numbers.
+    /// With the `rand` feature, the `LogUniform` distribution can be used instead.
+    /// ```
+    /// use size_format::ByteSize;
+    ///
+    /// let mut state = 0x2545_f491_4f6c_dd1du64;
+    /// let mut xorshift = || {
+    ///     state ^= state << 13;
+    ///     state ^= state >> 7;
+    ///     state ^= state << 17;
+    ///     state
+    /// };
+    ///
+    /// let size = ByteSize::log_uniform(ByteSize(1 << 40), &mut xorshift);
+    /// assert!(size <= ByteSize(1 << 40));
+    /// ```
+    pub fn log_uniform<Random: FnMut() -> u64>(max: ByteSize, mut random: Random) -> ByteSize {
+        let lengths = u64::from(64 - max.0.leading_zeros()) + 1;
+        let mask = lengths.next_power_of_two() - 1;
+
+        loop {
+            // Rejecting the masked lengths that are not possible keeps the others exactly uniform.
+            let length = random() & mask;
+            if length >= lengths {
+                continue;
+            }
+            let value = match length as u32 {
+                0 => 0,
+                length => {
+                    let lowest = 1u64 << (length - 1);
+                    lowest | (random() & (lowest - 1))
+                }
+            };
+
+            // Only values with the bit length of the maximum can exceed it, so this ends quickly.
+            if value <= max.0 {
+                return ByteSize(value);
+            }
+        }
+    }
+
+    /// Returns the order preserving string encoding of the size.
+    pub fn sortable(self) -> SortableSize {
+        SortableSize::new(self)
+    }
+
+    /// Returns the lossless string representation of the size.
+    pub fn exact(self) -> ExactSize {
+        ExactSize::new(self)
+    }
+
+    /// Returns the size followed by its exact number of bytes.
+    pub fn annotated(self) -> AnnotatedSize {
+        AnnotatedSize::new(self)
+    }
+
+    /// Returns a formatter for the size using SI prefixes.
+    pub fn si(self) -> SizeFormatterSI {
+        SizeFormatterSI::new(self.0)
+    }
+
+    /// Returns a formatter for the size using binary prefixes.
+    pub fn binary(self) -> SizeFormatterBinary {
+        SizeFormatterBinary::new(self.0)
+    }
+}
+
+/// Describes how coarsely `ByteSize::blur` buckets sizes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Granularity {
+    /// Keeps the given number of significant decimal digits.
+    SignificantFigures(u32),
+    /// Uses the largest power of two that is not larger than the size.
+    PowerOfTwo,
+    /// Uses the largest multiple of the given step that is not larger than the size.
+    ///
+    /// A step of zero leaves sizes unchanged.
+    Multiple(u64),
+}
+
+impl From<u64> for ByteSize {
+    fn from(bytes: u64) -> ByteSize {
+        ByteSize(bytes)
+    }
+}
+
+impl From<ByteSize> for u64 {
+    fn from(size: ByteSize) -> u64 {
+        size.0
+    }
+}
+
+impl FromStr for ByteSize {
+    type Err = ParseSizeError;
+
+    /// Parses a size such as "1.5GiB", "42MB", "8k" or "512".
+    ///
+    /// Both SI and binary prefixes are accepted and the "B" is optional.
+    /// Fractions of a byte are rounded down. Use `parse_prefixed` to accept only one prefix system.
+    /// ```
+    /// use size_format::ByteSize;
+    ///
+    /// assert_eq!("1.5GiB".parse(), Ok(ByteSize(1_610_612_736)));
+    /// assert_eq!("42MB".parse(), Ok(ByteSize(42_000_000)));
+    /// assert!("42 MB".parse::<ByteSize>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<ByteSize, ParseSizeError> {
+        parse_bytes(s).map(ByteSize)
+    }
+}
+
+impl<'a> TryFrom<&'a str> for ByteSize {
+    type Error = ParseSizeError;
+
+    /// Parses a size like `ByteSize::from_str`.
+    ///
+    /// This allows using `ByteSize` with conversion based deserialization.
+    /// The `serde` feature implements deserialization directly, also accepting numbers of bytes.
+    fn try_from(s: &'a str) -> Result<ByteSize, ParseSizeError> {
+        s.parse()
+    }
+}
+
+impl Display for ByteSize {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let precision = f.precision();
+
+        align::pad(f, fmt::Alignment::Right, |w| {
+            self.binary().write_unpadded(w, precision)?;
+            w.write_char('B')
+        })
+    }
+}
@@ -0,0 +1,66 @@
+//! This module contains the order preserving string encoding of sizes.
+
+use core::{
+    fmt::{self, Display},
+    str::FromStr,
+};
+use parse::{parse_digits, ParseErrorKind, ParseSizeError};
+use ByteSize;
+
+/// Encodes a size as a fixed width string that sorts in the same order as the sizes.
+///
+/// This is meant for systems that can only sort lexicographically, such as key value stores.
+/// The encoding is the number of bytes padded with zeros to `WIDTH` digits.
+/// ```
+/// use size_format::{ByteSize, SortableSize};
+///
+/// let encoded = ByteSize(1_610_612_736).sortable().to_string();
+/// assert_eq!(encoded, "00000000001610612736".to_string());
+/// assert!(encoded < ByteSize(1 << 40).sortable().to_string());
+///
+/// let decoded: SortableSize = encoded.parse().unwrap();
+/// assert_eq!(decoded.size(), ByteSize(1_610_612_736));
+/// assert_eq!(format!("{}", decoded.size()), "1.5GiB".to_string());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SortableSize(ByteSize);
+
+impl SortableSize {
+    /// The number of characters of every encoded size.
+    pub const WIDTH: usize = 20;
+
+    /// Creates the encoding of the size.
+    pub(crate) fn new(size: ByteSize) -> SortableSize {
+        SortableSize(size)
+    }
+
+    /// Returns the encoded size.
+    pub fn size(self) -> ByteSize {
+        self.0
+    }
+}
+
+impl Display for SortableSize {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:01$}", self.0.bytes(), SortableSize::WIDTH)
+    }
+}
+
+impl FromStr for SortableSize {
+    type Err = ParseSizeError;
+
+    /// Decodes a size, which must consist of exactly `WIDTH` digits.
+    fn from_str(s: &str) -> Result<SortableSize, ParseSizeError> {
+        if s.len() < SortableSize::WIDTH {
+            return Err(ParseSizeError::new(ParseErrorKind::InvalidNumber, s.len()));
+        }
+        if s.len() > SortableSize::WIDTH {
+            return Err(ParseSizeError::new(
+                ParseErrorKind::TrailingCharacters,
+                SortableSize::WIDTH,
+            ));
+        }
+
+        parse_digits(s).map(|bytes| SortableSize(ByteSize(bytes)))
+    }
+}
@@ -0,0 +1,177 @@
+//! This module contains summary statistics for sets of sizes.
+
+use core::{
+    borrow::Borrow,
+    fmt::{self, Display},
+    marker::PhantomData,
+};
+use {BinaryPrefixes, DecimalSeparator, PointSeparated, PrefixType, SIPrefixes, SizeFormatter};
+
+/// Implements `Display` to format size statistics using SI prefixes.
+pub type SizeStatsSI = SizeStats<SIPrefixes, PointSeparated>;
+
+/// Implements `Display` to format size statistics using binary prefixes.
+pub type SizeStatsBinary = SizeStats<BinaryPrefixes, PointSeparated>;
+
+/// Summarizes a set of sizes by their minimum, median, mean and maximum.
+///
+/// All statistics are displayed with the prefix of the maximum, so they are easy to compare.
+/// The median of an even number of sizes and the mean are rounded down.
+/// The format options, like precision and width, apply to every statistic.
+/// ```
+/// use size_format::SizeStatsSI;
+///
+/// let sizes = [1_200_000, 4_000_000, 3_100_000, 12_000_000];
+/// let stats = SizeStatsSI::new(&sizes).unwrap().with_unit("B");
+///
+/// assert_eq!(
+///     format!("{}", stats),
+///     "min 1.2MB, median 3.5MB, mean 5.0MB, max 12.0MB".to_string()
+/// );
+/// ```
+pub struct SizeStats<Prefix, Separator>
+where
+    Prefix: PrefixType,
+    Separator: DecimalSeparator,
+{
+    /// The smallest size.
+    min: u64,
+    /// The middle size.
+    median: u64,
+    /// The average size.
+    mean: u64,
+    /// The largest size.
+    max: u64,
+    /// The number of sizes.
+    count: u64,
+    /// The unit written after every statistic.
+    unit: &'static str,
+    _marker: PhantomData<(Prefix, Separator)>,
+}
+
+impl<Prefix, Separator> SizeStats<Prefix, Separator>
+where
+    Prefix: PrefixType,
+    Separator: DecimalSeparator,
+{
+    /// Computes the statistics of the sizes, or returns `None` if there are none.
+    ///
+    /// The sizes are iterated several times, but never stored, so this works without allocating.
+    pub fn new<Sizes>(sizes: Sizes) -> Option<SizeStats<Prefix, Separator>>
+    where
+        Sizes: IntoIterator + Clone,
+        Sizes::Item: Borrow<u64>,
+    {
+        let values = || sizes.clone().into_iter().map(|size| *size.borrow());
+
+        let (mut min, mut max, mut count, mut sum) = (u64::MAX, 0, 0u64, 0u128);
+        for value in values() {
+            min = min.min(value);
+            max = max.max(value);
+            count += 1;
+            sum += u128::from(value);
+        }
+
+        if count == 0 {
+            return None;
+        }
+
+        let lower = nth_smallest(values, (count - 1) / 2, min, max);
+        let upper = nth_smallest(values, count / 2, lower, max);
+
+        Some(SizeStats {
+            min,
+            median: lower + (upper - lower) / 2,
+            // The mean lies between the minimum and the maximum, so it fits.
+            mean: (sum / u128::from(count)) as u64,
+            max,
+            count,
+            unit: "",
+            _marker: PhantomData,
+        })
+    }
+
+    /// Sets the unit that is written after every statistic.
+    pub fn with_unit(mut self, unit: &'static str) -> SizeStats<Prefix, Separator> {
+        self.unit = unit;
+        self
+    }
+
+    /// Returns the smallest size.
+    pub fn min(&self) -> u64 {
+        self.min
+    }
+
+    /// Returns the median of the sizes.
+    pub fn median(&self) -> u64 {
+        self.median
+    }
+
+    /// Returns the mean of the sizes.
+    pub fn mean(&self) -> u64 {
+        self.mean
+    }
+
+    /// Returns the largest size.
+    pub fn max(&self) -> u64 {
+        self.max
+    }
+
+    /// Returns the number of sizes.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+}
+
+/// Returns the `n`th smallest value, counting from zero, which is known to lie in `low..=high`.
+///
+/// This searches the range of values instead of sorting them, so it needs no storage.
+fn nth_smallest<F, I>(values: F, n: u64, mut low: u64, mut high: u64) -> u64
+where
+    F: Fn() -> I,
+    I: Iterator<Item = u64>,
+{
+    while low < high {
+        let mid = low + (high - low) / 2;
+
+        if values().filter(|&value| value <= mid).count() as u64 > n {
+            high = mid;
+        } else {
+            low = mid + 1;
+        }
+    }
+
+    low
+}
+
+impl<Prefix, Separator> Display for SizeStats<Prefix, Separator>
+where
+    Prefix: PrefixType,
+    Separator: DecimalSeparator,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let prefix = SizeFormatter::<u64, Prefix, Separator>::new(self.max)
+            .chosen_prefix()
+            .index();
+        let statistics = [
+            ("min", self.min),
+            ("median", self.median),
+            ("mean", self.mean),
+            ("max", self.max),
+        ];
+
+        for (i, &(name, value)) in statistics.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+
+            write!(f, "{} ", name)?;
+            let formatter =
+                SizeFormatter::<u64, Prefix, Separator>::new(value).with_forced_prefix(prefix);
+            Display::fmt(&formatter, f)?;
+            f.write_str(self.unit)?;
+        }
+
+        Ok(())
+    }
+}
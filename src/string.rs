@@ -0,0 +1,76 @@
+//! This module contains conversions of sizes to owned strings.
+
+use alloc::string::String;
+use core::fmt::Write;
+use {BinaryPrefixes, PointSeparated, SIPrefixes, SizeFormatter};
+
+/// Converts byte sizes to strings without constructing a formatter first.
+///
+/// The strings include the byte unit "B".
+/// ```
+/// use size_format::ToSizeString;
+///
+/// assert_eq!(42_000_000u64.to_si_string(), "42.0MB".to_string());
+/// assert_eq!(1_536u32.to_binary_string(), "1.5KiB".to_string());
+/// assert_eq!(1_999_999u64.to_si_string_with_precision(3), "1.999MB".to_string());
+/// ```
+pub trait ToSizeString {
+    /// Formats the size using SI prefixes.
+    fn to_si_string(&self) -> String;
+
+    /// Formats the size using binary prefixes.
+    fn to_binary_string(&self) -> String;
+
+    /// Formats the size using SI prefixes and the given precision.
+    fn to_si_string_with_precision(&self, precision: usize) -> String;
+
+    /// Formats the size using binary prefixes and the given precision.
+    fn to_binary_string_with_precision(&self, precision: usize) -> String;
+}
+
+/// Implements `ToSizeString` for the given types, formatting them as the second type.
+macro_rules! impl_to_size_string {
+    ($($ty:ty => $base:ty),*) => {
+        $(
+            impl ToSizeString for $ty {
+                fn to_si_string(&self) -> String {
+                    to_string(SizeFormatter::<$base, SIPrefixes, PointSeparated>::from(*self), None)
+                }
+
+                fn to_binary_string(&self) -> String {
+                    to_string(SizeFormatter::<$base, BinaryPrefixes, PointSeparated>::from(*self), None)
+                }
+
+                fn to_si_string_with_precision(&self, precision: usize) -> String {
+                    to_string(
+                        SizeFormatter::<$base, SIPrefixes, PointSeparated>::from(*self),
+                        Some(precision),
+                    )
+                }
+
+                fn to_binary_string_with_precision(&self, precision: usize) -> String {
+                    to_string(
+                        SizeFormatter::<$base, BinaryPrefixes, PointSeparated>::from(*self),
+                        Some(precision),
+                    )
+                }
+            }
+        )*
+    };
+}
+
+// `u8` cannot hold the prefix sizes, so it is formatted as `u16`.
+impl_to_size_string!(u8 => u16, u16 => u16, u32 => u32, u64 => u64, u128 => u128, usize => usize);
+
+/// Formats a size with its unit into a new string.
+fn to_string<T: core::fmt::Display>(size: T, precision: Option<usize>) -> String {
+    let mut string = String::new();
+
+    match precision {
+        Some(precision) => write!(string, "{:.*}B", precision, size),
+        None => write!(string, "{}B", size),
+    }
+    .expect("formatting a size into a string cannot fail");
+
+    string
+}
@@ -0,0 +1,100 @@
+//! This module contains formatters that combine sizes, durations and rates.
+
+use align;
+use core::{
+    fmt::{self, Display},
+    marker::PhantomData,
+    time::Duration,
+};
+use {
+    BinaryPrefixes, DecimalSeparator, ElapsedFormatter, PointSeparated, PrefixType, RateFormatter,
+    SIPrefixes, SizeFormatter,
+};
+
+/// Implements `Display` to summarize a transfer using SI prefixes.
+pub type TransferSummarySI = TransferSummary<SIPrefixes, PointSeparated>;
+
+/// Implements `Display` to summarize a transfer using binary prefixes.
+pub type TransferSummaryBinary = TransferSummary<BinaryPrefixes, PointSeparated>;
+
+/// Summarizes a finished transfer on one line ("1.4GiB in 2min 13s (10.8MiB/s)").
+///
+/// The precision applies to both the size and the rate.
+/// ```
+/// use size_format::TransferSummaryBinary;
+/// use std::time::Duration;
+///
+/// let summary = TransferSummaryBinary::new(1_510_000_000, Duration::from_secs(133));
+/// assert_eq!(
+///     format!("copied {}", summary),
+///     "copied 1.4GiB in 2min 13s (10.8MiB/s)".to_string()
+/// );
+/// assert_eq!(
+///     format!("copied {}", summary.with_files(12)),
+///     "copied 12 files, 1.4GiB in 2min 13s (10.8MiB/s)".to_string()
+/// );
+/// ```
+pub struct TransferSummary<Prefix, Separator>
+where
+    Prefix: PrefixType,
+    Separator: DecimalSeparator,
+{
+    /// The number of bytes transferred.
+    bytes: u64,
+    /// The time the transfer took.
+    elapsed: Duration,
+    /// The number of files transferred, if they should be mentioned.
+    files: Option<u64>,
+    _marker: PhantomData<(Prefix, Separator)>,
+}
+
+impl<Prefix, Separator> TransferSummary<Prefix, Separator>
+where
+    Prefix: PrefixType,
+    Separator: DecimalSeparator,
+{
+    /// Creates a new summary of a transfer of `bytes` that took `elapsed`.
+    pub fn new(bytes: u64, elapsed: Duration) -> TransferSummary<Prefix, Separator> {
+        TransferSummary {
+            bytes,
+            elapsed,
+            files: None,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Adds the number of transferred files to the summary.
+    pub fn with_files(mut self, files: u64) -> TransferSummary<Prefix, Separator> {
+        self.files = Some(files);
+        self
+    }
+
+    /// Writes the summary without any padding.
+    fn write_unpadded(&self, f: &mut dyn fmt::Write, precision: Option<usize>) -> fmt::Result {
+        match self.files {
+            Some(1) => f.write_str("1 file, ")?,
+            Some(files) => write!(f, "{} files, ", files)?,
+            None => (),
+        }
+
+        SizeFormatter::<u64, Prefix, Separator>::new(self.bytes).write_unpadded(f, precision)?;
+        write!(f, "B in {} (", ElapsedFormatter::new(self.elapsed))?;
+        RateFormatter::<Prefix, Separator>::from_interval(self.bytes, self.elapsed)
+            .write_unpadded(f, precision)?;
+        f.write_str(")")
+    }
+}
+
+impl<Prefix, Separator> Display for TransferSummary<Prefix, Separator>
+where
+    Prefix: PrefixType,
+    Separator: DecimalSeparator,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let precision = f.precision();
+
+        align::pad(f, fmt::Alignment::Left, |w| {
+            self.write_unpadded(w, precision)
+        })
+    }
+}
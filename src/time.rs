@@ -0,0 +1,57 @@
+//! This module contains formatters for durations.
+
+use align;
+use core::{
+    fmt::{self, Display},
+    time::Duration,
+};
+
+/// Formats an elapsed duration using its two most significant units ("2min 13s").
+///
+/// Durations below a second are shown in milliseconds. Values are always rounded down.
+/// ```
+/// use size_format::ElapsedFormatter;
+/// use std::time::Duration;
+///
+/// assert_eq!(format!("{}", ElapsedFormatter::new(Duration::from_millis(850))), "850ms".to_string());
+/// assert_eq!(format!("{}", ElapsedFormatter::new(Duration::from_secs(45))), "45s".to_string());
+/// assert_eq!(format!("{}", ElapsedFormatter::new(Duration::from_secs(133))), "2min 13s".to_string());
+/// assert_eq!(format!("{}", ElapsedFormatter::new(Duration::from_secs(3_900))), "1h 5min".to_string());
+/// assert_eq!(format!("{}", ElapsedFormatter::new(Duration::from_secs(97_200))), "1d 3h".to_string());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ElapsedFormatter {
+    /// The duration to be formatted.
+    duration: Duration,
+}
+
+impl ElapsedFormatter {
+    /// Creates a new formatter for the given duration.
+    pub fn new(duration: Duration) -> ElapsedFormatter {
+        ElapsedFormatter { duration }
+    }
+
+    /// Writes the formatted duration without any padding.
+    fn write_unpadded(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        let secs = self.duration.as_secs();
+        let (mins, hours, days) = (secs / 60, secs / 3_600, secs / 86_400);
+
+        if days > 0 {
+            write!(f, "{}d {}h", days, hours % 24)
+        } else if hours > 0 {
+            write!(f, "{}h {}min", hours, mins % 60)
+        } else if mins > 0 {
+            write!(f, "{}min {}s", mins, secs % 60)
+        } else if secs > 0 {
+            write!(f, "{}s", secs)
+        } else {
+            write!(f, "{}ms", self.duration.subsec_millis())
+        }
+    }
+}
+
+impl Display for ElapsedFormatter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        align::pad(f, fmt::Alignment::Right, |w| self.write_unpadded(w))
+    }
+}
@@ -0,0 +1,107 @@
+//! This module contains an allocator that keeps track of the allocated bytes.
+
+use core::{
+    alloc::{GlobalAlloc, Layout},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+use std::alloc::System;
+use ByteSize;
+
+/// Wraps an allocator and counts the bytes allocated through it.
+///
+/// It can be used as the global allocator, so programs can report their heap usage.
+/// ```
+/// use size_format::TrackingAllocator;
+/// use std::alloc::System;
+///
+/// #[global_allocator]
+/// static ALLOCATOR: TrackingAllocator = TrackingAllocator::new(System);
+///
+/// let buffer = vec![0u8; 4 << 20];
+/// assert!(ALLOCATOR.in_use().bytes() >= 4 << 20);
+/// println!("heap in use: {}", ALLOCATOR.in_use());
+/// # drop(buffer);
+/// ```
+pub struct TrackingAllocator<A = System> {
+    /// The allocator that performs the allocations.
+    inner: A,
+    /// The number of bytes that are currently allocated.
+    in_use: AtomicUsize,
+    /// The largest number of bytes that were allocated at the same time.
+    peak: AtomicUsize,
+    /// The number of bytes that were allocated in total, including freed ones.
+    total: AtomicUsize,
+}
+
+impl<A> TrackingAllocator<A> {
+    /// Creates a new tracking allocator wrapping `inner`.
+    pub const fn new(inner: A) -> TrackingAllocator<A> {
+        TrackingAllocator {
+            inner,
+            in_use: AtomicUsize::new(0),
+            peak: AtomicUsize::new(0),
+            total: AtomicUsize::new(0),
+        }
+    }
+
+    /// Returns the number of bytes that are currently allocated.
+    pub fn in_use(&self) -> ByteSize {
+        ByteSize(self.in_use.load(Ordering::Relaxed) as u64)
+    }
+
+    /// Returns the largest number of bytes that were allocated at the same time.
+    pub fn peak(&self) -> ByteSize {
+        ByteSize(self.peak.load(Ordering::Relaxed) as u64)
+    }
+
+    /// Returns the number of bytes that were allocated in total, including freed ones.
+    ///
+    /// This wraps around on overflow.
+    pub fn total(&self) -> ByteSize {
+        ByteSize(self.total.load(Ordering::Relaxed) as u64)
+    }
+
+    /// Records that `size` bytes were allocated.
+    fn allocated(&self, size: usize) {
+        let in_use = self.in_use.fetch_add(size, Ordering::Relaxed) + size;
+        self.peak.fetch_max(in_use, Ordering::Relaxed);
+        self.total.fetch_add(size, Ordering::Relaxed);
+    }
+
+    /// Records that `size` bytes were freed.
+    fn freed(&self, size: usize) {
+        self.in_use.fetch_sub(size, Ordering::Relaxed);
+    }
+}
+
+unsafe impl<A: GlobalAlloc> GlobalAlloc for TrackingAllocator<A> {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = self.inner.alloc(layout);
+        if !ptr.is_null() {
+            self.allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = self.inner.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            self.allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        self.inner.dealloc(ptr, layout);
+        self.freed(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = self.inner.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            self.freed(layout.size());
+            self.allocated(new_size);
+        }
+        new_ptr
+    }
+}
@@ -0,0 +1,82 @@
+//! Model-checking harnesses for the digit emission.
+//!
+//! These are only compiled by [Kani](https://github.com/model-checking/kani) and can be run
+//! with `cargo kani`.
+
+use super::*;
+
+/// A writer into a fixed buffer, so formatting can be checked without allocating.
+struct Buffer {
+    bytes: [u8; 32],
+    len: usize,
+}
+
+impl fmt::Write for Buffer {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self.len + s.len();
+        if end > self.bytes.len() {
+            return Err(fmt::Error);
+        }
+        self.bytes[self.len..end].copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+macro_rules! next_digit_harness {
+    ($name:ident, $narrow:ty, $wide:ty) => {
+        #[kani::proof]
+        fn $name() {
+            let rem: $narrow = kani::any();
+            let denom: $narrow = kani::any();
+            kani::assume(rem < denom);
+
+            let (digit, next_rem) = next_digit(rem, &denom);
+
+            assert!(digit < 10);
+            assert!(next_rem < denom);
+            assert_eq!(
+                digit as $wide * denom as $wide + next_rem as $wide,
+                rem as $wide * 10
+            );
+        }
+    };
+}
+
+next_digit_harness!(next_digit_u8, u8, u16);
+next_digit_harness!(next_digit_u16, u16, u32);
+next_digit_harness!(next_digit_u32, u32, u64);
+
+#[kani::proof]
+#[kani::unwind(12)]
+fn formatting_u16_is_bounded() {
+    let num: u16 = kani::any();
+    let precision: usize = kani::any();
+    kani::assume(precision <= 8);
+
+    let mut buffer = Buffer {
+        bytes: [0; 32],
+        len: 0,
+    };
+    let result = fmt::write(
+        &mut buffer,
+        format_args!(
+            "{:.*}",
+            precision,
+            SizeFormatter::<u16, SIPrefixes, PointSeparated>::new(num)
+        ),
+    );
+
+    // At most five integer digits, the separator, three fractional digits and the prefix.
+    assert!(result.is_ok());
+    assert!(buffer.len <= 10);
+
+    let output = &buffer.bytes[..buffer.len];
+    let digits = match output.iter().position(|&b| b == b'k') {
+        Some(end) => &output[..end],
+        None => output,
+    };
+    for &byte in digits {
+        assert!(byte.is_ascii_digit() || byte == b'.');
+    }
+}
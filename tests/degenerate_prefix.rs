@@ -0,0 +1,61 @@
+extern crate size_format;
+
+use size_format::{parse_prefixed, PointSeparated, PrefixType, SizeFormatter};
+
+struct NoPrefixes;
+
+impl PrefixType for NoPrefixes {
+    const PREFIX_SIZE: u32 = 1000;
+
+    const PREFIXES: &'static [&'static str] = &[];
+}
+
+struct SinglePrefix;
+
+impl PrefixType for SinglePrefix {
+    const PREFIX_SIZE: u32 = 1000;
+
+    const PREFIXES: &'static [&'static str] = &["k"];
+}
+
+#[test]
+fn no_prefixes_formats_plain_number() {
+    assert_eq!(
+        format!(
+            "{}B",
+            SizeFormatter::<u32, NoPrefixes, PointSeparated>::new(0)
+        ),
+        "0B".to_string()
+    );
+    assert_eq!(
+        format!(
+            "{:.3}B",
+            SizeFormatter::<u32, NoPrefixes, PointSeparated>::new(1_234_567)
+        ),
+        "1234567B".to_string()
+    );
+}
+
+#[test]
+fn single_prefix_never_scales() {
+    assert_eq!(
+        format!(
+            "{}B",
+            SizeFormatter::<u32, SinglePrefix, PointSeparated>::new(7)
+        ),
+        "7kB".to_string()
+    );
+    assert_eq!(
+        format!(
+            "{:.3}B",
+            SizeFormatter::<u32, SinglePrefix, PointSeparated>::new(1_234_567)
+        ),
+        "1234567kB".to_string()
+    );
+}
+
+#[test]
+fn no_prefixes_parses_plain_number() {
+    assert_eq!(parse_prefixed::<NoPrefixes>("1234", ""), Ok(1_234));
+    assert!(parse_prefixed::<NoPrefixes>("1234k", "").is_err());
+}
@@ -0,0 +1,167 @@
+//! Checks that the library does not use floating point when the `float` feature is disabled.
+//!
+//! On targets without a floating point unit, any floating point operation compiles to a call of
+//! a soft-float intrinsic. The library is built for such a target with and without the `float`
+//! feature, and a probe crate that instantiates its generic code is compiled against it.
+//! The intrinsics must appear in the probe only if the feature is enabled, which also shows that
+//! the check is able to find them.
+//!
+//! This requires the target to be installed with `rustup target add thumbv6m-none-eabi`,
+//! so the check is ignored by default and run with `cargo test --test float_free -- --ignored`.
+
+extern crate size_format;
+
+#[cfg(not(feature = "float"))]
+use size_format::{SizeFormatterBinary, SizeFormatterSI};
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+/// A target without a floating point unit.
+const SOFT_FLOAT_TARGET: &str = "thumbv6m-none-eabi";
+
+/// The names of the soft-float intrinsics of the compiler builtins.
+const SOFT_FLOAT_INTRINSICS: &[&str] = &[
+    "__adddf3",
+    "__addsf3",
+    "__subdf3",
+    "__subsf3",
+    "__muldf3",
+    "__mulsf3",
+    "__divdf3",
+    "__divsf3",
+    "__floatundidf",
+    "__floatuntidf",
+    "__fixunsdfdi",
+    "__fixunsdfti",
+    "__aeabi_dadd",
+    "__aeabi_dsub",
+    "__aeabi_dmul",
+    "__aeabi_ddiv",
+    "__aeabi_dcmplt",
+    "__aeabi_dcmpge",
+    "__aeabi_ui2d",
+    "__aeabi_ul2d",
+    "__aeabi_d2ulz",
+];
+
+/// Runs the command and panics with its output if it fails.
+fn run(command: &mut Command) {
+    let output = command.output().unwrap();
+
+    assert!(
+        output.status.success(),
+        "{:?} failed (is the {} target installed?):\n{}",
+        command,
+        SOFT_FLOAT_TARGET,
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+/// Returns the most recently built file in `dir` with the given prefix and extension.
+fn newest(dir: &Path, prefix: &str, extension: &str) -> PathBuf {
+    fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| {
+            let name = path.file_name().unwrap().to_string_lossy();
+            name.starts_with(prefix) && name.ends_with(extension)
+        })
+        .max_by_key(|path| fs::metadata(path).unwrap().modified().unwrap())
+        .unwrap_or_else(|| panic!("no {}*{} in {}", prefix, extension, dir.display()))
+}
+
+/// Builds the library for the soft-float target and returns the probe compiled against it.
+fn probe_object(float: bool) -> Vec<u8> {
+    let cargo = PathBuf::from(env!("CARGO"));
+    let rustc = env::var_os("RUSTC")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| cargo.with_file_name("rustc"));
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let name = if float { "float" } else { "no-float" };
+    let target_dir = Path::new(env!("CARGO_TARGET_TMPDIR"))
+        .join("float-check")
+        .join(name);
+
+    let mut build = Command::new(&cargo);
+    build
+        .current_dir(manifest_dir)
+        .args(["build", "--offline", "--lib", "--target", SOFT_FLOAT_TARGET])
+        .arg("--target-dir")
+        .arg(&target_dir);
+    if !float {
+        build.arg("--no-default-features");
+    }
+    run(&mut build);
+
+    let deps = target_dir
+        .join(SOFT_FLOAT_TARGET)
+        .join("debug")
+        .join("deps");
+    let library = newest(&deps, "libsize_format-", ".rlib");
+    let object = target_dir.join("probe.o");
+
+    let mut compile = Command::new(rustc);
+    compile
+        .arg(
+            manifest_dir
+                .join("tests")
+                .join("float_probe")
+                .join("probe.rs"),
+        )
+        .args(["--crate-type", "lib", "--target", SOFT_FLOAT_TARGET])
+        .arg("--emit")
+        .arg(format!("obj={}", object.display()))
+        .arg("--extern")
+        .arg(format!("size_format={}", library.display()))
+        .arg("-L")
+        .arg(format!("dependency={}", deps.display()));
+    if float {
+        compile.args(["--cfg", "float"]);
+    }
+    run(&mut compile);
+
+    fs::read(object).unwrap()
+}
+
+/// Returns the soft-float intrinsics referenced by the object.
+fn referenced_intrinsics(object: &[u8]) -> Vec<&'static str> {
+    SOFT_FLOAT_INTRINSICS
+        .iter()
+        .cloned()
+        .filter(|intrinsic| {
+            object
+                .windows(intrinsic.len())
+                .any(|window| window == intrinsic.as_bytes())
+        })
+        .collect()
+}
+
+#[test]
+#[ignore = "requires the thumbv6m-none-eabi target"]
+fn soft_float_intrinsics_only_with_float_feature() {
+    assert_eq!(
+        referenced_intrinsics(&probe_object(false)),
+        Vec::<&str>::new(),
+        "the library uses floating point without the float feature"
+    );
+    assert!(
+        !referenced_intrinsics(&probe_object(true)).is_empty(),
+        "the float feature does not show up, so the check cannot detect floating point"
+    );
+}
+
+#[test]
+#[cfg(not(feature = "float"))]
+fn formatting_works_without_float() {
+    assert_eq!(
+        format!("{}B", SizeFormatterSI::new(42_000_000)),
+        "42.0MB".to_string()
+    );
+    assert_eq!(
+        format!("{:.2}B", SizeFormatterBinary::new(1_234_567)),
+        "1.17MiB".to_string()
+    );
+}
@@ -0,0 +1,50 @@
+//! Instantiates the generic code of the library for `tests/float_free.rs`.
+//!
+//! The library itself contains almost no machine code, since nearly all of it is generic.
+//! Compiling this crate for a target without a floating point unit turns every floating point
+//! operation in the instantiated code into a call of a soft-float intrinsic.
+#![no_std]
+
+extern crate size_format;
+
+use core::fmt::{self, Write};
+use size_format::{
+    ByteSize, SizeFormatterBinary, SizeFormatterBinary128, SizeFormatterSI, SizeParser,
+};
+
+/// Discards everything written to it.
+struct Sink;
+
+impl Write for Sink {
+    fn write_str(&mut self, _: &str) -> fmt::Result {
+        Ok(())
+    }
+}
+
+/// Formats and parses sizes with the APIs that are available without the `float` feature.
+#[no_mangle]
+pub fn probe_integer(bytes: u64, wide: u128, text: &str) -> u64 {
+    let _ = write!(Sink, "{:.2}", SizeFormatterSI::new(bytes));
+    let _ = write!(Sink, "{:>10}", SizeFormatterBinary::new(bytes));
+    let _ = write!(Sink, "{:.30}", SizeFormatterBinary128::new(wide));
+
+    let parsed = text.parse::<ByteSize>().map(ByteSize::bytes).unwrap_or(0);
+    let strict = SizeParser::new()
+        .parse(text)
+        .map(ByteSize::bytes)
+        .unwrap_or(0);
+    parsed.wrapping_add(strict)
+}
+
+/// Uses the APIs of the `float` feature, which must show up as soft-float intrinsics.
+#[cfg(float)]
+#[no_mangle]
+pub fn probe_float(bytes: u64) -> f64 {
+    let formatter = SizeFormatterSI::new(bytes);
+    let sum = formatter
+        .representations()
+        .map(|(value, _)| value)
+        .fold(0.0, |sum, value| sum + value);
+
+    sum * formatter.scaled().value
+}
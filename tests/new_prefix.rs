@@ -1,19 +1,13 @@
-extern crate generic_array;
 extern crate size_format;
 
-use generic_array::{typenum::U3, GenericArray};
-use size_format::{PointSeparated, PrefixType, SizeFormatter};
+use size_format::{parse_prefixed, PointSeparated, PrefixType, SizeFormatter};
 
 struct Millimeter;
 
 impl PrefixType for Millimeter {
-    type N = U3;
-
     const PREFIX_SIZE: u32 = 1000;
 
-    fn prefixes() -> GenericArray<&'static str, Self::N> {
-        ["m", "", "k"].into()
-    }
+    const PREFIXES: &'static [&'static str] = &["m", "", "k"];
 }
 
 #[test]
@@ -47,3 +41,38 @@ fn new_prefix_works() {
         "10000.0km".to_string()
     );
 }
+
+#[test]
+fn new_prefix_parses() {
+    for &(input, millimeters) in &[
+        ("1mm", 1),
+        ("1m", 1_000),
+        ("1", 1_000),
+        ("1.0km", 1_000_000),
+        ("10000.0km", 10_000_000_000),
+        ("0.0015km", 1_500),
+        ("2k", 2_000_000),
+    ] {
+        assert_eq!(parse_prefixed::<Millimeter>(input, "m"), Ok(millimeters));
+    }
+
+    for input in &["1Mm", "1kmm", "km", "1 km"] {
+        assert!(parse_prefixed::<Millimeter>(input, "m").is_err());
+    }
+}
+
+#[test]
+fn new_prefix_parses_into_formatter() {
+    type Length = SizeFormatter<u32, Millimeter, PointSeparated>;
+
+    for &millimeters in &[1, 1_000, 1_500, 1_000_000, 4_000_000_000] {
+        let formatted = format!("{:.3}", Length::new(millimeters));
+        assert_eq!(formatted.parse::<Length>().unwrap().num(), millimeters);
+    }
+
+    assert_eq!(
+        Length::parse_with_unit("1.5km", "m").unwrap().num(),
+        1_500_000
+    );
+    assert!("5000k".parse::<Length>().is_err());
+}
@@ -9,3 +9,52 @@ fn std_works() {
         "8.5MB".to_string()
     );
 }
+
+#[test]
+#[cfg(feature = "std")]
+fn write_size_to_io() {
+    use size_format::{write_size, FormatOptions, Locale};
+    use std::io::{self, Write};
+
+    let mut output = Vec::new();
+    write_size(&mut output, 42 << 20, &FormatOptions::new()).unwrap();
+    output.write_all(b" ").unwrap();
+    let options = FormatOptions::new().with_locale(Locale::from_tag("de").unwrap());
+    write_size(&mut output, 1_234 << 50, &options).unwrap();
+    assert_eq!(output, "42.0MiB 1,2 EiB".as_bytes());
+
+    struct Full;
+
+    impl Write for Full {
+        fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+            Err(io::Error::new(io::ErrorKind::WriteZero, "full"))
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let error = write_size(&mut Full, 1, &FormatOptions::new()).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::WriteZero);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn human_size_of_files() {
+    use size_format::{ByteSize, HumanSize};
+    use std::{env, fs, io::Write};
+
+    let path = env::temp_dir().join(format!("size_format_human_size_{}", std::process::id()));
+    let mut file = fs::File::create(&path).unwrap();
+    file.write_all(&[0; 2_048]).unwrap();
+    file.sync_all().unwrap();
+
+    assert_eq!(file.human_size().unwrap(), ByteSize(2_048));
+    assert_eq!(
+        format!("{}", fs::metadata(&path).unwrap().human_size()),
+        "2.0KiB".to_string()
+    );
+
+    fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,27 @@
+#![cfg(feature = "std")]
+
+extern crate size_format;
+
+use size_format::TrackingAllocator;
+use std::alloc::System;
+
+#[global_allocator]
+static ALLOCATOR: TrackingAllocator = TrackingAllocator::new(System);
+
+#[test]
+fn tracks_allocations() {
+    let before = ALLOCATOR.in_use().bytes();
+    let total_before = ALLOCATOR.total().bytes();
+
+    let mut buffer = vec![0u8; 3 << 20];
+    assert!(ALLOCATOR.in_use().bytes() >= before + (3 << 20));
+    assert!(ALLOCATOR.peak().bytes() >= 3 << 20);
+
+    buffer.resize(5 << 20, 0);
+    assert!(ALLOCATOR.in_use().bytes() >= before + (5 << 20));
+    assert!(ALLOCATOR.total().bytes() >= total_before + (8 << 20));
+
+    drop(buffer);
+    assert!(ALLOCATOR.in_use().bytes() < before + (1 << 20));
+    assert!(format!("heap in use: {}", ALLOCATOR.in_use()).ends_with("B"));
+}